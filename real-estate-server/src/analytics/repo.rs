@@ -0,0 +1,146 @@
+use chrono::NaiveDateTime;
+use postgres::Row;
+
+use crate::{database::storage::Storage, error::app_error::DynAppError};
+
+// the area of every lot in m², measured by PostGIS on the spheroid. The ring
+// is closed by repeating its first point
+const LOT_AREA: &str = "
+    lot_line AS (
+        SELECT 
+            l.subdivision_id, l.l_name,
+            ST_MakeLine(ST_SetSRID(ST_MakePoint(al.long, al.lat), 4326) ORDER BY ll.position) as line
+        FROM 
+            lot l join lot_location ll on l.l_name = ll.l_name and l.subdivision_id = ll.subdivision_id 
+            join app_location al on al.id = ll.location_id
+        GROUP BY 
+            l.subdivision_id, l.l_name
+    ),
+    lot_area AS (
+        SELECT 
+            subdivision_id, l_name,
+            ST_Area(ST_MakePolygon(ST_AddPoint(line, ST_StartPoint(line)))::geography) as area
+        FROM 
+            lot_line
+        WHERE 
+            ST_NPoints(line) >= 3
+    )";
+
+// the reservations of every lot made in [$1, $2), and how many of them the lot
+// was sold after
+const LOT_RESERVATIONS: &str = "
+    lot_reservations AS (
+        SELECT 
+            r.subdivision_id, r.l_name,
+            count(r.id) as reservations,
+            count(r.id) filter (where sa.sold_at >= r.reserved_at) as converted
+        FROM 
+            reservation r left join sale sa on sa.subdivision_id = r.subdivision_id and sa.l_name = r.l_name
+        WHERE 
+            r.reserved_at >= $1 and r.reserved_at < $2
+        GROUP BY 
+            r.subdivision_id, r.l_name
+    )";
+
+#[derive(Clone)]
+pub struct AnalyticsRepo {
+    storage: Storage,
+}
+
+impl AnalyticsRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    // one row per subdivision plus one per organization, told apart by
+    // organization_total. Lot counts are the current ones, the sale and
+    // reservation figures only take the ones made in [from, to)
+    pub async fn get_totals(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        organization_ids: Option<Vec<String>>,
+        organization_id: Option<String>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = format!(
+            "
+            WITH {}, {}
+            SELECT 
+                o.id as organization_id, o.o_name, s.id as subdivision_id, s.s_name,
+                grouping(s.id) = 1 as organization_total,
+                count(l.l_name) as lots_amount,
+                count(sa.id) as sold_lots,
+                count(sa.id) filter (where sa.sold_at >= $1 and sa.sold_at < $2) as period_sales,
+                coalesce(sum(la.area) filter (where sa.sold_at >= $1 and sa.sold_at < $2), 0) as sold_area,
+                coalesce(sum(sa.price) filter (where sa.sold_at >= $1 and sa.sold_at < $2), 0) as revenue,
+                sum(sa.price) filter (where sa.sold_at >= $1 and sa.sold_at < $2)
+                    / nullif(sum(la.area) filter (where sa.sold_at >= $1 and sa.sold_at < $2), 0) as price_per_m2,
+                coalesce(sum(lr.reservations), 0)::bigint as reservations,
+                coalesce(sum(lr.converted), 0)::bigint as converted_reservations,
+                sum(lr.converted)::double precision / nullif(sum(lr.reservations), 0) as conversion_rate
+            FROM 
+                subdivision s join organization o on o.id = s.organization_id
+                left join lot l on l.subdivision_id = s.id
+                left join lot_area la on la.subdivision_id = l.subdivision_id and la.l_name = l.l_name
+                left join sale sa on sa.subdivision_id = l.subdivision_id and sa.l_name = l.l_name
+                left join lot_reservations lr on lr.subdivision_id = l.subdivision_id and lr.l_name = l.l_name
+            WHERE 
+                ($3::varchar[] is null or s.organization_id = ANY($3))
+                and ($4::varchar is null or s.organization_id = $4)
+            GROUP BY 
+                GROUPING SETS ((o.id, o.o_name, s.id, s.s_name), (o.id, o.o_name))
+            ORDER BY 
+                o.o_name, s.s_name nulls first;
+            ",
+            LOT_AREA, LOT_RESERVATIONS
+        );
+
+        self.storage
+            .query(cmd, &[&from, &to, &organization_ids, &organization_id])
+            .await
+    }
+
+    // sales of every month in [from, to), months without sales included, per
+    // subdivision and per organization like get_totals
+    pub async fn get_monthly_sales(
+        &self,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+        organization_ids: Option<Vec<String>>,
+        organization_id: Option<String>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            WITH months AS (
+                SELECT generate_series(
+                    date_trunc('month', $1::timestamp),
+                    $2::timestamp - interval '1 second',
+                    interval '1 month'
+                ) as month
+            )
+            SELECT 
+                s.organization_id, s.id as subdivision_id,
+                grouping(s.id) = 1 as organization_total,
+                to_char(m.month, 'YYYY-MM') as month,
+                count(sa.id) as sales,
+                coalesce(sum(sa.price), 0) as revenue
+            FROM 
+                subdivision s cross join months m
+                left join sale sa on sa.subdivision_id = s.id
+                    and date_trunc('month', sa.sold_at) = m.month
+                    and sa.sold_at >= $1 and sa.sold_at < $2
+            WHERE 
+                ($3::varchar[] is null or s.organization_id = ANY($3))
+                and ($4::varchar is null or s.organization_id = $4)
+            GROUP BY 
+                GROUPING SETS ((s.organization_id, s.id, m.month), (s.organization_id, m.month))
+            ORDER BY 
+                m.month;
+            ",
+        );
+
+        self.storage
+            .query(cmd, &[&from, &to, &organization_ids, &organization_id])
+            .await
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{Months, NaiveDate, Utc};
+use postgres::Row;
+
+use crate::{
+    api_contracts::{
+        analytics_params::AnalyticsParams,
+        analytics_report::{
+            AnalyticsReport, MonthlySales, OrganizationAnalytics, SalesTotals, SubdivisionAnalytics,
+        },
+    },
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::repo::AnalyticsRepo;
+
+// ten years of months is already more than any chart shows
+const MAX_MONTHS: u32 = 120;
+
+#[derive(Clone)]
+pub struct AnalyticsService {
+    repo: AnalyticsRepo,
+}
+
+impl AnalyticsService {
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            repo: AnalyticsRepo::new(storage),
+        }
+    }
+
+    pub async fn get_report(
+        &self,
+        params: AnalyticsParams,
+        scope: &TenantScope,
+    ) -> Result<AnalyticsReport, DynAppError> {
+        let to = params.to.unwrap_or(Utc::now().date_naive());
+        let from = match params.from {
+            Some(from) => from,
+            None => to
+                .checked_sub_months(Months::new(12))
+                .and_then(|date| date.succ_opt())
+                .unwrap_or(to),
+        };
+        check_range(from, to)?;
+
+        if let Some(organization_id) = &params.organization_id {
+            if !scope.includes(organization_id) {
+                return Err(Box::new(ApiError::NotFound(format!(
+                    "Organization {} not found",
+                    organization_id
+                ))));
+            }
+        }
+
+        // the range covers the whole of its last day
+        let start = from.and_hms_opt(0, 0, 0).unwrap_or_default();
+        let end = to
+            .succ_opt()
+            .unwrap_or(to)
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default();
+
+        let totals = self
+            .repo
+            .get_totals(
+                start,
+                end,
+                scope.organization_ids.clone(),
+                params.organization_id.clone(),
+            )
+            .await?;
+        let monthly_sales = self
+            .repo
+            .get_monthly_sales(
+                start,
+                end,
+                scope.organization_ids.clone(),
+                params.organization_id,
+            )
+            .await?;
+
+        let mut organizations: Vec<OrganizationAnalytics> = vec![];
+        let mut subdivisions: Vec<SubdivisionAnalytics> = vec![];
+        for row in totals.iter() {
+            let organization_id: String = row.get("organization_id");
+            if row.get::<&str, bool>("organization_total") {
+                organizations.push(OrganizationAnalytics {
+                    totals: to_sales_totals(row, months_of(&monthly_sales, &organization_id, None)),
+                    organization_id,
+                    name: row.get("o_name"),
+                });
+            } else {
+                let subdivision_id: String = row.get("subdivision_id");
+                subdivisions.push(SubdivisionAnalytics {
+                    totals: to_sales_totals(
+                        row,
+                        months_of(&monthly_sales, &organization_id, Some(&subdivision_id)),
+                    ),
+                    subdivision_id,
+                    name: row.get("s_name"),
+                    organization_id,
+                });
+            }
+        }
+
+        Ok(AnalyticsReport {
+            from,
+            to,
+            organizations,
+            subdivisions,
+        })
+    }
+}
+
+fn check_range(from: NaiveDate, to: NaiveDate) -> Result<(), DynAppError> {
+    let message = if from > to {
+        Some(String::from("The range must not end before it starts"))
+    } else if from
+        .checked_add_months(Months::new(MAX_MONTHS))
+        .is_some_and(|limit| to >= limit)
+    {
+        Some(format!(
+            "The range must not be longer than {} months",
+            MAX_MONTHS
+        ))
+    } else {
+        None
+    };
+
+    match message {
+        Some(message) => Err(Box::new(ApiError::BadRequest(message))),
+        None => Ok(()),
+    }
+}
+
+fn to_sales_totals(row: &Row, monthly_sales: Vec<MonthlySales>) -> SalesTotals {
+    let lots_amount: i64 = row.get("lots_amount");
+    let sold_lots: i64 = row.get("sold_lots");
+
+    SalesTotals {
+        lots_amount,
+        available_lots: lots_amount - sold_lots,
+        sold_lots,
+        period_sales: row.get("period_sales"),
+        sold_area: row.get("sold_area"),
+        revenue: row.get("revenue"),
+        average_price_per_m2: row.get("price_per_m2"),
+        reservations: row.get("reservations"),
+        converted_reservations: row.get("converted_reservations"),
+        reservation_conversion_rate: row.get("conversion_rate"),
+        monthly_sales,
+    }
+}
+
+// subdivision_id None picks the organization rows
+fn months_of(
+    rows: &[Row],
+    organization_id: &str,
+    subdivision_id: Option<&String>,
+) -> Vec<MonthlySales> {
+    rows.iter()
+        .filter(|row| {
+            row.get::<&str, String>("organization_id") == organization_id
+                && row.get::<&str, Option<String>>("subdivision_id").as_ref() == subdivision_id
+        })
+        .map(|row| MonthlySales {
+            month: row.get("month"),
+            sales: row.get("sales"),
+            revenue: row.get("revenue"),
+        })
+        .collect()
+}
@@ -0,0 +1,14 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AnalyticsParams {
+    // first day of the range, twelve months before `to` when not informed
+    pub from: Option<NaiveDate>,
+    // last day of the range, included, today when not informed
+    pub to: Option<NaiveDate>,
+    // restricts the report to one organization
+    pub organization_id: Option<String>,
+}
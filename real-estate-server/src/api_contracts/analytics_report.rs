@@ -0,0 +1,55 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct MonthlySales {
+    // such as 2024-03
+    pub month: String,
+    pub sales: i64,
+    pub revenue: f64,
+}
+
+// lot counts are the current ones, sale and reservation figures only cover the
+// report range
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct SalesTotals {
+    pub lots_amount: i64,
+    pub available_lots: i64,
+    pub sold_lots: i64,
+    pub period_sales: i64,
+    // in m²
+    pub sold_area: f64,
+    pub revenue: f64,
+    // None when nothing was sold in the range
+    pub average_price_per_m2: Option<f64>,
+    // reservations made in the range, and how many of them ended in a sale
+    pub reservations: i64,
+    pub converted_reservations: i64,
+    // converted_reservations / reservations, None without reservations
+    pub reservation_conversion_rate: Option<f64>,
+    pub monthly_sales: Vec<MonthlySales>,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct SubdivisionAnalytics {
+    pub subdivision_id: String,
+    pub name: String,
+    pub organization_id: String,
+    pub totals: SalesTotals,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct OrganizationAnalytics {
+    pub organization_id: String,
+    pub name: String,
+    pub totals: SalesTotals,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct AnalyticsReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub organizations: Vec<OrganizationAnalytics>,
+    pub subdivisions: Vec<SubdivisionAnalytics>,
+}
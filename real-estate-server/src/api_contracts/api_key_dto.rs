@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api_key::api_key::ApiKeyScope;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyCreationPayload {
+    pub name: String,
+    pub scope: ApiKeyScope,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyDto {
+    pub id: String,
+    pub organization_id: String,
+    pub name: String,
+    pub scope: ApiKeyScope,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    // only filled in the creation response
+    pub key: Option<String>,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuthTokenDto {
+    pub access_token: String,
+    pub token_type: String,
+    // seconds until the access token expires
+    pub expires_in: i64,
+    pub refresh_token: String,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct BlockDto {
+    // such as Q3, lots inside it are usually named Q3-L01, Q3-L02...
+    pub name: String,
+    // empty when the block has no polygon
+    #[serde(default)]
+    pub area: Box<Vec<(f64, f64)>>,
+    // EPSG code of the area, WGS84 when not informed
+    #[serde(default)]
+    pub crs: Option<String>,
+}
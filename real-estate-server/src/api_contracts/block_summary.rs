@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct BlockSummary {
+    pub name: String,
+    pub subdivision_id: String,
+    pub area: Box<Vec<(f64, f64)>>,
+    pub lots_amount: usize,
+    pub available_lots: usize,
+    pub sold_lots: usize,
+    // sum of the lot areas, in m²
+    pub lots_area: f64,
+    // sum of the asking prices of the priced lots
+    pub listed_price: f64,
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContractTemplateDto {
+    pub body: String,
+}
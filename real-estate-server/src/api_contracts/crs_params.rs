@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CrsParams {
+    // EPSG code to return the areas in, WGS84 when not informed
+    pub crs: Option<String>,
+}
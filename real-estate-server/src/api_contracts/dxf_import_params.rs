@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use crate::geometry::utm::Hemisphere;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DxfImportParams {
+    // layer holding the closed lot polylines
+    pub lot_layer: String,
+    // layer holding the lot names, as TEXT or MTEXT
+    pub label_layer: String,
+    // UTM zone of the drawing coordinates, 1 to 60
+    pub utm_zone: u8,
+    // south when not informed
+    pub hemisphere: Option<Hemisphere>,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use crate::export::export_format::ExportFormat;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportParams {
+    // GeoJSON when not informed
+    pub format: Option<ExportFormat>,
+}
@@ -0,0 +1,16 @@
+use geojson::FeatureCollection;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct GeoJsonImportPayload {
+    pub subdivision_id: String,
+    pub organization_id: Option<String>,
+    // property holding the subdivision and lot names, "name" by default
+    pub name_property: Option<String>,
+    // property set to "subdivision" on the boundary feature, "kind" by default
+    pub kind_property: Option<String>,
+    // a GeoJSON FeatureCollection
+    #[schema(value_type = Object)]
+    pub feature_collection: FeatureCollection,
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::import::draft::FeatureKind;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct FeatureReport {
+    // position of the feature in the imported file
+    pub index: usize,
+    pub kind: FeatureKind,
+    pub name: Option<String>,
+    // created, valid (nothing was saved because of other features) or invalid
+    pub status: String,
+    pub errors: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportReport {
+    pub subdivision_id: String,
+    pub created: bool,
+    pub errors: Vec<String>,
+    pub features: Vec<FeatureReport>,
+}
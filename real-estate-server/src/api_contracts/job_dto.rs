@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::job::job::{JobKind, JobStatus};
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobDto {
+    pub id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    // 0 to 100, moves as the job goes through its stages
+    pub progress: i32,
+    pub attempts: i32,
+    pub error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+    // where to download the exported file or the import report, once the job
+    // succeeded
+    pub result_url: Option<String>,
+}
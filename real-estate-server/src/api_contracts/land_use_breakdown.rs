@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct LandUseShare {
+    // lots, street, green_area, institutional, preservation or unassigned
+    pub land_use: String,
+    pub area_m2: f64,
+    // of the subdivision total area, from 0 to 100
+    pub percentage: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct LandUseBreakdown {
+    pub subdivision_id: String,
+    pub total_area_m2: f64,
+    pub uses: Vec<LandUseShare>,
+}
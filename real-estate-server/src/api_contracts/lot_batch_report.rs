@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LotBatchStatus {
+    Created,
+    // a lot with the same name already exists, usually saved by an earlier
+    // attempt of the same batch
+    Skipped,
+    Failed,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct LotBatchResult {
+    // position of the lot in the request
+    pub index: usize,
+    pub name: String,
+    pub status: LotBatchStatus,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct LotBatchReport {
+    pub subdivision_id: String,
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub lots: Vec<LotBatchResult>,
+}
@@ -1,9 +1,23 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Clone, Serialize, Deserialize)]
+use crate::subdivision::lot::LotStatus;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct LotDto {
     pub area: Box<Vec<(f64, f64)>>,
     pub id: String,
     pub name: String,
     pub subdivision_id: String,
+    #[serde(default)]
+    pub price: Option<f64>,
+    #[serde(default)]
+    pub block_name: Option<String>,
+    // filled on reads only, ignored when creating lots
+    #[serde(default)]
+    pub status: Option<LotStatus>,
+    // EPSG code of the area, WGS84 when not informed. UTM areas are
+    // (easting, northing) pairs
+    #[serde(default)]
+    pub crs: Option<String>,
 }
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct LotGridPayload {
+    // prefix of the lot names, "Q3" gives Q3-L01, Q3-L02...
+    pub block_name: String,
+    // the block polygon, it must fall inside the subdivision
+    pub area: Box<Vec<(f64, f64)>>,
+    // EPSG code of the area, WGS84 when not informed
+    #[serde(default)]
+    pub crs: Option<String>,
+    // target frontage in meters, lots get the closest width splitting the block evenly
+    pub frontage: f64,
+    // direction from the street into the lots, degrees clockwise from the north
+    pub depth_azimuth: f64,
+    // number of the first lot, 1 when not informed
+    pub first_number: Option<u32>,
+}
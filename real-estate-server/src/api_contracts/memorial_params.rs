@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use crate::memorial::memorial_format::MemorialFormat;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MemorialParams {
+    // plain text when not informed
+    pub format: Option<MemorialFormat>,
+}
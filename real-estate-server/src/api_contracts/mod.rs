@@ -1,4 +1,34 @@
+pub mod analytics_params;
+pub mod analytics_report;
+pub mod api_key_dto;
+pub mod auth_token_dto;
+pub mod block_dto;
+pub mod block_summary;
+pub mod contract_template_dto;
+pub mod login_payload;
+pub mod crs_params;
+pub mod dxf_import_params;
+pub mod export_params;
+pub mod geojson_import_payload;
+pub mod import_report;
+pub mod job_dto;
+pub mod land_use_breakdown;
+pub mod lot_batch_report;
 pub mod lot_dto;
+pub mod lot_grid_payload;
+pub mod memorial_params;
+pub mod organization_dto;
+pub mod organization_member_payload;
+pub mod plat_params;
+pub mod problem_details;
+pub mod refresh_token_payload;
+pub mod role_update_payload;
+pub mod sale_dto;
+pub mod sales_table_params;
 pub mod search_subdivision_params;
+pub mod shapefile_import_params;
+pub mod spreadsheet_import_params;
+pub mod site_feature_dto;
 pub mod subdivision_dto;
-pub mod subdivision_preview;
\ No newline at end of file
+pub mod subdivision_preview;
+pub mod subscription_payload;
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PlatParams {
+    // name of the lot to highlight
+    pub selected_lot: Option<String>,
+    // in pixels, 1200 when not informed
+    pub width: Option<u32>,
+}
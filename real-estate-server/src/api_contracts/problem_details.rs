@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::error::api_error::FieldError;
+
+// body of every error response, following RFC 9457 (application/problem+json)
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProblemDetails {
+    // always about:blank, `code` tells the kinds of problems apart
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+}
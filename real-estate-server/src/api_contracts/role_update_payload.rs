@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::role::Role;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct RoleUpdatePayload {
+    pub role: Role,
+}
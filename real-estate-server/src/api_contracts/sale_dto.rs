@@ -0,0 +1,20 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct InstallmentDto {
+    pub due_date: NaiveDate,
+    pub amount: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct SaleDto {
+    pub id: Option<String>,
+    pub subdivision_id: String,
+    pub lot_name: String,
+    pub buyer_name: String,
+    pub buyer_document: String,
+    pub price: f64,
+    pub installments: Vec<InstallmentDto>,
+}
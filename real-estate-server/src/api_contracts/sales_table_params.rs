@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SalesTableParams {
+    // every lot when not informed
+    pub available_only: Option<bool>,
+}
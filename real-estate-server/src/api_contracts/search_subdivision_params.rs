@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchSubdivisionParams {
     pub name: Option<String>,
     pub lat: Option<f64>,
     pub long: Option<f64>,
+    // EPSG code to return the areas in, WGS84 when not informed
+    pub crs: Option<String>,
 }
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ShapefileImportParams {
+    pub subdivision_id: String,
+    pub organization_id: Option<String>,
+    // attribute holding the subdivision and lot names, NAME by default
+    pub name_field: Option<String>,
+    // attribute set to "subdivision" on the boundary, KIND by default
+    pub kind_field: Option<String>,
+    // attribute holding the lot prices, lots are left unpriced without it
+    pub price_field: Option<String>,
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::site_feature::site_feature::{GeometryKind, LandUse};
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct SiteFeatureDto {
+    // generated by the server, ignored on creation
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    pub land_use: LandUse,
+    pub geometry_kind: GeometryKind,
+    pub coordinates: Box<Vec<(f64, f64)>>,
+    // in meters, turns a line string into an area, such as a street center line
+    #[serde(default)]
+    pub width: Option<f64>,
+    // in m², filled in on reads
+    #[serde(default)]
+    pub area_m2: Option<f64>,
+    // EPSG code of the coordinates, WGS84 when not informed
+    #[serde(default)]
+    pub crs: Option<String>,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SpreadsheetImportParams {
+    // creates the valid rows even when others fail, off when not informed
+    pub partial: Option<bool>,
+    // EPSG code of the coordinates column, WGS84 when not informed
+    pub crs: Option<String>,
+}
@@ -1,11 +1,18 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::lot_dto::LotDto;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct SubdivisionDto {
     pub id: String,
     pub name: String,
     pub area: Box<Vec<(f64, f64)>>,
     pub lots: Option<Box<Vec<LotDto>>>,
+    // required when the creator belongs to more than one organization
+    pub organization_id: Option<String>,
+    // EPSG code of the area, WGS84 when not informed. UTM areas are
+    // (easting, northing) pairs
+    #[serde(default)]
+    pub crs: Option<String>,
 }
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
 pub struct SubdivisionPreview {
     pub id: String,
     pub name: String,
     pub lots_amount: i64,
+    pub organization_id: String,
 }
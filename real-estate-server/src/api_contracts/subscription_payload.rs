@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct SubscriptionPayload {
+    pub name: String,
+    pub username: String,
+    pub password: String,
+}
@@ -0,0 +1,50 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::role::Role;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl ApiKeyScope {
+    pub fn value_str(&self) -> &'static str {
+        match *self {
+            ApiKeyScope::ReadOnly => "read_only",
+            ApiKeyScope::ReadWrite => "read_write",
+        }
+    }
+
+    pub fn from_value_str(value: &str) -> Option<ApiKeyScope> {
+        match value {
+            "read_only" => Some(ApiKeyScope::ReadOnly),
+            "read_write" => Some(ApiKeyScope::ReadWrite),
+            _ => None,
+        }
+    }
+
+    // keys act with the permissions of the role matching their scope, except for
+    // the administrative ones, which keys never get
+    pub fn role(&self) -> Role {
+        match *self {
+            ApiKeyScope::ReadOnly => Role::Buyer,
+            ApiKeyScope::ReadWrite => Role::Developer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiKey {
+    pub id: String,
+    pub organization_id: String,
+    pub name: String,
+    pub scope: ApiKeyScope,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+}
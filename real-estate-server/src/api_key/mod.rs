@@ -0,0 +1,3 @@
+pub mod api_key;
+mod repo;
+pub mod service;
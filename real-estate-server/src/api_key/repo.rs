@@ -0,0 +1,104 @@
+use postgres::Row;
+
+use crate::{database::storage::Storage, error::app_error::DynAppError};
+
+use super::api_key::{ApiKey, ApiKeyScope};
+
+#[derive(Clone)]
+pub struct ApiKeyRepo {
+    storage: Storage,
+}
+
+impl ApiKeyRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn create(&self, api_key: ApiKey, key_hash: String) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                api_key
+                    (id, organization_id, k_name, key_hash, k_scope, expires_at, created_at)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7);",
+        );
+
+        self.storage
+            .exec(
+                cmd,
+                &[
+                    &api_key.id,
+                    &api_key.organization_id,
+                    &api_key.name,
+                    &key_hash,
+                    &api_key.scope.value_str().to_string(),
+                    &api_key.expires_at,
+                    &api_key.created_at,
+                ],
+            )
+            .await
+    }
+
+    pub async fn get_by_organization(
+        &self,
+        organization_id: String,
+    ) -> Result<Vec<ApiKey>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT *
+            FROM
+                api_key
+            WHERE
+                organization_id = $1
+            ORDER BY
+                created_at;",
+        );
+
+        let rows = self.storage.query(cmd, &[&organization_id]).await?;
+
+        Ok(rows.iter().map(api_key_from_row).collect())
+    }
+
+    // finds a usable key by its hash, recording the use in the same statement
+    pub async fn touch(&self, key_hash: String) -> Result<Option<ApiKey>, DynAppError> {
+        let cmd = String::from(
+            "
+            UPDATE api_key
+            SET last_used_at = now()
+            WHERE
+                key_hash = $1
+                and revoked_at is null
+                and (expires_at is null or expires_at > now())
+            RETURNING *;",
+        );
+
+        let rows = self.storage.query(cmd, &[&key_hash]).await?;
+
+        Ok(rows.first().map(api_key_from_row))
+    }
+
+    pub async fn revoke(&self, organization_id: String, id: String) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE api_key
+            SET revoked_at = now()
+            WHERE organization_id = $1 and id = $2 and revoked_at is null",
+        );
+
+        self.storage.exec(cmd, &[&organization_id, &id]).await
+    }
+}
+
+fn api_key_from_row(row: &Row) -> ApiKey {
+    let scope: String = row.get("k_scope");
+    ApiKey {
+        id: row.get("id"),
+        organization_id: row.get("organization_id"),
+        name: row.get("k_name"),
+        // unknown values fall back to the narrowest scope
+        scope: ApiKeyScope::from_value_str(&scope).unwrap_or(ApiKeyScope::ReadOnly),
+        expires_at: row.get("expires_at"),
+        last_used_at: row.get("last_used_at"),
+        created_at: row.get("created_at"),
+        revoked_at: row.get("revoked_at"),
+    }
+}
@@ -0,0 +1,145 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    api_contracts::api_key_dto::{ApiKeyCreationPayload, ApiKeyDto},
+    auth::{
+        token::{hash_token, random_token},
+        user::User,
+    },
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::{api_key::ApiKey, repo::ApiKeyRepo};
+
+// prefix making leaked keys easy to recognize, e.g. by secret scanners
+const KEY_PREFIX: &str = "rek_";
+
+#[derive(Clone)]
+pub struct ApiKeyService {
+    repo: ApiKeyRepo,
+}
+
+impl ApiKeyService {
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            repo: ApiKeyRepo::new(storage),
+        }
+    }
+
+    // the plain key is only ever returned here, the server keeps its hash
+    pub async fn create(
+        &self,
+        organization_id: String,
+        payload: ApiKeyCreationPayload,
+        scope: &TenantScope,
+    ) -> Result<ApiKeyDto, DynAppError> {
+        check_organization(&organization_id, scope)?;
+
+        let now = Utc::now().naive_utc();
+        if let Some(expires_at) = payload.expires_at {
+            if expires_at <= now {
+                return Err(Box::new(ApiError::validation(
+                    "expires_at",
+                    String::from("The expiration date must be in the future"),
+                )));
+            }
+        }
+
+        let key = format!("{}{}", KEY_PREFIX, random_token());
+        let api_key = ApiKey {
+            id: Uuid::new_v4().to_string(),
+            organization_id,
+            name: payload.name,
+            scope: payload.scope,
+            expires_at: payload.expires_at,
+            last_used_at: None,
+            created_at: now,
+            revoked_at: None,
+        };
+
+        self.repo.create(api_key.clone(), hash_token(&key)).await?;
+
+        let mut dto = to_dto(api_key);
+        dto.key = Some(key);
+        Ok(dto)
+    }
+
+    pub async fn get_by_organization(
+        &self,
+        organization_id: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<ApiKeyDto>, DynAppError> {
+        check_organization(&organization_id, scope)?;
+
+        let api_keys = self.repo.get_by_organization(organization_id).await?;
+        Ok(api_keys.into_iter().map(to_dto).collect())
+    }
+
+    pub async fn revoke(
+        &self,
+        organization_id: String,
+        id: String,
+        scope: &TenantScope,
+    ) -> Result<(), DynAppError> {
+        check_organization(&organization_id, scope)?;
+
+        if self.repo.revoke(organization_id, id.clone()).await? != 1 {
+            return Err(Box::new(ApiError::NotFound(format!(
+                "Active API key {} not found",
+                id
+            ))));
+        }
+
+        Ok(())
+    }
+
+    // resolves the key into the principal handlers see, restricted to the key's
+    // organization and to the role matching its scope
+    pub async fn authorize(&self, key: String) -> Result<User, DynAppError> {
+        let api_key = match self.repo.touch(hash_token(key.trim())).await? {
+            Some(api_key) => api_key,
+            None => {
+                return Err(Box::new(ApiError::Unauthorized(String::from(
+                    "Invalid, expired or revoked API key",
+                ))))
+            }
+        };
+
+        Ok(User {
+            id: format!("api-key:{}", api_key.id),
+            name: api_key.name,
+            username: String::new(),
+            role: api_key.scope.role(),
+            organization_ids: vec![api_key.organization_id],
+            api_key_id: Some(api_key.id),
+        })
+    }
+}
+
+fn check_organization(organization_id: &str, scope: &TenantScope) -> Result<(), DynAppError> {
+    if scope.includes(organization_id) {
+        return Ok(());
+    }
+
+    Err(Box::new(ApiError::NotFound(format!(
+        "Organization {} not found",
+        organization_id
+    ))))
+}
+
+fn to_dto(api_key: ApiKey) -> ApiKeyDto {
+    ApiKeyDto {
+        id: api_key.id,
+        organization_id: api_key.organization_id,
+        name: api_key.name,
+        scope: api_key.scope,
+        expires_at: api_key.expires_at,
+        last_used_at: api_key.last_used_at,
+        created_at: api_key.created_at,
+        revoked_at: api_key.revoked_at,
+        key: None,
+    }
+}
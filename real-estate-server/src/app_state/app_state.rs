@@ -1,6 +1,15 @@
 use crate::{
-    database::storage::Storage, location::service::LocationService,
+    analytics::service::AnalyticsService, api_key::service::ApiKeyService,
+    auth::service::AuthService, block::service::BlockService,
+    contract::service::ContractService, database::storage::Storage,
+    export::service::ExportService, idempotency::service::IdempotencyService,
+    import::service::ImportService, job::service::JobService,
+    location::service::LocationService, lot_grid::service::LotGridService,
+    memorial::service::MemorialService, organization::service::OrganizationService,
+    plat::service::PlatService, sale::service::SaleService,
+    sales_table::service::SalesTableService, site_feature::service::SiteFeatureService,
     subdivision::service::SubdivisionService,
+    tile::{service::TileService, tile_cache::TileCache},
 };
 
 #[derive(Clone)]
@@ -8,10 +17,33 @@ pub struct AppState {
     pub subdivision_service: SubdivisionService,
     pub storage: Storage,
     pub location_service: LocationService,
+    pub contract_service: ContractService,
+    pub sale_service: SaleService,
+    pub auth_service: AuthService,
+    pub organization_service: OrganizationService,
+    pub api_key_service: ApiKeyService,
+    pub import_service: ImportService,
+    pub export_service: ExportService,
+    pub memorial_service: MemorialService,
+    pub lot_grid_service: LotGridService,
+    pub block_service: BlockService,
+    pub site_feature_service: SiteFeatureService,
+    pub tile_service: TileService,
+    pub plat_service: PlatService,
+    pub sales_table_service: SalesTableService,
+    pub analytics_service: AnalyticsService,
+    pub idempotency_service: IdempotencyService,
+    pub job_service: JobService,
 }
 
+// HS256 keys shorter than its 256 bit hash are easier to brute force
+const MIN_JWT_SECRET_LENGTH: usize = 32;
+
 impl AppState {
-    pub fn new() -> Self {
+    // fails when the environment misses something the server cannot run without
+    pub fn new() -> Result<Self, String> {
+        let jwt_secret = jwt_secret(std::env::var("REAL_ESTATE_JWT_SECRET").ok())?;
+
         let storage = Storage::new(
             String::from("localhost"),
             String::from("postgres"),
@@ -19,13 +51,94 @@ impl AppState {
             String::from("postgres"),
         );
         let location_service = LocationService::new(storage.clone());
-        let subdivision_service =
-            SubdivisionService::new(storage.clone(), location_service.clone());
+        let tile_cache = TileCache::new();
+        let subdivision_service = SubdivisionService::new(
+            storage.clone(),
+            location_service.clone(),
+            tile_cache.clone(),
+        );
+        let tile_service = TileService::new(storage.clone(), tile_cache.clone());
+        let contract_service = ContractService::new(storage.clone(), subdivision_service.clone());
+        let sale_service = SaleService::new(
+            storage.clone(),
+            subdivision_service.clone(),
+            contract_service.clone(),
+        );
+        let auth_service = AuthService::new(storage.clone(), jwt_secret);
+        let organization_service = OrganizationService::new(storage.clone());
+        let api_key_service = ApiKeyService::new(storage.clone());
+        let import_service = ImportService::new(subdivision_service.clone());
+        let export_service = ExportService::new(subdivision_service.clone());
+        let memorial_service = MemorialService::new(subdivision_service.clone());
+        let block_service = BlockService::new(storage.clone(), subdivision_service.clone());
+        let site_feature_service =
+            SiteFeatureService::new(storage.clone(), subdivision_service.clone());
+        let plat_service = PlatService::new(
+            subdivision_service.clone(),
+            block_service.clone(),
+            site_feature_service.clone(),
+        );
+        let sales_table_service = SalesTableService::new(subdivision_service.clone());
+        let analytics_service = AnalyticsService::new(storage.clone());
+        let idempotency_service = IdempotencyService::new(storage.clone());
+        let job_service = JobService::new(
+            storage.clone(),
+            import_service.clone(),
+            export_service.clone(),
+            sales_table_service.clone(),
+        );
+        let lot_grid_service =
+            LotGridService::new(subdivision_service.clone(), block_service.clone());
 
-        Self {
+        Ok(Self {
             storage: storage.clone(),
             location_service: location_service.clone(),
             subdivision_service: subdivision_service.clone(),
-        }
+            contract_service: contract_service.clone(),
+            sale_service: sale_service.clone(),
+            auth_service: auth_service.clone(),
+            organization_service: organization_service.clone(),
+            api_key_service: api_key_service.clone(),
+            import_service: import_service.clone(),
+            export_service: export_service.clone(),
+            memorial_service: memorial_service.clone(),
+            lot_grid_service: lot_grid_service.clone(),
+            block_service: block_service.clone(),
+            site_feature_service: site_feature_service.clone(),
+            tile_service: tile_service.clone(),
+            plat_service: plat_service.clone(),
+            sales_table_service: sales_table_service.clone(),
+            analytics_service: analytics_service.clone(),
+            idempotency_service: idempotency_service.clone(),
+            job_service: job_service.clone(),
+        })
+    }
+}
+
+// every environment sets its own secret, tokens signed with a known one could
+// be forged by anyone
+fn jwt_secret(value: Option<String>) -> Result<String, String> {
+    match value {
+        None => Err(String::from("REAL_ESTATE_JWT_SECRET is not set")),
+        Some(secret) if secret.len() < MIN_JWT_SECRET_LENGTH => Err(format!(
+            "REAL_ESTATE_JWT_SECRET must have at least {} characters",
+            MIN_JWT_SECRET_LENGTH
+        )),
+        Some(secret) => Ok(secret),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_a_long_enough_jwt_secret() {
+        assert!(jwt_secret(None).is_err());
+        assert!(jwt_secret(Some(String::new())).is_err());
+        assert!(jwt_secret(Some("s".repeat(MIN_JWT_SECRET_LENGTH - 1))).is_err());
+
+        let secret = "s".repeat(MIN_JWT_SECRET_LENGTH);
+        assert_eq!(jwt_secret(Some(secret.clone())), Ok(secret));
     }
 }
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Claims {
+    // user id
+    pub sub: String,
+    pub username: String,
+    // session the token was issued for, checked so revoked sessions lose access
+    // before their access tokens expire
+    pub sid: String,
+    pub iat: i64,
+    pub exp: i64,
+}
@@ -0,0 +1,91 @@
+use std::marker::PhantomData;
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts, response::Response};
+
+use crate::{error::api_error::ApiError, handlers::get_error_response};
+
+use super::{role::Permission, user::User};
+
+pub trait RequiredPermission {
+    const PERMISSION: Permission;
+}
+
+pub struct ViewSubdivisions;
+pub struct ManageSubdivisions;
+pub struct ManageContractTemplates;
+pub struct RecordSales;
+pub struct ViewContracts;
+pub struct ManageUsers;
+pub struct ManageOrganizations;
+pub struct ManageApiKeys;
+
+impl RequiredPermission for ViewSubdivisions {
+    const PERMISSION: Permission = Permission::ViewSubdivisions;
+}
+
+impl RequiredPermission for ManageSubdivisions {
+    const PERMISSION: Permission = Permission::ManageSubdivisions;
+}
+
+impl RequiredPermission for ManageContractTemplates {
+    const PERMISSION: Permission = Permission::ManageContractTemplates;
+}
+
+impl RequiredPermission for RecordSales {
+    const PERMISSION: Permission = Permission::RecordSales;
+}
+
+impl RequiredPermission for ViewContracts {
+    const PERMISSION: Permission = Permission::ViewContracts;
+}
+
+impl RequiredPermission for ManageUsers {
+    const PERMISSION: Permission = Permission::ManageUsers;
+}
+
+impl RequiredPermission for ManageOrganizations {
+    const PERMISSION: Permission = Permission::ManageOrganizations;
+}
+
+impl RequiredPermission for ManageApiKeys {
+    const PERMISSION: Permission = Permission::ManageApiKeys;
+}
+
+// extracts the user set by the auth layer, rejecting the request with a 403 when
+// its role lacks the permission P, e.g. `Authorized(user, _): Authorized<RecordSales>`
+pub struct Authorized<P: RequiredPermission>(pub User, pub PhantomData<P>);
+
+#[async_trait]
+impl<S, P> FromRequestParts<S> for Authorized<P>
+where
+    S: Send + Sync,
+    P: RequiredPermission,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user = match parts.extensions.get::<User>() {
+            Some(user) => user.clone(),
+            None => {
+                return Err(get_error_response(Box::new(ApiError::Unauthorized(
+                    String::from("Authentication required"),
+                ))))
+            }
+        };
+
+        if user.api_key_id.is_some() && P::PERMISSION.is_administrative() {
+            return Err(get_error_response(Box::new(ApiError::Forbidden(
+                String::from("API keys cannot perform this operation"),
+            ))));
+        }
+
+        if !user.role.has_permission(P::PERMISSION) {
+            return Err(get_error_response(Box::new(ApiError::Forbidden(format!(
+                "The {} role is not allowed to perform this operation",
+                user.role.value_str()
+            )))));
+        }
+
+        Ok(Authorized(user, PhantomData))
+    }
+}
@@ -1 +1,8 @@
-
+pub mod claims;
+pub mod guard;
+mod repo;
+pub mod role;
+pub mod service;
+pub mod session;
+pub mod token;
+pub mod user;
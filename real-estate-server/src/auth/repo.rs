@@ -0,0 +1,239 @@
+use postgres::Row;
+
+use crate::{
+    database::storage::{SqlParam, Storage},
+    error::app_error::DynAppError,
+};
+
+use super::{
+    role::Role,
+    session::{RefreshToken, Session},
+    user::User,
+};
+
+#[derive(Clone)]
+pub struct AuthRepo {
+    storage: Storage,
+}
+
+impl AuthRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn create_user(&self, user: User, password_hash: String) -> Result<u64, DynAppError> {
+        let statements: Vec<(String, Vec<SqlParam>)> = vec![
+            (
+                String::from(
+                    "INSERT INTO
+                        app_user
+                            (id, uname, u_role)
+                    VALUES
+                        ($1, $2, $3);",
+                ),
+                vec![
+                    Box::new(user.id.clone()),
+                    Box::new(user.name),
+                    Box::new(user.role.value_str().to_string()),
+                ],
+            ),
+            (
+                String::from(
+                    "INSERT INTO
+                        credentials
+                            (passwd, username, user_id)
+                    VALUES
+                        ($1, $2, $3);",
+                ),
+                vec![
+                    Box::new(password_hash),
+                    Box::new(user.username),
+                    Box::new(user.id),
+                ],
+            ),
+        ];
+
+        self.storage.transaction(statements).await
+    }
+
+    pub async fn get_credentials(&self, username: String) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT
+                u.id, u.uname, u.u_role, c.username, c.passwd,
+                array_remove(array_agg(om.organization_id), null) as organization_ids
+            FROM
+                credentials c
+                join app_user u on u.id = c.user_id
+                left join organization_member om on om.user_id = u.id
+            WHERE
+                c.username = $1
+            GROUP BY
+                u.id, u.uname, u.u_role, c.username, c.passwd;",
+        );
+
+        self.storage.query(cmd, &[&username]).await
+    }
+
+    pub async fn get_user(&self, id: String) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT
+                u.id, u.uname, u.u_role, c.username,
+                array_remove(array_agg(om.organization_id), null) as organization_ids
+            FROM
+                app_user u
+                join credentials c on u.id = c.user_id
+                left join organization_member om on om.user_id = u.id
+            WHERE
+                u.id = $1
+            GROUP BY
+                u.id, u.uname, u.u_role, c.username;",
+        );
+
+        self.storage.query(cmd, &[&id]).await
+    }
+
+    pub async fn create_session(
+        &self,
+        session: Session,
+        refresh_token: RefreshToken,
+    ) -> Result<u64, DynAppError> {
+        let statements: Vec<(String, Vec<SqlParam>)> = vec![
+            (
+                String::from(
+                    "INSERT INTO
+                        auth_session
+                            (id, user_id, created_at)
+                    VALUES
+                        ($1, $2, now());",
+                ),
+                vec![Box::new(session.id), Box::new(session.user_id)],
+            ),
+            (
+                String::from(
+                    "INSERT INTO
+                        refresh_token
+                            (token_hash, session_id, expires_at, created_at)
+                    VALUES
+                        ($1, $2, $3, now());",
+                ),
+                vec![
+                    Box::new(refresh_token.token_hash),
+                    Box::new(refresh_token.session_id),
+                    Box::new(refresh_token.expires_at),
+                ],
+            ),
+        ];
+
+        self.storage.transaction(statements).await
+    }
+
+    pub async fn get_refresh_token(
+        &self,
+        token_hash: String,
+    ) -> Result<Option<RefreshToken>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT *
+            FROM
+                refresh_token
+            WHERE
+                token_hash = $1;",
+        );
+
+        let rows = self.storage.query(cmd, &[&token_hash]).await?;
+
+        Ok(rows.first().map(|row| RefreshToken {
+            token_hash: row.get("token_hash"),
+            session_id: row.get("session_id"),
+            expires_at: row.get("expires_at"),
+            used_at: row.get("used_at"),
+        }))
+    }
+
+    pub async fn get_session(&self, id: String) -> Result<Option<Session>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT *
+            FROM
+                auth_session
+            WHERE
+                id = $1;",
+        );
+
+        let rows = self.storage.query(cmd, &[&id]).await?;
+
+        Ok(rows.first().map(|row| Session {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            revoked_at: row.get("revoked_at"),
+        }))
+    }
+
+    // marks the token as used, returning false when it already was. The check
+    // and the update happen in one statement so two concurrent refreshes with
+    // the same token cannot both succeed.
+    pub async fn mark_refresh_token_used(&self, token_hash: String) -> Result<bool, DynAppError> {
+        let cmd = String::from(
+            "UPDATE refresh_token
+            SET used_at = now()
+            WHERE token_hash = $1 and used_at is null",
+        );
+
+        Ok(self.storage.exec(cmd, &[&token_hash]).await? == 1)
+    }
+
+    pub async fn save_refresh_token(&self, refresh_token: RefreshToken) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                refresh_token
+                    (token_hash, session_id, expires_at, created_at)
+                VALUES
+                    ($1, $2, $3, now());",
+        );
+
+        self.storage
+            .exec(
+                cmd,
+                &[
+                    &refresh_token.token_hash,
+                    &refresh_token.session_id,
+                    &refresh_token.expires_at,
+                ],
+            )
+            .await
+    }
+
+    pub async fn revoke_session(&self, id: String) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE auth_session
+            SET revoked_at = now()
+            WHERE id = $1 and revoked_at is null",
+        );
+
+        self.storage.exec(cmd, &[&id]).await
+    }
+
+    pub async fn revoke_user_sessions(&self, user_id: String) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE auth_session
+            SET revoked_at = now()
+            WHERE user_id = $1 and revoked_at is null",
+        );
+
+        self.storage.exec(cmd, &[&user_id]).await
+    }
+
+    pub async fn update_role(&self, user_id: String, role: Role) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE app_user
+            SET u_role = $1
+            WHERE id = $2",
+        );
+
+        self.storage
+            .exec(cmd, &[&role.value_str().to_string(), &user_id])
+            .await
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Admin,
+    Developer,
+    Broker,
+    // read only, the role every self-registered user starts with
+    Buyer,
+}
+
+impl Role {
+    pub fn value_str(&self) -> &'static str {
+        match *self {
+            Role::Admin => "admin",
+            Role::Developer => "developer",
+            Role::Broker => "broker",
+            Role::Buyer => "buyer",
+        }
+    }
+
+    pub fn from_value_str(value: &str) -> Option<Role> {
+        match value {
+            "admin" => Some(Role::Admin),
+            "developer" => Some(Role::Developer),
+            "broker" => Some(Role::Broker),
+            "buyer" => Some(Role::Buyer),
+            _ => None,
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::ViewSubdivisions => true,
+            Permission::ManageSubdivisions
+            | Permission::ManageContractTemplates
+            | Permission::ManageApiKeys => matches!(*self, Role::Admin | Role::Developer),
+            Permission::RecordSales | Permission::ViewContracts => {
+                matches!(*self, Role::Admin | Role::Developer | Role::Broker)
+            }
+            Permission::ManageUsers | Permission::ManageOrganizations => *self == Role::Admin,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Permission {
+    ViewSubdivisions,
+    ManageSubdivisions,
+    ManageContractTemplates,
+    RecordSales,
+    ViewContracts,
+    ManageUsers,
+    ManageOrganizations,
+    ManageApiKeys,
+}
+
+impl Permission {
+    // permissions over users and credentials, never granted to API keys whatever
+    // their scope
+    pub fn is_administrative(&self) -> bool {
+        matches!(
+            *self,
+            Permission::ManageUsers | Permission::ManageOrganizations | Permission::ManageApiKeys
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Permission, Role};
+
+    const PERMISSIONS: [Permission; 8] = [
+        Permission::ViewSubdivisions,
+        Permission::ManageSubdivisions,
+        Permission::ManageContractTemplates,
+        Permission::RecordSales,
+        Permission::ViewContracts,
+        Permission::ManageUsers,
+        Permission::ManageOrganizations,
+        Permission::ManageApiKeys,
+    ];
+
+    fn assert_permissions(role: Role, granted: &[Permission]) {
+        for permission in PERMISSIONS {
+            assert_eq!(
+                role.has_permission(permission),
+                granted.contains(&permission),
+                "{:?} with {:?}",
+                role,
+                permission
+            );
+        }
+    }
+
+    #[test]
+    fn admin_has_every_permission() {
+        assert_permissions(Role::Admin, &PERMISSIONS);
+    }
+
+    #[test]
+    fn developer_manages_its_subdivisions_but_not_users() {
+        assert_permissions(
+            Role::Developer,
+            &[
+                Permission::ViewSubdivisions,
+                Permission::ManageSubdivisions,
+                Permission::ManageContractTemplates,
+                Permission::RecordSales,
+                Permission::ViewContracts,
+                Permission::ManageApiKeys,
+            ],
+        );
+    }
+
+    #[test]
+    fn broker_sells_but_does_not_manage_subdivisions() {
+        assert_permissions(
+            Role::Broker,
+            &[
+                Permission::ViewSubdivisions,
+                Permission::RecordSales,
+                Permission::ViewContracts,
+            ],
+        );
+    }
+
+    #[test]
+    fn buyer_only_views_subdivisions() {
+        assert_permissions(Role::Buyer, &[Permission::ViewSubdivisions]);
+    }
+
+    #[test]
+    fn roles_round_trip_through_their_stored_value() {
+        for role in [Role::Admin, Role::Developer, Role::Broker, Role::Buyer] {
+            assert_eq!(Role::from_value_str(role.value_str()), Some(role));
+        }
+        assert_eq!(Role::from_value_str("superuser"), None);
+    }
+
+    #[test]
+    fn only_credential_management_is_administrative() {
+        for permission in PERMISSIONS {
+            assert_eq!(
+                permission.is_administrative(),
+                matches!(
+                    permission,
+                    Permission::ManageUsers
+                        | Permission::ManageOrganizations
+                        | Permission::ManageApiKeys
+                ),
+                "{:?}",
+                permission
+            );
+        }
+    }
+}
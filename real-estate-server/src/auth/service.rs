@@ -0,0 +1,315 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, SaltString},
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use postgres::Row;
+use uuid::Uuid;
+
+use crate::{
+    api_contracts::{
+        auth_token_dto::AuthTokenDto, login_payload::LoginPayload,
+        subscription_payload::SubscriptionPayload,
+    },
+    database::storage::Storage,
+    error::{
+        api_error::{ApiError, FieldError},
+        app_error::DynAppError,
+    },
+};
+
+use super::{
+    claims::Claims,
+    repo::AuthRepo,
+    role::Role,
+    session::{RefreshToken, Session},
+    token::{hash_token, random_token},
+    user::User,
+};
+
+const ACCESS_TOKEN_TTL_SECONDS: i64 = 15 * 60;
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+#[derive(Clone)]
+pub struct AuthService {
+    repo: AuthRepo,
+    jwt_secret: String,
+}
+
+impl AuthService {
+    pub fn new(storage: Storage, jwt_secret: String) -> Self {
+        Self {
+            repo: AuthRepo::new(storage),
+            jwt_secret,
+        }
+    }
+
+    pub async fn subscribe(&self, payload: SubscriptionPayload) -> Result<User, DynAppError> {
+        let mut errors: Vec<FieldError> = vec![];
+        if payload.username.trim().is_empty() {
+            errors.push(FieldError::new(
+                "username",
+                String::from("The username is required"),
+            ));
+        }
+        if payload.password.len() < 8 {
+            errors.push(FieldError::new(
+                "password",
+                String::from("The password must have at least 8 characters"),
+            ));
+        }
+        if !errors.is_empty() {
+            return Err(Box::new(ApiError::Validation {
+                message: String::from(
+                    "A username and a password with at least 8 characters are required",
+                ),
+                errors,
+            }));
+        }
+
+        let existing = self.repo.get_credentials(payload.username.clone()).await?;
+        if !existing.is_empty() {
+            return Err(Box::new(ApiError::Conflict(format!(
+                "Username {} is already taken",
+                payload.username
+            ))));
+        }
+
+        let password_hash = hash_password(&payload.password)?;
+        let user = User {
+            id: Uuid::new_v4().to_string(),
+            name: payload.name,
+            username: payload.username,
+            role: Role::Buyer,
+            organization_ids: vec![],
+            api_key_id: None,
+        };
+
+        self.repo.create_user(user.clone(), password_hash).await?;
+        Ok(user)
+    }
+
+    pub async fn login(&self, payload: LoginPayload) -> Result<AuthTokenDto, DynAppError> {
+        let rows = self.repo.get_credentials(payload.username).await?;
+
+        let row = match rows.first() {
+            Some(row) => row,
+            None => return Err(invalid_credentials()),
+        };
+
+        let stored_hash: String = row.get("passwd");
+        if !verify_password(&payload.password, &stored_hash) {
+            return Err(invalid_credentials());
+        }
+
+        let user = user_from_row(row);
+
+        let session = Session {
+            id: Uuid::new_v4().to_string(),
+            user_id: user.id.clone(),
+            revoked_at: None,
+        };
+        let (refresh_token, refresh_token_entity) = new_refresh_token(session.id.clone());
+
+        self.repo
+            .create_session(session.clone(), refresh_token_entity)
+            .await?;
+
+        self.issue_tokens(&user, session.id, refresh_token)
+    }
+
+    // exchanges a refresh token for a new pair of tokens. Every refresh token can
+    // only be used once: presenting one that was already exchanged means it
+    // leaked, so the whole session it belongs to is revoked.
+    pub async fn refresh(&self, refresh_token: String) -> Result<AuthTokenDto, DynAppError> {
+        let token_hash = hash_token(&refresh_token);
+
+        let stored_token = match self.repo.get_refresh_token(token_hash.clone()).await? {
+            Some(token) => token,
+            None => return Err(invalid_refresh_token()),
+        };
+
+        let session = match self.repo.get_session(stored_token.session_id.clone()).await? {
+            Some(session) if session.revoked_at.is_none() => session,
+            _ => return Err(invalid_refresh_token()),
+        };
+
+        if stored_token.used_at.is_some()
+            || !self.repo.mark_refresh_token_used(token_hash).await?
+        {
+            self.repo.revoke_session(session.id).await?;
+            return Err(Box::new(ApiError::Unauthorized(String::from(
+                "Refresh token reuse detected, the session was revoked",
+            ))));
+        }
+
+        if stored_token.expires_at < Utc::now().naive_utc() {
+            return Err(invalid_refresh_token());
+        }
+
+        let user = self.get_user(session.user_id.clone()).await?;
+        let (new_refresh_token, new_refresh_token_entity) = new_refresh_token(session.id.clone());
+        self.repo
+            .save_refresh_token(new_refresh_token_entity)
+            .await?;
+
+        self.issue_tokens(&user, session.id, new_refresh_token)
+    }
+
+    pub async fn logout(&self, refresh_token: String) -> Result<(), DynAppError> {
+        let token_hash = hash_token(&refresh_token);
+
+        match self.repo.get_refresh_token(token_hash).await? {
+            Some(token) => {
+                self.repo.revoke_session(token.session_id).await?;
+                Ok(())
+            }
+            None => Err(invalid_refresh_token()),
+        }
+    }
+
+    pub async fn revoke_user_sessions(&self, user_id: String) -> Result<u64, DynAppError> {
+        self.repo.revoke_user_sessions(user_id).await
+    }
+
+    pub async fn update_role(&self, user_id: String, role: Role) -> Result<(), DynAppError> {
+        if self.repo.update_role(user_id.clone(), role).await? != 1 {
+            return Err(Box::new(ApiError::NotFound(format!("User {} not found", user_id))));
+        }
+
+        // tokens issued before the change would keep the old role until they
+        // expire, so the user has to log in again
+        self.repo.revoke_user_sessions(user_id).await?;
+        Ok(())
+    }
+
+    // validates an "Authorization: Bearer <jwt>" header value and loads its user
+    pub async fn authorize(&self, auth_header: String) -> Result<User, DynAppError> {
+        let token = match auth_header.strip_prefix("Bearer ") {
+            Some(token) => token.trim(),
+            None => {
+                return Err(Box::new(ApiError::Unauthorized(String::from(
+                    "Malformed authentication header",
+                ))))
+            }
+        };
+
+        let claims = match decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(self.jwt_secret.as_bytes()),
+            &Validation::default(),
+        ) {
+            Ok(data) => data.claims,
+            Err(_) => {
+                return Err(Box::new(ApiError::Unauthorized(String::from(
+                    "Invalid authentication token",
+                ))))
+            }
+        };
+
+        match self.repo.get_session(claims.sid).await? {
+            Some(session) if session.revoked_at.is_none() => {}
+            _ => {
+                return Err(Box::new(ApiError::Unauthorized(String::from(
+                    "The session was revoked",
+                ))))
+            }
+        }
+
+        self.get_user(claims.sub).await
+    }
+
+    async fn get_user(&self, id: String) -> Result<User, DynAppError> {
+        let rows = self.repo.get_user(id).await?;
+        match rows.first() {
+            Some(row) => Ok(user_from_row(row)),
+            None => Err(Box::new(ApiError::Unauthorized(String::from(
+                "The token's user no longer exists",
+            )))),
+        }
+    }
+
+    fn issue_tokens(
+        &self,
+        user: &User,
+        session_id: String,
+        refresh_token: String,
+    ) -> Result<AuthTokenDto, DynAppError> {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user.id.clone(),
+            username: user.username.clone(),
+            sid: session_id,
+            iat: now,
+            exp: now + ACCESS_TOKEN_TTL_SECONDS,
+        };
+
+        match encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.jwt_secret.as_bytes()),
+        ) {
+            Ok(access_token) => Ok(AuthTokenDto {
+                access_token,
+                token_type: String::from("Bearer"),
+                expires_in: ACCESS_TOKEN_TTL_SECONDS,
+                refresh_token,
+            }),
+            Err(err) => Err(Box::new(ApiError::Internal(err.to_string()))),
+        }
+    }
+}
+
+fn user_from_row(row: &Row) -> User {
+    let role: String = row.get("u_role");
+    User {
+        id: row.get("id"),
+        name: row.get("uname"),
+        username: row.get("username"),
+        // unknown values fall back to the least privileged role
+        role: Role::from_value_str(&role).unwrap_or(Role::Buyer),
+        organization_ids: row.get("organization_ids"),
+        api_key_id: None,
+    }
+}
+
+// returns the token handed to the client and the entity to be stored, which only
+// keeps its hash
+fn new_refresh_token(session_id: String) -> (String, RefreshToken) {
+    let token = random_token();
+
+    let entity = RefreshToken {
+        token_hash: hash_token(&token),
+        session_id,
+        expires_at: (Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS)).naive_utc(),
+        used_at: None,
+    };
+
+    (token, entity)
+}
+
+fn hash_password(password: &str) -> Result<String, DynAppError> {
+    let salt = SaltString::generate(&mut OsRng);
+    match Argon2::default().hash_password(password.as_bytes(), &salt) {
+        Ok(hash) => Ok(hash.to_string()),
+        Err(err) => Err(Box::new(ApiError::Internal(err.to_string()))),
+    }
+}
+
+fn verify_password(password: &str, stored_hash: &str) -> bool {
+    match PasswordHash::new(stored_hash) {
+        Ok(parsed_hash) => Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash)
+            .is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn invalid_credentials() -> DynAppError {
+    Box::new(ApiError::Unauthorized(String::from("Invalid username or password")))
+}
+
+fn invalid_refresh_token() -> DynAppError {
+    Box::new(ApiError::Unauthorized(String::from("Invalid refresh token")))
+}
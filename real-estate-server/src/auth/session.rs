@@ -0,0 +1,17 @@
+use chrono::NaiveDateTime;
+
+// a login session, shared by every refresh token rotated out of the same login
+#[derive(Clone)]
+pub struct Session {
+    pub id: String,
+    pub user_id: String,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone)]
+pub struct RefreshToken {
+    pub token_hash: String,
+    pub session_id: String,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+}
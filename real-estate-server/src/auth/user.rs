@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::role::Role;
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+    pub username: String,
+    pub role: Role,
+    pub organization_ids: Vec<String>,
+    // set when the request authenticated with an API key instead of a login
+    pub api_key_id: Option<String>,
+}
+
+impl User {
+    // who owns what the request leaves behind, like idempotency keys or jobs.
+    // Each API key is on its own, apart from the user who created it
+    pub fn principal_id(&self) -> String {
+        self.api_key_id.clone().unwrap_or(self.id.clone())
+    }
+}
@@ -0,0 +1,109 @@
+// writes the TypeScript client of the UI from the committed OpenAPI document,
+// which is generated from the handlers and the api_contracts types
+mod requests;
+mod types;
+
+use serde_json::Value;
+
+use requests::Operation;
+use types::declaration;
+
+const USAGE: &str = "usage:
+    ts_client [--spec <openapi.json>] [--output <file>]";
+
+const SPEC_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+const CLIENT_FILE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../real-estate-ui/services/api.ts"
+);
+
+const HEADER: &str = concat!(
+    "// Generated by `cargo run --bin ts_client` from real-estate-server/openapi.json,\n",
+    "// regenerate it instead of editing it.\n",
+);
+const RUNTIME: &str = include_str!("runtime.ts");
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(err) = run(&args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let mut spec_file = String::from(SPEC_FILE);
+    let mut output = String::from(CLIENT_FILE);
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match args.next() {
+            Some(value) => value.clone(),
+            None => return Err(String::from(USAGE)),
+        };
+        match arg.as_str() {
+            "--spec" => spec_file = value,
+            "--output" => output = value,
+            _ => return Err(String::from(USAGE)),
+        }
+    }
+
+    let spec = read_spec(&spec_file)?;
+    match std::fs::write(&output, generate(&spec)) {
+        Ok(_) => {
+            println!("{}", output);
+            Ok(())
+        }
+        Err(err) => Err(format!("Could not write {}: {}", output, err)),
+    }
+}
+
+fn read_spec(spec_file: &str) -> Result<Value, String> {
+    let content = match std::fs::read_to_string(spec_file) {
+        Ok(content) => content,
+        Err(err) => return Err(format!("Could not read {}: {}", spec_file, err)),
+    };
+    match serde_json::from_str(&content) {
+        Ok(spec) => Ok(spec),
+        Err(err) => Err(format!("Invalid OpenAPI document {}: {}", spec_file, err)),
+    }
+}
+
+fn generate(spec: &Value) -> String {
+    let mut ts = format!("{}\n{}", HEADER, RUNTIME);
+
+    if let Some(schemas) = spec["components"]["schemas"].as_object() {
+        for (name, schema) in schemas {
+            ts.push('\n');
+            ts.push_str(&declaration(name, schema));
+        }
+    }
+
+    let mut tag = "";
+    for operation in Operation::all(spec) {
+        if operation.tag() != tag {
+            tag = operation.tag();
+            ts.push_str(&format!("\n// {}\n", tag));
+        }
+        ts.push('\n');
+        ts.push_str(&operation.to_ts());
+    }
+    ts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_client_is_up_to_date() {
+        let spec = read_spec(SPEC_FILE).unwrap();
+        let committed = std::fs::read_to_string(CLIENT_FILE).unwrap_or_default();
+
+        assert!(
+            committed == generate(&spec),
+            "{} is out of date, regenerate it with `cargo run --bin ts_client`",
+            CLIENT_FILE
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use serde_json::Value;
+
+use crate::types::{is_binary, ts_type, union};
+
+const METHODS: [&str; 4] = ["get", "post", "put", "delete"];
+
+// the status the server answers with when the client sent
+// `Prefer: respond-async`, the body is the queued job
+const ACCEPTED: &str = "202";
+
+pub struct Operation<'a> {
+    pub method: &'a str,
+    pub path: &'a str,
+    pub spec: &'a Value,
+}
+
+impl<'a> Operation<'a> {
+    // every operation of the document, grouped by tag in the order the
+    // document lists the tags
+    pub fn all(spec: &'a Value) -> Vec<Operation<'a>> {
+        let mut operations: Vec<Operation> = vec![];
+        if let Some(paths) = spec.get("paths").and_then(Value::as_object) {
+            for (path, item) in paths {
+                for method in METHODS {
+                    if let Some(operation) = item.get(method) {
+                        operations.push(Operation {
+                            method,
+                            path,
+                            spec: operation,
+                        });
+                    }
+                }
+            }
+        }
+
+        let tags: Vec<&str> = spec
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| tags.iter().filter_map(|tag| tag["name"].as_str()).collect())
+            .unwrap_or_default();
+        operations.sort_by_key(|operation| {
+            tags.iter()
+                .position(|tag| *tag == operation.tag())
+                .unwrap_or(tags.len())
+        });
+        operations
+    }
+
+    pub fn tag(&self) -> &'a str {
+        self.spec["tags"][0].as_str().unwrap_or_default()
+    }
+
+    // lot_creation_handler becomes lotCreation
+    pub fn function_name(&self) -> String {
+        let operation_id = self.spec["operationId"].as_str().unwrap_or_default();
+        camel_case(operation_id.trim_end_matches("_handler"))
+    }
+
+    pub fn to_ts(&self) -> String {
+        let statuses: Vec<&str> = self.success_statuses();
+        let (sync_statuses, async_statuses): (Vec<&str>, Vec<&str>) =
+            statuses.into_iter().partition(|status| *status != ACCEPTED);
+
+        let mut ts = self.function(&self.function_name(), &sync_statuses, false);
+        if !async_statuses.is_empty() {
+            let name = format!("{}Async", self.function_name());
+            ts.push('\n');
+            ts.push_str(&self.function(&name, &async_statuses, true));
+        }
+        ts
+    }
+
+    fn function(&self, name: &str, statuses: &[&str], respond_async: bool) -> String {
+        let mut arguments: Vec<String> = vec![];
+        let mut options: Vec<String> = vec![];
+
+        let path_parameters = self.parameters("path");
+        for parameter in &path_parameters {
+            arguments.push(format!(
+                "{}: {}",
+                camel_case(&name_of(parameter)),
+                schema_type(parameter)
+            ));
+        }
+
+        if let Some(body) = self.spec.get("requestBody") {
+            let content = body["content"].as_object().cloned().unwrap_or_default();
+            let body_types: Vec<String> = content
+                .values()
+                .map(|media| ts_type(&media["schema"]))
+                .collect();
+            arguments.push(format!("body: {}", union(body_types)));
+            options.push(String::from("body"));
+
+            let content_types: Vec<&String> = content.keys().collect();
+            match content_types.as_slice() {
+                [content_type] if *content_type == "application/json" => {}
+                [content_type] => options.push(format!("contentType: \"{}\"", content_type)),
+                _ => {
+                    let literals = content_types
+                        .iter()
+                        .map(|content_type| format!("\"{}\"", content_type))
+                        .collect();
+                    arguments.push(format!("contentType: {}", union(literals)));
+                    options.push(String::from("contentType"));
+                }
+            }
+        }
+
+        let query_parameters = self.parameters("query");
+        if !query_parameters.is_empty() {
+            let fields: Vec<String> = query_parameters
+                .iter()
+                .map(|parameter| field(parameter))
+                .collect();
+            let required = query_parameters
+                .iter()
+                .any(|parameter| parameter["required"] == true);
+            let default = if required { "" } else { " = {}" };
+            arguments.push(format!("query: {{ {} }}{}", fields.join("; "), default));
+            options.push(String::from("query"));
+        }
+
+        // Prefer is set by the Async variant of the function
+        let header_parameters: Vec<&Value> = self
+            .parameters("header")
+            .into_iter()
+            .filter(|parameter| name_of(parameter) != "Prefer")
+            .collect();
+        if !header_parameters.is_empty() {
+            let fields: Vec<String> = header_parameters
+                .iter()
+                .map(|parameter| field(parameter))
+                .collect();
+            arguments.push(format!("headers: {{ {} }} = {{}}", fields.join("; ")));
+        }
+        match (header_parameters.is_empty(), respond_async) {
+            (true, false) => {}
+            (true, true) => options.push(String::from("headers: { Prefer: \"respond-async\" }")),
+            (false, false) => options.push(String::from("headers")),
+            (false, true) => options.push(String::from(
+                "headers: { ...headers, Prefer: \"respond-async\" }",
+            )),
+        }
+
+        let (return_type, response_type) = self.response(statuses);
+        options.push(format!("responseType: \"{}\"", response_type));
+        options.push(format!("accepted: [{}]", statuses.join(", ")));
+
+        let mut url = self.path.to_string();
+        for parameter in &path_parameters {
+            let name = name_of(parameter);
+            url = url.replace(
+                &format!("{{{}}}", name),
+                &format!("${{encodeURIComponent({})}}", camel_case(&name)),
+            );
+        }
+
+        format!(
+            "// {} {}\nexport const {} = ({}): Promise<{}> =>\n    send<{}>(\"{}\", `{}`, {{ {} }});\n",
+            self.method.to_uppercase(),
+            self.path,
+            name,
+            arguments.join(", "),
+            return_type,
+            return_type,
+            self.method.to_uppercase(),
+            url,
+            options.join(", ")
+        )
+    }
+
+    // the documented statuses besides the `default` problem response
+    fn success_statuses(&self) -> Vec<&str> {
+        self.spec["responses"]
+            .as_object()
+            .map(|responses| {
+                responses
+                    .keys()
+                    .map(|status| status.as_str())
+                    .filter(|status| *status != "default")
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // what the promise resolves to and how axios should read the body
+    fn response(&self, statuses: &[&str]) -> (String, &'static str) {
+        let mut body_types: Vec<String> = vec![];
+        let mut response_type = "json";
+        for status in statuses {
+            let content = match self.spec["responses"][*status]["content"].as_object() {
+                Some(content) => content,
+                None => continue,
+            };
+            for (content_type, media) in content {
+                if is_binary(&media["schema"]) {
+                    response_type = "arraybuffer";
+                } else if content_type != "application/json" && response_type == "json" {
+                    response_type = "text";
+                }
+                body_types.push(ts_type(&media["schema"]));
+            }
+        }
+
+        match response_type {
+            // files come whole, even when some of their formats are text
+            "arraybuffer" => (String::from("ArrayBuffer"), response_type),
+            _ if body_types.is_empty() => (String::from("void"), response_type),
+            _ => (union(body_types), response_type),
+        }
+    }
+
+    fn parameters(&self, location: &str) -> Vec<&Value> {
+        self.spec["parameters"]
+            .as_array()
+            .map(|parameters| {
+                parameters
+                    .iter()
+                    .filter(|parameter| parameter["in"] == location)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn name_of(parameter: &Value) -> String {
+    parameter["name"].as_str().unwrap_or_default().to_string()
+}
+
+fn schema_type(parameter: &Value) -> String {
+    ts_type(&parameter["schema"])
+}
+
+// query and header names go on the wire as they are, quoted when they are not
+// identifiers, like Idempotency-Key
+fn field(parameter: &Value) -> String {
+    let name = name_of(parameter);
+    let name = if name.contains('-') {
+        format!("\"{}\"", name)
+    } else {
+        name
+    };
+    let optional = if parameter["required"] == true {
+        ""
+    } else {
+        "?"
+    };
+    format!("{}{}: {}", name, optional, schema_type(parameter))
+}
+
+fn camel_case(snake_case: &str) -> String {
+    let mut camel_case = String::new();
+    for (index, word) in snake_case.split('_').enumerate() {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) if index > 0 => {
+                camel_case.push(first.to_ascii_uppercase());
+                camel_case.push_str(chars.as_str());
+            }
+            Some(_) => camel_case.push_str(word),
+            None => {}
+        }
+    }
+    camel_case
+}
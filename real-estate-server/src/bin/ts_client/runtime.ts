@@ -0,0 +1,65 @@
+import axios, { Method, ResponseType } from "axios";
+
+export const client = {
+    baseUrl: "http://192.168.0.9:5000",
+    // "Bearer <access_token>" once logged in, or "ApiKey <key>" for integrations
+    authorization: undefined as string | undefined,
+};
+
+// thrown for any status the route does not document, `problem` is the error
+// body the server sent
+export class ApiError extends Error {
+    status: number;
+    problem?: ProblemDetails;
+
+    constructor(status: number, problem?: ProblemDetails) {
+        super(problem?.detail ?? `Request failed with status ${status}`);
+        this.status = status;
+        this.problem = problem;
+    }
+}
+
+interface SendOptions {
+    body?: unknown;
+    contentType?: string;
+    query?: object;
+    headers?: Record<string, string | undefined>;
+    responseType: ResponseType;
+    accepted: number[];
+}
+
+const send = async <T>(method: Method, path: string, options: SendOptions): Promise<T> => {
+    const headers: Record<string, string> = {};
+    const allHeaders = {
+        ...options.headers,
+        "Content-Type": options.contentType,
+        Authorization: client.authorization,
+    };
+    for (const [name, value] of Object.entries(allHeaders)) {
+        if (value !== undefined) {
+            headers[name] = value;
+        }
+    }
+
+    try {
+        const response = await axios.request<T>({
+            method,
+            url: `${client.baseUrl}${path}`,
+            data: options.body,
+            params: options.query,
+            headers,
+            responseType: options.responseType,
+            validateStatus: (status) => options.accepted.includes(status),
+        });
+        return response.data;
+    } catch (error) {
+        if (axios.isAxiosError(error) && error.response) {
+            const data = error.response.data;
+            const problem = typeof data === "object" && data !== null && "code" in data
+                ? data as ProblemDetails
+                : undefined;
+            throw new ApiError(error.response.status, problem);
+        }
+        throw error;
+    }
+};
@@ -0,0 +1,137 @@
+use serde_json::Value;
+
+// `export interface` for objects, `export type` for everything else, such as
+// the enums
+pub fn declaration(name: &str, schema: &Value) -> String {
+    let properties = match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) => properties,
+        None => return format!("export type {} = {};\n", name, ts_type(schema)),
+    };
+
+    let mut declaration = format!("export interface {} {{\n", name);
+    for (property, property_schema) in properties {
+        let optional = if is_required(schema, property) {
+            ""
+        } else {
+            "?"
+        };
+        declaration.push_str(&format!(
+            "    {}{}: {};\n",
+            property,
+            optional,
+            ts_type(property_schema)
+        ));
+    }
+    declaration.push_str("}\n");
+    declaration
+}
+
+pub fn ts_type(schema: &Value) -> String {
+    if let Some(name) = reference_name(schema) {
+        return name.to_string();
+    }
+    if let Some(variants) = schema
+        .get("oneOf")
+        .or(schema.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        return union(variants.iter().map(ts_type).collect());
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        // JSON literals are TypeScript literals too
+        return union(values.iter().map(Value::to_string).collect());
+    }
+
+    match schema.get("type") {
+        // nullable types come as ["string", "null"]
+        Some(Value::Array(kinds)) => union(
+            kinds
+                .iter()
+                .map(|kind| primitive(kind.as_str().unwrap_or_default(), schema))
+                .collect(),
+        ),
+        Some(Value::String(kind)) => primitive(kind, schema),
+        _ => String::from("unknown"),
+    }
+}
+
+// the component a `$ref` points to, like LotDto for #/components/schemas/LotDto
+pub fn reference_name(schema: &Value) -> Option<&str> {
+    schema
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.rsplit('/').next())
+}
+
+pub fn is_binary(schema: &Value) -> bool {
+    reference_name(schema) == Some("BinaryFile")
+        || schema.get("format").and_then(Value::as_str) == Some("binary")
+}
+
+fn is_required(schema: &Value, property: &str) -> bool {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .is_some_and(|required| required.iter().any(|name| name == property))
+}
+
+fn primitive(kind: &str, schema: &Value) -> String {
+    match kind {
+        "string" if is_binary(schema) => String::from("ArrayBuffer | Blob"),
+        "string" => String::from("string"),
+        "integer" | "number" => String::from("number"),
+        "boolean" => String::from("boolean"),
+        "null" => String::from("null"),
+        "array" => array(schema),
+        "object" => object(schema),
+        _ => String::from("unknown"),
+    }
+}
+
+fn array(schema: &Value) -> String {
+    // tuples, like the (longitude, latitude) pairs of the areas
+    if let Some(items) = schema.get("prefixItems").and_then(Value::as_array) {
+        let items: Vec<String> = items.iter().map(ts_type).collect();
+        return format!("[{}]", items.join(", "));
+    }
+
+    let item = schema
+        .get("items")
+        .map(ts_type)
+        .unwrap_or(String::from("unknown"));
+    if item.contains(" | ") {
+        format!("({})[]", item)
+    } else {
+        format!("{}[]", item)
+    }
+}
+
+fn object(schema: &Value) -> String {
+    let properties = match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) => properties,
+        None => return String::from("Record<string, unknown>"),
+    };
+
+    let fields: Vec<String> = properties
+        .iter()
+        .map(|(property, property_schema)| {
+            let optional = if is_required(schema, property) {
+                ""
+            } else {
+                "?"
+            };
+            format!("{}{}: {}", property, optional, ts_type(property_schema))
+        })
+        .collect();
+    format!("{{ {} }}", fields.join("; "))
+}
+
+pub fn union(types: Vec<String>) -> String {
+    let mut unique: Vec<String> = vec![];
+    for ts_type in types {
+        if !unique.contains(&ts_type) {
+            unique.push(ts_type);
+        }
+    }
+    unique.join(" | ")
+}
@@ -0,0 +1,7 @@
+#[derive(Clone)]
+pub struct Block {
+    pub name: String,
+    pub subdivision_id: String,
+    // location ids, empty while the block has no polygon
+    pub area: Box<Vec<String>>,
+}
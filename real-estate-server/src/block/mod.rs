@@ -0,0 +1,3 @@
+pub mod block;
+pub mod repo;
+pub mod service;
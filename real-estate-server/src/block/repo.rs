@@ -0,0 +1,96 @@
+use postgres::Row;
+
+use crate::{
+    database::storage::{SqlParam, Storage},
+    error::app_error::DynAppError,
+    location::location::Location,
+    subdivision::repo::location_statements,
+};
+
+use super::block::Block;
+
+#[derive(Clone)]
+pub struct BlockRepo {
+    storage: Storage,
+}
+
+impl BlockRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn create(&self, block: Block, locations: Vec<Location>) -> Result<u64, DynAppError> {
+        let mut statements = location_statements(locations);
+        statements.extend(block_statements(block));
+
+        self.storage.transaction(statements).await
+    }
+
+    // blocks without a polygon come with empty lats and longs
+    pub async fn get_subdivision_blocks(
+        &self,
+        subdivision_id: String,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                b.b_name,
+                array_remove(array_agg(al.lat ORDER BY bl.position), NULL) as lats,
+                array_remove(array_agg(al.long ORDER BY bl.position), NULL) as longs
+            FROM 
+                block b join subdivision s on s.id = b.subdivision_id
+                left join block_location bl on bl.b_name = b.b_name and bl.subdivision_id = b.subdivision_id
+                left join app_location al on al.id = bl.location_id
+            WHERE 
+                b.subdivision_id = $1
+                and ($2::varchar[] is null or s.organization_id = ANY($2))
+            GROUP BY 
+                b.b_name
+            ORDER BY 
+                b.b_name;
+            "
+        );
+
+        self.storage
+            .query(cmd, &[&subdivision_id, &organization_ids])
+            .await
+    }
+}
+
+// the block and its vertices, assuming that the locations already exist
+pub fn block_statements(block: Block) -> Vec<(String, Vec<SqlParam>)> {
+    let mut statements: Vec<(String, Vec<SqlParam>)> = vec![(
+        String::from(
+            "INSERT INTO
+                block
+                    (b_name, subdivision_id)
+            VALUES
+                ($1, $2);",
+        ),
+        vec![
+            Box::new(block.name.clone()),
+            Box::new(block.subdivision_id.clone()),
+        ],
+    )];
+
+    for (position, location_id) in block.area.into_iter().enumerate() {
+        statements.push((
+            String::from(
+                "INSERT INTO
+                    block_location
+                        (b_name, subdivision_id, location_id, position)
+                VALUES
+                    ($1, $2, $3, $4);",
+            ),
+            vec![
+                Box::new(block.name.clone()),
+                Box::new(block.subdivision_id.clone()),
+                Box::new(location_id),
+                Box::new(position as i32),
+            ],
+        ));
+    }
+
+    statements
+}
@@ -0,0 +1,174 @@
+use crate::{
+    api_contracts::{block_dto::BlockDto, block_summary::BlockSummary, lot_dto::LotDto},
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+    geometry::polygon::{area_m2, validate_polygon},
+    location::location::Location,
+    organization::tenant_scope::TenantScope,
+    subdivision::{
+        lot::LotStatus,
+        service::{assemble_area, resolve_crs, to_locations, SubdivisionService},
+    },
+};
+
+use super::{block::Block, repo::BlockRepo};
+
+#[derive(Clone)]
+pub struct BlockService {
+    repo: BlockRepo,
+    subdivision_service: SubdivisionService,
+}
+
+impl BlockService {
+    pub fn new(storage: Storage, subdivision_service: SubdivisionService) -> Self {
+        Self {
+            repo: BlockRepo::new(storage),
+            subdivision_service,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        subdivision_id: String,
+        block_dto: BlockDto,
+        scope: &TenantScope,
+    ) -> Result<String, DynAppError> {
+        let (block, locations) = self.prepare(subdivision_id, &block_dto, scope).await?;
+
+        self.repo.create(block, locations).await?;
+        Ok(block_dto.name)
+    }
+
+    // validates a new block and converts it to the entity and the vertices the
+    // repos save, without writing anything
+    pub async fn prepare(
+        &self,
+        subdivision_id: String,
+        block_dto: &BlockDto,
+        scope: &TenantScope,
+    ) -> Result<(Block, Vec<Location>), DynAppError> {
+        self.subdivision_service
+            .check_access(subdivision_id.clone(), scope)
+            .await?;
+
+        if block_dto.name.trim().is_empty() {
+            return Err(Box::new(ApiError::validation(
+                "name",
+                String::from("The block name is required"),
+            )));
+        }
+
+        let area = resolve_crs(block_dto.crs.clone())?.area_to_wgs84(&block_dto.area);
+        if !area.is_empty() {
+            let errors = validate_polygon(&area);
+            if !errors.is_empty() {
+                return Err(Box::new(ApiError::field_problems(
+                    "area",
+                    "Invalid block polygon",
+                    errors,
+                )));
+            }
+        }
+
+        let existing = self.get_blocks(subdivision_id.clone(), scope).await?;
+        if existing.iter().any(|block| block.name == block_dto.name) {
+            return Err(Box::new(ApiError::Conflict(format!(
+                "Block {} already exists in subdivision {}",
+                block_dto.name, subdivision_id
+            ))));
+        }
+
+        let mut locations: Vec<Location> = vec![];
+        let block = Block {
+            name: block_dto.name.clone(),
+            subdivision_id,
+            area: Box::new(to_locations(&area, &mut locations)),
+        };
+        Ok((block, locations))
+    }
+
+    pub async fn get_blocks(
+        &self,
+        subdivision_id: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<BlockDto>, DynAppError> {
+        self.subdivision_service
+            .check_access(subdivision_id.clone(), scope)
+            .await?;
+
+        let rows = self
+            .repo
+            .get_subdivision_blocks(subdivision_id, scope.organization_ids.clone())
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| BlockDto {
+                name: row.get("b_name"),
+                area: Box::new(assemble_area(row.get("lats"), row.get("longs"))),
+                crs: None,
+            })
+            .collect())
+    }
+
+    // every block with the totals of the lots inside it
+    pub async fn get_summaries(
+        &self,
+        subdivision_id: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<BlockSummary>, DynAppError> {
+        let blocks = self.get_blocks(subdivision_id.clone(), scope).await?;
+        let lots = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id.clone(), scope)
+            .await?;
+
+        Ok(blocks
+            .into_iter()
+            .map(|block| {
+                let block_lots: Vec<&LotDto> = lots
+                    .iter()
+                    .filter(|lot| lot.block_name.as_ref() == Some(&block.name))
+                    .collect();
+                let sold_lots = block_lots
+                    .iter()
+                    .filter(|lot| lot.status == Some(LotStatus::Sold))
+                    .count();
+
+                BlockSummary {
+                    name: block.name,
+                    subdivision_id: subdivision_id.clone(),
+                    area: block.area,
+                    lots_amount: block_lots.len(),
+                    available_lots: block_lots.len() - sold_lots,
+                    sold_lots,
+                    lots_area: block_lots.iter().map(|lot| area_m2(&lot.area)).sum(),
+                    listed_price: block_lots.iter().filter_map(|lot| lot.price).sum(),
+                }
+            })
+            .collect())
+    }
+
+    pub async fn get_block_lots(
+        &self,
+        subdivision_id: String,
+        block_name: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<LotDto>, DynAppError> {
+        let blocks = self.get_blocks(subdivision_id.clone(), scope).await?;
+        if !blocks.iter().any(|block| block.name == block_name) {
+            return Err(Box::new(ApiError::NotFound(format!(
+                "Block {} not found in subdivision {}",
+                block_name, subdivision_id
+            ))));
+        }
+
+        Ok(self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id, scope)
+            .await?
+            .into_iter()
+            .filter(|lot| lot.block_name.as_ref() == Some(&block_name))
+            .collect())
+    }
+}
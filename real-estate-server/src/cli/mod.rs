@@ -0,0 +1,83 @@
+use crate::{
+    app_state::app_state::AppState,
+    openapi::{spec_json, SPEC_FILE},
+    organization::tenant_scope::TenantScope,
+    sales_table::sales_table::file_name,
+};
+
+const USAGE: &str = "usage:
+    real-estate-server                    starts the web server
+    real-estate-server sales-table <subdivision_id> [--available-only] [--output <file>]
+    real-estate-server openapi [--output <file>]";
+
+// commands for operators with direct access to the database, so they run
+// without any tenant restriction
+pub async fn run(args: Vec<String>) -> Result<(), String> {
+    match args.first().map(|arg| arg.as_str()) {
+        Some("sales-table") => sales_table(&args[1..]).await,
+        Some("openapi") => openapi(&args[1..]),
+        _ => Err(String::from(USAGE)),
+    }
+}
+
+async fn sales_table(args: &[String]) -> Result<(), String> {
+    let mut subdivision_id: Option<String> = None;
+    let mut available_only = false;
+    let mut output: Option<String> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--available-only" => available_only = true,
+            "--output" => match args.next() {
+                Some(file) => output = Some(file.clone()),
+                None => return Err(String::from(USAGE)),
+            },
+            _ if subdivision_id.is_none() && !arg.starts_with("--") => {
+                subdivision_id = Some(arg.clone())
+            }
+            _ => return Err(String::from(USAGE)),
+        }
+    }
+
+    let subdivision_id = match subdivision_id {
+        Some(id) => id,
+        None => return Err(String::from(USAGE)),
+    };
+    let output = output.unwrap_or(file_name(&subdivision_id));
+
+    let sales_table = match AppState::new()?
+        .sales_table_service
+        .generate(subdivision_id, available_only, &TenantScope::unrestricted())
+        .await
+    {
+        Ok(sales_table) => sales_table,
+        Err(err) => return Err(err.message()),
+    };
+
+    match std::fs::write(&output, sales_table.to_pdf()) {
+        Ok(_) => {
+            println!("{}", output);
+            Ok(())
+        }
+        Err(err) => Err(format!("Could not write {}: {}", output, err)),
+    }
+}
+
+// rewrites the committed OpenAPI document after the handlers or the
+// api_contracts change
+fn openapi(args: &[String]) -> Result<(), String> {
+    let output = match args {
+        [] => String::from(SPEC_FILE),
+        [flag, file] if flag == "--output" => file.clone(),
+        _ => return Err(String::from(USAGE)),
+    };
+
+    match std::fs::write(&output, spec_json() + "\n") {
+        Ok(_) => {
+            println!("{}", output);
+            Ok(())
+        }
+        Err(err) => Err(format!("Could not write {}: {}", output, err)),
+    }
+}
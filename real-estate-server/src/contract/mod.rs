@@ -0,0 +1,3 @@
+mod repo;
+pub mod service;
+pub mod template;
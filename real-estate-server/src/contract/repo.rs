@@ -0,0 +1,80 @@
+use crate::{database::storage::Storage, error::app_error::DynAppError};
+
+use super::template::ContractTemplate;
+
+#[derive(Clone)]
+pub struct ContractRepo {
+    storage: Storage,
+}
+
+impl ContractRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn get_template(
+        &self,
+        subdivision_id: String,
+    ) -> Result<Option<ContractTemplate>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT *
+            FROM
+                contract_template
+            WHERE
+                subdivision_id = $1;",
+        );
+
+        let rows = self.storage.query(cmd, &[&subdivision_id]).await?;
+
+        Ok(rows.first().map(|row| ContractTemplate {
+            subdivision_id: row.get("subdivision_id"),
+            body: row.get("body"),
+        }))
+    }
+
+    pub async fn save_template(&self, template: ContractTemplate) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                contract_template
+                    (subdivision_id, body)
+                VALUES
+                    ($1, $2)
+                ON CONFLICT (subdivision_id) DO UPDATE
+                    SET body = EXCLUDED.body;",
+        );
+
+        self.storage
+            .exec(cmd, &[&template.subdivision_id, &template.body])
+            .await
+    }
+
+    pub async fn save_contract(&self, sale_id: String, content: Vec<u8>) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                sale_contract
+                    (sale_id, content, generated_at)
+                VALUES
+                    ($1, $2, now())
+                ON CONFLICT (sale_id) DO UPDATE
+                    SET content = EXCLUDED.content, generated_at = EXCLUDED.generated_at;",
+        );
+
+        self.storage.exec(cmd, &[&sale_id, &content]).await
+    }
+
+    pub async fn get_contract(&self, sale_id: String) -> Result<Option<Vec<u8>>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT content
+            FROM
+                sale_contract
+            WHERE
+                sale_id = $1;",
+        );
+
+        let rows = self.storage.query(cmd, &[&sale_id]).await?;
+
+        Ok(rows.first().map(|row| row.get("content")))
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use crate::{
+    api_contracts::contract_template_dto::ContractTemplateDto,
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+    geometry::polygon::area_m2,
+    organization::tenant_scope::TenantScope,
+    pdf::text_layout::render_text_document,
+    sale::sale::Sale,
+    subdivision::service::SubdivisionService,
+};
+
+use super::{
+    repo::ContractRepo,
+    template::{render, unknown_placeholders, ContractTemplate, DEFAULT_TEMPLATE},
+};
+
+#[derive(Clone)]
+pub struct ContractService {
+    repo: ContractRepo,
+    subdivision_service: SubdivisionService,
+}
+
+impl ContractService {
+    pub fn new(storage: Storage, subdivision_service: SubdivisionService) -> Self {
+        Self {
+            repo: ContractRepo::new(storage),
+            subdivision_service,
+        }
+    }
+
+    // subdivisions without a template of their own use the default one
+    pub async fn get_template(
+        &self,
+        subdivision_id: String,
+        scope: &TenantScope,
+    ) -> Result<ContractTemplateDto, DynAppError> {
+        self.subdivision_service
+            .check_access(subdivision_id.clone(), scope)
+            .await?;
+
+        let body = match self.repo.get_template(subdivision_id).await? {
+            Some(template) => template.body,
+            None => String::from(DEFAULT_TEMPLATE),
+        };
+
+        Ok(ContractTemplateDto { body })
+    }
+
+    pub async fn save_template(
+        &self,
+        subdivision_id: String,
+        template_dto: ContractTemplateDto,
+        scope: &TenantScope,
+    ) -> Result<(), DynAppError> {
+        let unknown = unknown_placeholders(&template_dto.body);
+        if !unknown.is_empty() {
+            return Err(Box::new(ApiError::validation(
+                "body",
+                format!("Unknown template placeholders: {}", unknown.join(", ")),
+            )));
+        }
+
+        // fails with 404 when the subdivision does not exist
+        self.subdivision_service
+            .check_access(subdivision_id.clone(), scope)
+            .await?;
+
+        self.repo
+            .save_template(ContractTemplate {
+                subdivision_id,
+                body: template_dto.body,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    // callers are expected to have checked the sale against the requester's scope
+    pub async fn generate(&self, sale: &Sale) -> Result<Vec<u8>, DynAppError> {
+        let scope = TenantScope::unrestricted();
+        let template = self
+            .get_template(sale.subdivision_id.clone(), &scope)
+            .await?;
+        let subdivision = self
+            .subdivision_service
+            .get_preview(sale.subdivision_id.clone(), &scope)
+            .await?;
+        let lot = self
+            .subdivision_service
+            .get_lot(sale.subdivision_id.clone(), sale.lot_name.clone(), &scope)
+            .await?;
+
+        let boundaries = lot
+            .area
+            .iter()
+            .enumerate()
+            .map(|(pos, (lat, long))| format!("Vertex {}: {:.7}, {:.7}", pos + 1, lat, long))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let payment_schedule = if sale.installments.is_empty() {
+            format!("Single payment of {} on signature.", format_money(sale.price))
+        } else {
+            sale.installments
+                .iter()
+                .map(|installment| {
+                    format!(
+                        "Installment {}: {} due on {}",
+                        installment.number,
+                        format_money(installment.amount),
+                        installment.due_date.format("%d/%m/%Y")
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+
+        let values: HashMap<&str, String> = HashMap::from([
+            ("buyer_name", sale.buyer_name.clone()),
+            ("buyer_document", sale.buyer_document.clone()),
+            ("subdivision_name", subdivision.name),
+            ("lot_name", lot.name.clone()),
+            ("lot_area", format!("{:.2} m²", area_m2(&lot.area))),
+            ("lot_boundaries", boundaries),
+            ("price", format_money(sale.price)),
+            ("payment_schedule", payment_schedule),
+            ("date", sale.sold_at.format("%d/%m/%Y").to_string()),
+        ]);
+
+        let body = render(&template.body, &values);
+        let title = format!("Contract {} - Lot {}", sale.id, lot.name);
+        let content = render_text_document(&title, &body);
+
+        self.repo
+            .save_contract(sale.id.clone(), content.clone())
+            .await?;
+
+        Ok(content)
+    }
+
+    pub async fn get_contract(&self, sale_id: String) -> Result<Option<Vec<u8>>, DynAppError> {
+        self.repo.get_contract(sale_id).await
+    }
+}
+
+fn format_money(value: f64) -> String {
+    format!("R$ {:.2}", value)
+}
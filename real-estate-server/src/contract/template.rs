@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+pub const PLACEHOLDERS: [&str; 9] = [
+    "buyer_name",
+    "buyer_document",
+    "subdivision_name",
+    "lot_name",
+    "lot_area",
+    "lot_boundaries",
+    "price",
+    "payment_schedule",
+    "date",
+];
+
+pub const DEFAULT_TEMPLATE: &str = "PURCHASE AND SALE AGREEMENT
+
+The seller, developer of the {{subdivision_name}} subdivision, sells to {{buyer_name}}, holder of the document {{buyer_document}}, the lot {{lot_name}}, with a total area of {{lot_area}}, bounded by the following vertices:
+
+{{lot_boundaries}}
+
+The total price of the lot is {{price}}, to be paid according to the following schedule:
+
+{{payment_schedule}}
+
+Signed on {{date}}.
+
+
+______________________________
+Seller
+
+
+______________________________
+Buyer";
+
+#[derive(Clone)]
+pub struct ContractTemplate {
+    pub subdivision_id: String,
+    pub body: String,
+}
+
+// placeholders are written as {{name}}, spaces inside the braces are ignored
+pub fn find_placeholders(body: &str) -> Vec<String> {
+    let mut placeholders: Vec<String> = vec![];
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        match rest[start + 2..].find("}}") {
+            Some(end) => {
+                placeholders.push(rest[start + 2..start + 2 + end].trim().to_string());
+                rest = &rest[start + 2 + end + 2..];
+            }
+            None => break,
+        }
+    }
+
+    placeholders
+}
+
+pub fn unknown_placeholders(body: &str) -> Vec<String> {
+    find_placeholders(body)
+        .into_iter()
+        .filter(|placeholder| !PLACEHOLDERS.contains(&placeholder.as_str()))
+        .collect()
+}
+
+pub fn render(body: &str, values: &HashMap<&str, String>) -> String {
+    let mut rendered = String::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start + 2..].find("}}") {
+            Some(end) => start + 2 + end,
+            None => break,
+        };
+
+        rendered += &rest[..start];
+        let name = rest[start + 2..end].trim();
+        match values.get(name) {
+            Some(value) => rendered += value,
+            None => rendered += &rest[start..end + 2],
+        }
+        rest = &rest[end + 2..];
+    }
+
+    rendered += rest;
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_placeholders_ignoring_spaces() {
+        let body = "{{buyer_name}} buys {{ lot_name }} for {{price}}";
+
+        assert_eq!(
+            find_placeholders(body),
+            vec!["buyer_name", "lot_name", "price"]
+        );
+    }
+
+    #[test]
+    fn lists_only_unknown_placeholders() {
+        let body = "{{buyer_name}} pays {{discount}} on {{ due_day }}";
+
+        assert_eq!(unknown_placeholders(body), vec!["discount", "due_day"]);
+        assert!(unknown_placeholders(DEFAULT_TEMPLATE).is_empty());
+    }
+
+    #[test]
+    fn renders_the_known_values() {
+        let values = HashMap::from([
+            ("buyer_name", String::from("Ana Souza")),
+            ("lot_name", String::from("Q1-L03")),
+        ]);
+
+        assert_eq!(
+            render("{{buyer_name}} buys {{ lot_name }}.", &values),
+            "Ana Souza buys Q1-L03."
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_placeholders_as_written() {
+        let values = HashMap::from([("price", String::from("R$ 90000.00"))]);
+
+        assert_eq!(
+            render("{{price}} minus {{ discount }}", &values),
+            "R$ 90000.00 minus {{ discount }}"
+        );
+    }
+
+    #[test]
+    fn keeps_unclosed_placeholders_as_text() {
+        let values = HashMap::from([("price", String::from("R$ 90000.00"))]);
+        let body = "Total {{price}}, see {{price";
+
+        assert_eq!(find_placeholders(body), vec!["price"]);
+        assert_eq!(render(body, &values), "Total R$ 90000.00, see {{price");
+    }
+}
@@ -4,40 +4,207 @@ create table app_location(
     long double precision
 );
 
+create table organization(
+    id varchar(255) PRIMARY KEY,
+    o_name varchar(255)
+);
+
 create table subdivision(
     id varchar(255) PRIMARY KEY,
-    s_name varchar(255)
+    s_name varchar(255),
+    organization_id varchar(255) references organization NOT NULL
 );
 
+-- position is the index of the vertex in the ring, the polygons are read in
+-- that order
 create table subdivision_location(
     subdivision_id varchar(255) references subdivision,
     location_id varchar(255) references app_location,
+    position integer,
     PRIMARY KEY (subdivision_id, location_id)
 );
 
+-- quadras, the blocks lots are numbered inside of. The polygon is optional
+create table block(
+    b_name varchar(255),
+    subdivision_id varchar(255) references subdivision,
+    PRIMARY KEY (b_name, subdivision_id)
+);
+
+create table block_location(
+    b_name varchar(255),
+    subdivision_id varchar(255),
+    location_id varchar(255) references app_location,
+    position integer,
+    FOREIGN KEY (b_name, subdivision_id) references block (b_name, subdivision_id),
+    PRIMARY KEY(b_name, subdivision_id, location_id)
+);
+
 create table lot(
     l_name varchar(255),
     subdivision_id varchar(255) references subdivision,
-    PRIMARY KEY (l_name, subdivision_id)
+    l_price double precision,
+    block_name varchar(255),
+    PRIMARY KEY (l_name, subdivision_id),
+    CONSTRAINT lot_block_fk FOREIGN KEY (block_name, subdivision_id) references block (b_name, subdivision_id)
 );
 
 create table lot_location(
     l_name varchar(255),
     subdivision_id varchar(255),
     location_id varchar(255) references app_location,
+    position integer,
     FOREIGN KEY (l_name, subdivision_id) references lot (l_name, subdivision_id),
     PRIMARY KEY(l_name, subdivision_id, location_id)
 );
 
+-- streets, green areas, institutional lots and APPs, the parts of the
+-- subdivision that are not for sale. Line strings may carry a width in meters
+create table site_feature(
+    id varchar(255) PRIMARY KEY,
+    subdivision_id varchar(255) references subdivision,
+    f_name varchar(255),
+    land_use varchar(50),
+    geometry_kind varchar(50),
+    width double precision
+);
+
+create table site_feature_location(
+    feature_id varchar(255) references site_feature,
+    location_id varchar(255) references app_location,
+    position integer,
+    PRIMARY KEY (feature_id, location_id)
+);
+
 create table app_user(
     id varchar(255),
     uname varchar(255),
+    -- one of admin, developer, broker or buyer. The first admin has to be
+    -- promoted directly in the database
+    u_role varchar(50) DEFAULT 'buyer',
     primary key (id)
 );
 
+-- passwd holds an Argon2 PHC string, never the plain password
 create table credentials(
     passwd varchar(255),
-    username varchar(255),
+    username varchar(255) UNIQUE,
     user_id varchar(255) references app_user,
     primary key (user_id, username)
-);
\ No newline at end of file
+);
+
+create table organization_member(
+    organization_id varchar(255) references organization,
+    user_id varchar(255) references app_user,
+    PRIMARY KEY (organization_id, user_id)
+);
+
+create table auth_session(
+    id varchar(255) PRIMARY KEY,
+    user_id varchar(255) references app_user,
+    created_at timestamp,
+    revoked_at timestamp
+);
+
+-- only the SHA-256 of each refresh token is stored
+create table refresh_token(
+    token_hash varchar(64) PRIMARY KEY,
+    session_id varchar(255) references auth_session,
+    expires_at timestamp,
+    used_at timestamp,
+    created_at timestamp
+);
+
+create table sale(
+    id varchar(255) PRIMARY KEY,
+    subdivision_id varchar(255),
+    l_name varchar(255),
+    buyer_name varchar(255),
+    buyer_document varchar(255),
+    price double precision,
+    sold_at timestamp,
+    FOREIGN KEY (l_name, subdivision_id) references lot (l_name, subdivision_id),
+    UNIQUE (l_name, subdivision_id)
+);
+
+create table sale_installment(
+    sale_id varchar(255) references sale,
+    i_number integer,
+    due_date date,
+    amount double precision,
+    PRIMARY KEY (sale_id, i_number)
+);
+
+create table contract_template(
+    subdivision_id varchar(255) references subdivision PRIMARY KEY,
+    body text
+);
+
+create table sale_contract(
+    sale_id varchar(255) references sale PRIMARY KEY,
+    content bytea,
+    generated_at timestamp
+);
+
+-- a lot held for a buyer before the sale. A reservation counts as converted
+-- when the lot is sold after it was made
+create table reservation(
+    id varchar(255) PRIMARY KEY,
+    subdivision_id varchar(255),
+    l_name varchar(255),
+    buyer_name varchar(255),
+    reserved_at timestamp,
+    FOREIGN KEY (l_name, subdivision_id) references lot (l_name, subdivision_id)
+);
+
+-- only the SHA-256 of each key is stored, k_scope is read_only or read_write
+create table api_key(
+    id varchar(255) PRIMARY KEY,
+    organization_id varchar(255) references organization,
+    k_name varchar(255),
+    key_hash varchar(64) UNIQUE,
+    k_scope varchar(50),
+    expires_at timestamp,
+    last_used_at timestamp,
+    created_at timestamp,
+    revoked_at timestamp
+);
+
+-- responses of POST requests sent with an Idempotency-Key header, replayed for
+-- 24 hours. status_code is null while the first request is still running
+create table idempotency_key(
+    owner_id varchar(255),
+    i_key varchar(255),
+    request_hash varchar(64),
+    status_code integer,
+    content_type varchar(255),
+    body bytea,
+    created_at timestamp,
+    PRIMARY KEY (owner_id, i_key)
+);
+
+-- long running imports and exports, run by the workers inside the server.
+-- j_status is queued, running, succeeded, failed or cancelled
+create table job(
+    id varchar(255) PRIMARY KEY,
+    kind varchar(50),
+    j_status varchar(50),
+    request text,
+    input bytea,
+    owner_id varchar(255),
+    organization_ids varchar(255)[],
+    progress integer,
+    attempts integer,
+    cancel_requested boolean,
+    error text,
+    result_file_name varchar(255),
+    result_content_type varchar(255),
+    result_content bytea,
+    run_after timestamp,
+    heartbeat_at timestamp,
+    created_at timestamp,
+    started_at timestamp,
+    finished_at timestamp
+);
+
+create index job_queue on job (j_status, run_after);
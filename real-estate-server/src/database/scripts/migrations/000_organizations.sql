@@ -0,0 +1,122 @@
+-- Brings databases created before sales, logins and organizations existed up
+-- to init.sql, run it before the other migrations. Each section below covers
+-- one of those features and does not depend on the ones after it. Safe to run
+-- more than once, stopping at the first error:
+--   psql -h localhost -U postgres -v ON_ERROR_STOP=1 -f src/database/scripts/migrations/000_organizations.sql
+
+-- sales and their contracts
+create table if not exists sale(
+    id varchar(255) PRIMARY KEY,
+    subdivision_id varchar(255),
+    l_name varchar(255),
+    buyer_name varchar(255),
+    buyer_document varchar(255),
+    price double precision,
+    sold_at timestamp,
+    FOREIGN KEY (l_name, subdivision_id) references lot (l_name, subdivision_id),
+    UNIQUE (l_name, subdivision_id)
+);
+
+create table if not exists sale_installment(
+    sale_id varchar(255) references sale,
+    i_number integer,
+    due_date date,
+    amount double precision,
+    PRIMARY KEY (sale_id, i_number)
+);
+
+create table if not exists contract_template(
+    subdivision_id varchar(255) references subdivision PRIMARY KEY,
+    body text
+);
+
+create table if not exists sale_contract(
+    sale_id varchar(255) references sale PRIMARY KEY,
+    content bytea,
+    generated_at timestamp
+);
+
+-- logins are looked up by username, which has to be unique from now on.
+-- Passwords stored before the Argon2 hashes have to be reset. Which of two
+-- logins sharing a username is the right one is for an operator to decide, so
+-- the migration stops and names them instead of picking one
+do $$
+declare
+    repeated text;
+begin
+    select string_agg(username, ', ' order by username) into repeated
+    from (
+        select username from credentials group by username having count(*) > 1
+    ) duplicates;
+
+    if repeated is not null then
+        raise exception 'Usernames shared by more than one login: %. Rename or remove the extra logins and run this migration again', repeated;
+    end if;
+end
+$$;
+
+alter table credentials drop constraint if exists credentials_username_key;
+alter table credentials add constraint credentials_username_key UNIQUE (username);
+
+-- roles
+alter table app_user add column if not exists u_role varchar(50) DEFAULT 'buyer';
+
+-- sessions and refresh tokens
+create table if not exists auth_session(
+    id varchar(255) PRIMARY KEY,
+    user_id varchar(255) references app_user,
+    created_at timestamp,
+    revoked_at timestamp
+);
+
+create table if not exists refresh_token(
+    token_hash varchar(64) PRIMARY KEY,
+    session_id varchar(255) references auth_session,
+    expires_at timestamp,
+    used_at timestamp,
+    created_at timestamp
+);
+
+-- organizations
+create table if not exists organization(
+    id varchar(255) PRIMARY KEY,
+    o_name varchar(255)
+);
+
+create table if not exists organization_member(
+    organization_id varchar(255) references organization,
+    user_id varchar(255) references app_user,
+    PRIMARY KEY (organization_id, user_id)
+);
+
+-- the subdivisions that already exist, and the users that could see them, go
+-- to a default organization. Admins can move them to the right ones afterwards
+alter table subdivision add column if not exists organization_id varchar(255) references organization;
+
+with created as (
+    insert into organization (id, o_name)
+    select 'default', 'Default'
+    where exists (select 1 from subdivision where organization_id is null)
+    on conflict (id) do nothing
+    returning id
+)
+insert into organization_member (organization_id, user_id)
+select created.id, app_user.id
+from created, app_user;
+
+update subdivision set organization_id = 'default' where organization_id is null;
+
+alter table subdivision alter column organization_id set NOT NULL;
+
+-- API keys
+create table if not exists api_key(
+    id varchar(255) PRIMARY KEY,
+    organization_id varchar(255) references organization,
+    k_name varchar(255),
+    key_hash varchar(64) UNIQUE,
+    k_scope varchar(50),
+    expires_at timestamp,
+    last_used_at timestamp,
+    created_at timestamp,
+    revoked_at timestamp
+);
@@ -0,0 +1,39 @@
+-- Brings databases created before blocks existed up to init.sql. Safe to run
+-- more than once:
+--   psql -h localhost -U postgres -f src/database/scripts/migrations/001_blocks.sql
+
+-- lot prices came with the exports, older databases need the column too
+alter table lot add column if not exists l_price double precision;
+
+create table if not exists block(
+    b_name varchar(255),
+    subdivision_id varchar(255) references subdivision,
+    PRIMARY KEY (b_name, subdivision_id)
+);
+
+create table if not exists block_location(
+    b_name varchar(255),
+    subdivision_id varchar(255),
+    location_id varchar(255) references app_location,
+    FOREIGN KEY (b_name, subdivision_id) references block (b_name, subdivision_id),
+    PRIMARY KEY(b_name, subdivision_id, location_id)
+);
+
+alter table lot add column if not exists block_name varchar(255);
+alter table lot drop constraint if exists lot_block_fk;
+alter table lot add constraint lot_block_fk
+    FOREIGN KEY (block_name, subdivision_id) references block (b_name, subdivision_id);
+
+-- lots named like Q3-L01 or q03-l1 go to the block before the dash. The blocks
+-- are created without a polygon, which can be drawn afterwards
+insert into block (b_name, subdivision_id)
+select distinct
+    substring(l_name from '^([Qq][^-]+)-[Ll]'), subdivision_id
+from lot
+where substring(l_name from '^([Qq][^-]+)-[Ll]') is not null
+on conflict do nothing;
+
+update lot
+set block_name = substring(l_name from '^([Qq][^-]+)-[Ll]')
+where block_name is null
+    and substring(l_name from '^([Qq][^-]+)-[Ll]') is not null;
@@ -0,0 +1,18 @@
+-- Adds the non-sale features of a subdivision to databases created before
+-- them. Safe to run more than once:
+--   psql -h localhost -U postgres -f src/database/scripts/migrations/002_site_features.sql
+
+create table if not exists site_feature(
+    id varchar(255) PRIMARY KEY,
+    subdivision_id varchar(255) references subdivision,
+    f_name varchar(255),
+    land_use varchar(50),
+    geometry_kind varchar(50),
+    width double precision
+);
+
+create table if not exists site_feature_location(
+    feature_id varchar(255) references site_feature,
+    location_id varchar(255) references app_location,
+    PRIMARY KEY (feature_id, location_id)
+);
@@ -0,0 +1,14 @@
+-- Adds the stored responses of idempotent requests to databases created before
+-- them. Safe to run more than once:
+--   psql -h localhost -U postgres -f src/database/scripts/migrations/003_idempotency_keys.sql
+
+create table if not exists idempotency_key(
+    owner_id varchar(255),
+    i_key varchar(255),
+    request_hash varchar(64),
+    status_code integer,
+    content_type varchar(255),
+    body bytea,
+    created_at timestamp,
+    PRIMARY KEY (owner_id, i_key)
+);
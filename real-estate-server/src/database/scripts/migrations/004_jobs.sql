@@ -0,0 +1,26 @@
+-- Adds the job queue to databases created before it. Safe to run more than once:
+--   psql -h localhost -U postgres -f src/database/scripts/migrations/004_jobs.sql
+
+create table if not exists job(
+    id varchar(255) PRIMARY KEY,
+    kind varchar(50),
+    j_status varchar(50),
+    request text,
+    input bytea,
+    owner_id varchar(255),
+    organization_ids varchar(255)[],
+    progress integer,
+    attempts integer,
+    cancel_requested boolean,
+    error text,
+    result_file_name varchar(255),
+    result_content_type varchar(255),
+    result_content bytea,
+    run_after timestamp,
+    heartbeat_at timestamp,
+    created_at timestamp,
+    started_at timestamp,
+    finished_at timestamp
+);
+
+create index if not exists job_queue on job (j_status, run_after);
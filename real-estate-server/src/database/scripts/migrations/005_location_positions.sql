@@ -0,0 +1,37 @@
+-- Adds the order of the vertices of every polygon. Safe to run more than once:
+--   psql -h localhost -U postgres -f src/database/scripts/migrations/005_location_positions.sql
+
+alter table subdivision_location add column if not exists position integer;
+alter table block_location add column if not exists position integer;
+alter table lot_location add column if not exists position integer;
+alter table site_feature_location add column if not exists position integer;
+
+-- the vertices saved so far are numbered in the order the rows were written,
+-- which is the order they were inserted in unless the rows were updated since
+update subdivision_location sl set position = numbered.position
+from (
+    select ctid, row_number() over (partition by subdivision_id order by ctid) - 1 as position
+    from subdivision_location
+) numbered
+where sl.ctid = numbered.ctid and sl.position is null;
+
+update block_location bl set position = numbered.position
+from (
+    select ctid, row_number() over (partition by b_name, subdivision_id order by ctid) - 1 as position
+    from block_location
+) numbered
+where bl.ctid = numbered.ctid and bl.position is null;
+
+update lot_location ll set position = numbered.position
+from (
+    select ctid, row_number() over (partition by l_name, subdivision_id order by ctid) - 1 as position
+    from lot_location
+) numbered
+where ll.ctid = numbered.ctid and ll.position is null;
+
+update site_feature_location fl set position = numbered.position
+from (
+    select ctid, row_number() over (partition by feature_id order by ctid) - 1 as position
+    from site_feature_location
+) numbered
+where fl.ctid = numbered.ctid and fl.position is null;
@@ -0,0 +1,12 @@
+-- Adds the lot reservations the analytics report converts into sales. Safe to
+-- run more than once:
+--   psql -h localhost -U postgres -f src/database/scripts/migrations/006_reservations.sql
+
+create table if not exists reservation(
+    id varchar(255) PRIMARY KEY,
+    subdivision_id varchar(255),
+    l_name varchar(255),
+    buyer_name varchar(255),
+    reserved_at timestamp,
+    FOREIGN KEY (l_name, subdivision_id) references lot (l_name, subdivision_id)
+);
@@ -1,7 +1,9 @@
 use postgres_types::ToSql;
-use tokio_postgres::{GenericClient, NoTls, Row};
+use tokio_postgres::{NoTls, Row};
 
-use crate::error::{app_error::DynAppError, default::DefaultAppError};
+use crate::error::{api_error::ApiError, app_error::DynAppError};
+
+pub type SqlParam = Box<dyn ToSql + Sync + Send>;
 
 #[derive(Clone)]
 pub struct Storage {
@@ -11,10 +13,10 @@ pub struct Storage {
 impl Storage {
     pub fn new(host: String, database: String, user: String, passwd: String) -> Self {
         Self {
-            connection_string: String::from(format!(
+            connection_string: format!(
                 "host={} dbname={} user={} password={}",
                 host, database, user, passwd
-            )),
+            ),
         }
     }
 
@@ -33,22 +35,20 @@ impl Storage {
                     }
                 });
 
-                match client.execute(&cmd, &cmd_params).await {
+                match client.execute(&cmd, cmd_params).await {
                     Ok(lines) => Ok(lines),
-                    Err(error) => Err(Box::new(DefaultAppError {
-                        message: Some(error.to_string()),
-                        status_code: 500,
-                    })),
+                    Err(error) => Err(Box::new(ApiError::from(error))),
                 }
             }
-            Err(err) => Err(Box::new(DefaultAppError {
-                message: Some(err.to_string()),
-                status_code: 500,
-            })),
+            Err(err) => Err(Box::new(ApiError::Unavailable(err.to_string()))),
         }
     }
 
-    pub async fn batch_exec(&self, cmd: String) -> Result<(), DynAppError> {
+    pub async fn query(
+        &self,
+        cmd: String,
+        query_params: &[&(dyn ToSql + Sync)],
+    ) -> Result<Vec<Row>, DynAppError> {
         let connection_result = tokio_postgres::connect(&self.connection_string, NoTls).await;
 
         match connection_result {
@@ -59,51 +59,76 @@ impl Storage {
                     }
                 });
 
-                match client.batch_execute(&cmd).await {
-                    Ok(_) => Ok(()),
-                    Err(error) => Err(Box::new(DefaultAppError {
-                        message: Some(error.to_string()),
-                        status_code: 500,
-                    })),
+                match client.query(&cmd, query_params).await {
+                    Ok(rows) => Ok(rows),
+                    Err(err) => Err(Box::new(ApiError::from(err))),
                 }
             }
-            Err(err) => Err(Box::new(DefaultAppError {
-                message: Some(err.to_string()),
-                status_code: 500,
-            })),
+            Err(err) => Err(Box::new(ApiError::Unavailable(err.to_string()))),
         }
     }
 
-    pub async fn query(
+    // runs every statement inside a single transaction, rolling all of them back
+    // if any fails. Returns the total amount of affected rows.
+    pub async fn transaction(
         &self,
-        cmd: String,
-        query_params: &[&(dyn ToSql + Sync)],
-    ) -> Result<Vec<Row>, DynAppError> {
+        statements: Vec<(String, Vec<SqlParam>)>,
+    ) -> Result<u64, DynAppError> {
         let connection_result = tokio_postgres::connect(&self.connection_string, NoTls).await;
 
-        match connection_result {
-            Ok((client, connection)) => {
-                tokio::spawn(async move {
-                    if let Err(e) = connection.await {
-                        eprintln!("connection error: {}", e);
-                    }
-                });
+        let (mut client, connection) = match connection_result {
+            Ok(conn) => conn,
+            Err(err) => return Err(Box::new(ApiError::Unavailable(err.to_string()))),
+        };
 
-                match client.query(&cmd, &query_params).await {
-                    Ok(rows) => Ok(rows),
-                    Err(err) => Err(Box::new(DefaultAppError {
-                        message: Some(err.to_string()),
-                        status_code: 500,
-                    })),
-                }
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("connection error: {}", e);
             }
-            Err(err) => {
-                print!("{}", err);
-                Err(Box::new(DefaultAppError {
-                    message: Some(err.to_string()),
-                    status_code: 500,
-                }))
+        });
+
+        let transaction = match client.transaction().await {
+            Ok(transaction) => transaction,
+            Err(err) => return Err(Box::new(ApiError::from(err))),
+        };
+
+        let mut affected_rows: u64 = 0;
+        for (cmd, params) in statements.iter() {
+            let params_refs: Vec<&(dyn ToSql + Sync)> = params
+                .iter()
+                .map(|param| param.as_ref() as &(dyn ToSql + Sync))
+                .collect();
+
+            match transaction.execute(cmd, &params_refs).await {
+                Ok(lines) => affected_rows += lines,
+                Err(err) => {
+                    // dropping the transaction rolls it back
+                    return Err(Box::new(ApiError::from(err)));
+                }
             }
         }
+
+        match transaction.commit().await {
+            Ok(_) => Ok(affected_rows),
+            Err(err) => Err(Box::new(ApiError::from(err))),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Storage {
+    // tests that need the database run against the one REAL_ESTATE_TEST_DB_HOST
+    // points to, created by init.sql. REAL_ESTATE_TEST_DB_NAME names it,
+    // postgres when not set. Without a host those tests are skipped
+    pub fn for_tests() -> Option<Self> {
+        let host = std::env::var("REAL_ESTATE_TEST_DB_HOST").ok()?;
+        let database =
+            std::env::var("REAL_ESTATE_TEST_DB_NAME").unwrap_or(String::from("postgres"));
+        Some(Self::new(
+            host,
+            database,
+            String::from("postgres"),
+            String::from("postgres"),
+        ))
     }
 }
@@ -0,0 +1,276 @@
+use serde::{Deserialize, Serialize};
+use tokio_postgres::error::SqlState;
+use utoipa::ToSchema;
+
+use super::app_error::AppError;
+
+// a problem with one field of the request, `field` is its path in the payload,
+// e.g. "installments" or "area"
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: String) -> Self {
+        Self {
+            field: String::from(field),
+            message,
+        }
+    }
+}
+
+// every failure the API reports. Each variant has a stable code clients can
+// branch on, the message is for people and may change
+#[derive(Clone, Debug, PartialEq)]
+pub enum ApiError {
+    BadRequest(String),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    // the request clashes with the current state of a record, found by the
+    // service before writing, e.g. a taken block name or a job that already
+    // finished
+    Conflict(String),
+    // a unique constraint rejected the write, a duplicate the services did not
+    // look for or that a concurrent request saved first. Same status as
+    // Conflict, clients that do not care about the cause can treat both alike
+    AlreadyExists(String),
+    PayloadTooLarge(String),
+    Validation {
+        message: String,
+        errors: Vec<FieldError>,
+    },
+    // the request points at a record that does not exist, caught by a
+    // foreign key
+    InvalidReference(String),
+    Internal(String),
+    Unavailable(String),
+}
+
+impl ApiError {
+    pub fn validation(field: &str, message: String) -> Self {
+        ApiError::Validation {
+            message: message.clone(),
+            errors: vec![FieldError::new(field, message)],
+        }
+    }
+
+    // every problem found in the same field, e.g. the checks of a polygon
+    pub fn field_problems(field: &str, summary: &str, problems: Vec<String>) -> Self {
+        ApiError::Validation {
+            message: format!("{}: {}", summary, problems.join(", ")),
+            errors: problems
+                .into_iter()
+                .map(|problem| FieldError::new(field, problem))
+                .collect(),
+        }
+    }
+}
+
+impl AppError for ApiError {
+    fn message(&self) -> String {
+        match self {
+            ApiError::BadRequest(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::Forbidden(message)
+            | ApiError::NotFound(message)
+            | ApiError::Conflict(message)
+            | ApiError::AlreadyExists(message)
+            | ApiError::PayloadTooLarge(message)
+            | ApiError::Validation { message, .. }
+            | ApiError::InvalidReference(message)
+            | ApiError::Internal(message)
+            | ApiError::Unavailable(message) => message.clone(),
+        }
+    }
+
+    fn status_code(&self) -> i32 {
+        match *self {
+            ApiError::BadRequest(_) => 400,
+            ApiError::Unauthorized(_) => 401,
+            ApiError::Forbidden(_) => 403,
+            ApiError::NotFound(_) => 404,
+            ApiError::Conflict(_) | ApiError::AlreadyExists(_) => 409,
+            ApiError::PayloadTooLarge(_) => 413,
+            ApiError::Validation { .. } | ApiError::InvalidReference(_) => 422,
+            ApiError::Internal(_) => 500,
+            ApiError::Unavailable(_) => 503,
+        }
+    }
+
+    fn code(&self) -> String {
+        match *self {
+            ApiError::BadRequest(_) => String::from("bad_request"),
+            ApiError::Unauthorized(_) => String::from("unauthorized"),
+            ApiError::Forbidden(_) => String::from("forbidden"),
+            ApiError::NotFound(_) => String::from("not_found"),
+            ApiError::Conflict(_) => String::from("conflict"),
+            ApiError::AlreadyExists(_) => String::from("already_exists"),
+            ApiError::PayloadTooLarge(_) => String::from("payload_too_large"),
+            ApiError::Validation { .. } => String::from("validation_failed"),
+            ApiError::InvalidReference(_) => String::from("invalid_reference"),
+            ApiError::Internal(_) => String::from("internal_error"),
+            ApiError::Unavailable(_) => String::from("service_unavailable"),
+        }
+    }
+
+    fn field_errors(&self) -> Vec<FieldError> {
+        match self {
+            ApiError::Validation { errors, .. } => errors.clone(),
+            _ => vec![],
+        }
+    }
+}
+
+// constraint violations are the client's doing, Postgres tells which one and
+// usually the offending key in the detail, e.g.
+// "Key (l_name, subdivision_id)=(12, abc) already exists."
+impl From<tokio_postgres::Error> for ApiError {
+    fn from(err: tokio_postgres::Error) -> Self {
+        let db_error = match err.as_db_error() {
+            Some(db_error) => db_error,
+            None if err.is_closed() => {
+                return ApiError::Unavailable(String::from("The database is unavailable"))
+            }
+            None => return internal_error(err.to_string()),
+        };
+
+        from_db_error(
+            db_error.code(),
+            db_error.message(),
+            db_error.detail(),
+            db_error.column(),
+        )
+    }
+}
+
+fn from_db_error(
+    code: &SqlState,
+    message: &str,
+    detail: Option<&str>,
+    column: Option<&str>,
+) -> ApiError {
+    let key_message = detail.unwrap_or(message).to_string();
+    let message = message.to_string();
+    if *code == SqlState::UNIQUE_VIOLATION {
+        ApiError::AlreadyExists(key_message)
+    } else if *code == SqlState::FOREIGN_KEY_VIOLATION {
+        ApiError::InvalidReference(key_message)
+    } else if *code == SqlState::NOT_NULL_VIOLATION || *code == SqlState::CHECK_VIOLATION {
+        // the detail would only show the rejected row
+        ApiError::Validation {
+            errors: column
+                .map(|column| vec![FieldError::new(column, message.clone())])
+                .unwrap_or_default(),
+            message,
+        }
+    } else if code.code().starts_with("22") {
+        // data exceptions, like a value too long for its column
+        ApiError::BadRequest(message)
+    } else {
+        internal_error(message)
+    }
+}
+
+// the database message can show the schema or the data of other requests and
+// reaches clients through job errors and batch reports, so it only goes to the
+// logs
+fn internal_error(message: String) -> ApiError {
+    eprintln!("database error: {}", message);
+    ApiError::Internal(String::from("Internal server error"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_violations_are_conflicts_with_the_key() {
+        let error = from_db_error(
+            &SqlState::UNIQUE_VIOLATION,
+            "duplicate key value violates unique constraint \"lot_pkey\"",
+            Some("Key (l_name, subdivision_id)=(L1, abc) already exists."),
+            None,
+        );
+
+        assert_eq!(
+            error,
+            ApiError::AlreadyExists(String::from(
+                "Key (l_name, subdivision_id)=(L1, abc) already exists."
+            ))
+        );
+        assert_eq!(error.status_code(), 409);
+        assert_eq!(error.code(), "already_exists");
+    }
+
+    #[test]
+    fn foreign_key_violations_are_invalid_references() {
+        let error = from_db_error(
+            &SqlState::FOREIGN_KEY_VIOLATION,
+            "insert or update on table \"lot\" violates foreign key constraint",
+            Some("Key (subdivision_id)=(abc) is not present in table \"subdivision\"."),
+            None,
+        );
+
+        assert_eq!(error.status_code(), 422);
+        assert_eq!(error.code(), "invalid_reference");
+        assert_eq!(
+            error.message(),
+            "Key (subdivision_id)=(abc) is not present in table \"subdivision\"."
+        );
+    }
+
+    #[test]
+    fn not_null_and_check_violations_point_at_the_column() {
+        let message = "null value in column \"s_name\" violates not-null constraint";
+        let error = from_db_error(
+            &SqlState::NOT_NULL_VIOLATION,
+            message,
+            Some("Failing row contains (abc, null)."),
+            Some("s_name"),
+        );
+
+        assert_eq!(error.status_code(), 422);
+        assert_eq!(error.message(), message);
+        assert_eq!(
+            error.field_errors(),
+            vec![FieldError::new("s_name", String::from(message))]
+        );
+
+        let error = from_db_error(
+            &SqlState::CHECK_VIOLATION,
+            "new row violates check constraint",
+            None,
+            None,
+        );
+        assert_eq!(error.status_code(), 422);
+        assert!(error.field_errors().is_empty());
+    }
+
+    #[test]
+    fn data_exceptions_are_bad_requests() {
+        for code in [
+            SqlState::STRING_DATA_RIGHT_TRUNCATION,
+            SqlState::INVALID_TEXT_REPRESENTATION,
+            SqlState::NUMERIC_VALUE_OUT_OF_RANGE,
+        ] {
+            let error = from_db_error(&code, "value too long", None, None);
+            assert_eq!(error, ApiError::BadRequest(String::from("value too long")));
+        }
+    }
+
+    #[test]
+    fn other_database_errors_hide_their_message() {
+        let error = from_db_error(
+            &SqlState::UNDEFINED_TABLE,
+            "relation \"reservation\" does not exist",
+            None,
+            None,
+        );
+
+        assert_eq!(error.status_code(), 500);
+        assert_eq!(error.message(), "Internal server error");
+    }
+}
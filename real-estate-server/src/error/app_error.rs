@@ -1,9 +1,11 @@
-use std::sync::Arc;
+use super::api_error::FieldError;
 
 pub trait AppError {
     fn message(&self) -> String;
     fn status_code(&self) -> i32;
-    fn in_short(&self) -> String;
+    // stable, machine-readable identifier of the kind of error
+    fn code(&self) -> String;
+    fn field_errors(&self) -> Vec<FieldError>;
 }
 
 pub type DynAppError = Box<dyn AppError + Send + Sync>;
@@ -1,3 +1,2 @@
+pub mod api_error;
 pub mod app_error;
-pub mod auth;
-pub mod default;
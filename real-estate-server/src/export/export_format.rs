@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    GeoJson,
+    Kml,
+    Gpx,
+    // zipped .shp, .shx, .dbf, .prj and .cpg files
+    Shapefile,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match *self {
+            ExportFormat::GeoJson => "geojson",
+            ExportFormat::Kml => "kml",
+            ExportFormat::Gpx => "gpx",
+            ExportFormat::Shapefile => "zip",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match *self {
+            ExportFormat::GeoJson => "application/geo+json",
+            ExportFormat::Kml => "application/vnd.google-earth.kml+xml",
+            ExportFormat::Gpx => "application/gpx+xml",
+            ExportFormat::Shapefile => "application/zip",
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub struct ExportedFile {
+    pub file_name: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
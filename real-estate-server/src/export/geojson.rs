@@ -0,0 +1,60 @@
+use geojson::{Feature, FeatureCollection, Geometry, JsonObject, JsonValue, Value};
+
+use crate::{
+    api_contracts::{lot_dto::LotDto, subdivision_dto::SubdivisionDto},
+    geometry::polygon::area_m2,
+};
+
+// the boundary goes first with kind "subdivision", the same layout the GeoJSON
+// import reads, so an export can be imported back
+pub fn write_feature_collection(subdivision: &SubdivisionDto, lots: &[LotDto]) -> String {
+    let mut features: Vec<Feature> = vec![];
+
+    let mut properties = JsonObject::new();
+    properties.insert(String::from("kind"), JsonValue::from("subdivision"));
+    properties.insert(
+        String::from("name"),
+        JsonValue::from(subdivision.name.clone()),
+    );
+    properties.insert(
+        String::from("area"),
+        JsonValue::from(area_m2(&subdivision.area)),
+    );
+    features.push(feature(&subdivision.area, properties));
+
+    for lot in lots.iter() {
+        let mut properties = JsonObject::new();
+        properties.insert(String::from("kind"), JsonValue::from("lot"));
+        properties.insert(String::from("name"), JsonValue::from(lot.name.clone()));
+        properties.insert(String::from("area"), JsonValue::from(area_m2(&lot.area)));
+        properties.insert(
+            String::from("status"),
+            JsonValue::from(lot.status.map(|status| status.value_str())),
+        );
+        properties.insert(String::from("price"), JsonValue::from(lot.price));
+        features.push(feature(&lot.area, properties));
+    }
+
+    FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    }
+    .to_string()
+}
+
+fn feature(area: &[(f64, f64)], properties: JsonObject) -> Feature {
+    // GeoJSON positions are [long, lat] and rings are closed
+    let mut ring: Vec<Vec<f64>> = area.iter().map(|(lat, long)| vec![*long, *lat]).collect();
+    if let Some(first) = ring.first().cloned() {
+        ring.push(first);
+    }
+
+    Feature {
+        bbox: None,
+        geometry: Some(Geometry::new(Value::Polygon(vec![ring]))),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{
+    api_contracts::{lot_dto::LotDto, subdivision_dto::SubdivisionDto},
+    geometry::polygon::area_m2,
+    subdivision::lot::LotStatus,
+};
+
+use super::xml::escape;
+
+// GPX has no polygons, every area becomes a track walking its perimeter
+pub fn write_document(subdivision: &SubdivisionDto, lots: &[LotDto]) -> String {
+    let mut gpx = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    gpx += "<gpx version=\"1.1\" creator=\"real-estate-server\" xmlns=\"http://www.topografix.com/GPX/1/1\">\n";
+    gpx += &format!(
+        "  <metadata>\n    <name>{}</name>\n  </metadata>\n",
+        escape(&subdivision.name)
+    );
+
+    gpx += &track(
+        &subdivision.name,
+        "subdivision",
+        &format!("area: {:.2} m²", area_m2(&subdivision.area)),
+        &subdivision.area,
+    );
+
+    for lot in lots.iter() {
+        let status = lot.status.unwrap_or(LotStatus::Available);
+        let mut description = format!(
+            "area: {:.2} m², status: {}",
+            area_m2(&lot.area),
+            status.value_str()
+        );
+        if let Some(price) = lot.price {
+            description += &format!(", price: {:.2}", price);
+        }
+        gpx += &track(&lot.name, "lot", &description, &lot.area);
+    }
+
+    gpx += "</gpx>\n";
+    gpx
+}
+
+fn track(name: &str, kind: &str, description: &str, area: &[(f64, f64)]) -> String {
+    let mut track = String::from("  <trk>\n");
+    track += &format!("    <name>{}</name>\n", escape(name));
+    track += &format!("    <desc>{}</desc>\n", escape(description));
+    track += &format!("    <type>{}</type>\n", kind);
+    track += "    <trkseg>\n";
+
+    // the segment ends where it started, closing the perimeter
+    for (lat, long) in area.iter().chain(area.first()) {
+        track += &format!("      <trkpt lat=\"{}\" lon=\"{}\"/>\n", lat, long);
+    }
+
+    track += "    </trkseg>\n  </trk>\n";
+    track
+}
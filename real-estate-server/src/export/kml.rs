@@ -0,0 +1,89 @@
+use crate::{
+    api_contracts::{lot_dto::LotDto, subdivision_dto::SubdivisionDto},
+    geometry::polygon::area_m2,
+    subdivision::lot::LotStatus,
+};
+
+use super::xml::escape;
+
+// colors are aabbggrr, as KML wants them
+const STYLES: &str = "
+    <Style id=\"subdivision\">
+      <LineStyle><color>ff000000</color><width>3</width></LineStyle>
+      <PolyStyle><fill>0</fill></PolyStyle>
+    </Style>
+    <Style id=\"available\">
+      <LineStyle><color>ff2e7d32</color><width>1</width></LineStyle>
+      <PolyStyle><color>664caf50</color></PolyStyle>
+    </Style>
+    <Style id=\"sold\">
+      <LineStyle><color>ff2828c6</color><width>1</width></LineStyle>
+      <PolyStyle><color>663643f4</color></PolyStyle>
+    </Style>
+";
+
+pub fn write_document(subdivision: &SubdivisionDto, lots: &[LotDto]) -> String {
+    let mut kml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    kml += "<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n  <Document>\n";
+    kml += &format!("    <name>{}</name>\n", escape(&subdivision.name));
+    kml += STYLES;
+
+    kml += &placemark(
+        &subdivision.name,
+        "subdivision",
+        &[(
+            String::from("area"),
+            format!("{:.2}", area_m2(&subdivision.area)),
+        )],
+        &subdivision.area,
+    );
+
+    for lot in lots.iter() {
+        let status = lot.status.unwrap_or(LotStatus::Available);
+        let mut data = vec![
+            (String::from("area"), format!("{:.2}", area_m2(&lot.area))),
+            (String::from("status"), String::from(status.value_str())),
+        ];
+        if let Some(price) = lot.price {
+            data.push((String::from("price"), format!("{:.2}", price)));
+        }
+        kml += &placemark(&lot.name, status.value_str(), &data, &lot.area);
+    }
+
+    kml += "  </Document>\n</kml>\n";
+    kml
+}
+
+fn placemark(name: &str, style: &str, data: &[(String, String)], area: &[(f64, f64)]) -> String {
+    let mut placemark = String::from("    <Placemark>\n");
+    placemark += &format!("      <name>{}</name>\n", escape(name));
+    placemark += &format!("      <styleUrl>#{}</styleUrl>\n", style);
+
+    placemark += "      <ExtendedData>\n";
+    for (key, value) in data.iter() {
+        placemark += &format!(
+            "        <Data name=\"{}\"><value>{}</value></Data>\n",
+            escape(key),
+            escape(value)
+        );
+    }
+    placemark += "      </ExtendedData>\n";
+
+    // KML coordinates are long,lat[,alt] and rings are closed
+    let mut coordinates: Vec<String> = area
+        .iter()
+        .map(|(lat, long)| format!("{},{},0", long, lat))
+        .collect();
+    if let Some(first) = coordinates.first().cloned() {
+        coordinates.push(first);
+    }
+
+    placemark += "      <Polygon>\n        <outerBoundaryIs>\n          <LinearRing>\n";
+    placemark += &format!(
+        "            <coordinates>{}</coordinates>\n",
+        coordinates.join(" ")
+    );
+    placemark += "          </LinearRing>\n        </outerBoundaryIs>\n      </Polygon>\n";
+    placemark += "    </Placemark>\n";
+    placemark
+}
@@ -0,0 +1,8 @@
+pub mod export_format;
+pub mod exported_file;
+pub mod geojson;
+pub mod gpx;
+pub mod kml;
+pub mod service;
+pub mod shapefile;
+pub mod xml;
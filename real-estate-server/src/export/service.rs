@@ -0,0 +1,55 @@
+use crate::{
+    error::{api_error::ApiError, app_error::DynAppError},
+    organization::tenant_scope::TenantScope,
+    subdivision::service::SubdivisionService,
+};
+
+use super::{
+    export_format::ExportFormat, exported_file::ExportedFile, geojson::write_feature_collection,
+    gpx, kml, shapefile::write_bundle,
+};
+
+#[derive(Clone)]
+pub struct ExportService {
+    subdivision_service: SubdivisionService,
+}
+
+impl ExportService {
+    pub fn new(subdivision_service: SubdivisionService) -> Self {
+        Self {
+            subdivision_service,
+        }
+    }
+
+    pub async fn export(
+        &self,
+        subdivision_id: String,
+        format: ExportFormat,
+        scope: &TenantScope,
+    ) -> Result<ExportedFile, DynAppError> {
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+        let lots = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id.clone(), scope)
+            .await?;
+
+        let content = match format {
+            ExportFormat::GeoJson => write_feature_collection(&subdivision, &lots).into_bytes(),
+            ExportFormat::Kml => kml::write_document(&subdivision, &lots).into_bytes(),
+            ExportFormat::Gpx => gpx::write_document(&subdivision, &lots).into_bytes(),
+            ExportFormat::Shapefile => match write_bundle(&subdivision, &lots) {
+                Ok(bundle) => bundle,
+                Err(err) => return Err(Box::new(ApiError::Internal(err))),
+            },
+        };
+
+        Ok(ExportedFile {
+            file_name: format!("{}.{}", subdivision_id, format.extension()),
+            content_type: String::from(format.content_type()),
+            content,
+        })
+    }
+}
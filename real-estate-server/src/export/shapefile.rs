@@ -0,0 +1,89 @@
+use std::io::{Cursor, Write};
+
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    api_contracts::{lot_dto::LotDto, subdivision_dto::SubdivisionDto},
+    geometry::polygon::area_m2,
+    shapefile::{
+        dbf::{write_dbf, DbfField, DbfTable},
+        prj::WGS84_PRJ,
+        shp::write_polygons,
+    },
+    subdivision::lot::LotStatus,
+};
+
+// the boundary goes first with KIND "subdivision", the layout the shapefile
+// import reads, so an export can be imported back
+pub fn write_bundle(subdivision: &SubdivisionDto, lots: &[LotDto]) -> Result<Vec<u8>, String> {
+    // shapefiles store x, y, the longitude first
+    let mut polygons: Vec<Vec<(f64, f64)>> = vec![swap(&subdivision.area)];
+    let mut table = DbfTable {
+        fields: vec![
+            field("KIND", 'C', 20, 0),
+            field("NAME", 'C', 254, 0),
+            field("AREA", 'N', 18, 2),
+            field("STATUS", 'C', 20, 0),
+            field("PRICE", 'N', 18, 2),
+        ],
+        records: vec![vec![
+            String::from("subdivision"),
+            subdivision.name.clone(),
+            format!("{:.2}", area_m2(&subdivision.area)),
+            String::new(),
+            String::new(),
+        ]],
+    };
+
+    for lot in lots.iter() {
+        polygons.push(swap(&lot.area));
+        table.records.push(vec![
+            String::from("lot"),
+            lot.name.clone(),
+            format!("{:.2}", area_m2(&lot.area)),
+            String::from(lot.status.unwrap_or(LotStatus::Available).value_str()),
+            lot.price
+                .map(|price| format!("{:.2}", price))
+                .unwrap_or_default(),
+        ]);
+    }
+
+    let (shp, shx) = write_polygons(&polygons);
+    let files: Vec<(&str, Vec<u8>)> = vec![
+        ("shp", shp),
+        ("shx", shx),
+        ("dbf", write_dbf(&table)),
+        ("prj", WGS84_PRJ.as_bytes().to_vec()),
+        ("cpg", b"UTF-8".to_vec()),
+    ];
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (extension, content) in files.into_iter() {
+        let written = zip
+            .start_file(format!("{}.{}", subdivision.id, extension), options)
+            .map_err(|err| err.to_string())
+            .and_then(|_| zip.write_all(&content).map_err(|err| err.to_string()));
+        if let Err(err) = written {
+            return Err(format!("Could not write the shapefile bundle: {}", err));
+        }
+    }
+
+    match zip.finish() {
+        Ok(cursor) => Ok(cursor.into_inner()),
+        Err(err) => Err(format!("Could not write the shapefile bundle: {}", err)),
+    }
+}
+
+fn field(name: &str, kind: char, length: u8, decimals: u8) -> DbfField {
+    DbfField {
+        name: String::from(name),
+        kind,
+        length,
+        decimals,
+    }
+}
+
+fn swap(area: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    area.iter().map(|(lat, long)| (*long, *lat)).collect()
+}
@@ -0,0 +1,222 @@
+use super::utm::{Hemisphere, UtmZone};
+
+// The coordinate reference systems areas can be sent and requested in. The
+// server stores everything in WGS84.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Crs {
+    // EPSG:4326, (lat, long) pairs
+    Wgs84,
+    // EPSG:4674, (lat, long) pairs. Its ellipsoid and realization match WGS84
+    // to a few centimeters, so no conversion is made
+    Sirgas2000,
+    // EPSG:31965 to 31985, (easting, northing) pairs
+    Sirgas2000Utm(UtmZone),
+    // EPSG:32601 to 32760, (easting, northing) pairs
+    Wgs84Utm(UtmZone),
+}
+
+impl Crs {
+    // accepts "EPSG:31983" as well as the bare number
+    pub fn from_code(code: &str) -> Option<Crs> {
+        let code = code.trim();
+        let number = match code.get(..5) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("EPSG:") => &code[5..],
+            _ => code,
+        };
+
+        match number.parse::<u32>().ok()? {
+            4326 => Some(Crs::Wgs84),
+            4674 => Some(Crs::Sirgas2000),
+            // SIRGAS 2000 only defines zones 11N to 22N and 17S to 25S
+            code @ 31965..=31976 => {
+                UtmZone::new((code - 31954) as u8, Hemisphere::North).map(Crs::Sirgas2000Utm)
+            }
+            code @ 31977..=31985 => {
+                UtmZone::new((code - 31960) as u8, Hemisphere::South).map(Crs::Sirgas2000Utm)
+            }
+            code @ 32601..=32660 => {
+                UtmZone::new((code - 32600) as u8, Hemisphere::North).map(Crs::Wgs84Utm)
+            }
+            code @ 32701..=32760 => {
+                UtmZone::new((code - 32700) as u8, Hemisphere::South).map(Crs::Wgs84Utm)
+            }
+            _ => None,
+        }
+    }
+
+    // SIRGAS 2000 where it defines the zone, WGS84 elsewhere
+    pub fn utm_containing(lat: f64, long: f64) -> Crs {
+        let sirgas = Crs::Sirgas2000Utm(UtmZone::containing(lat, long));
+        match Crs::from_code(&sirgas.code()) {
+            Some(crs) if crs == sirgas => sirgas,
+            _ => Crs::Wgs84Utm(UtmZone::containing(lat, long)),
+        }
+    }
+
+    // as the EPSG registry names it
+    pub fn name(&self) -> String {
+        let zone_name = |zone: &UtmZone| {
+            let hemisphere = match zone.hemisphere {
+                Hemisphere::North => "N",
+                Hemisphere::South => "S",
+            };
+            format!("UTM zone {}{}", zone.number, hemisphere)
+        };
+
+        match self {
+            Crs::Wgs84 => String::from("WGS 84"),
+            Crs::Sirgas2000 => String::from("SIRGAS 2000"),
+            Crs::Sirgas2000Utm(zone) => format!("SIRGAS 2000 / {}", zone_name(zone)),
+            Crs::Wgs84Utm(zone) => format!("WGS 84 / {}", zone_name(zone)),
+        }
+    }
+
+    pub fn code(&self) -> String {
+        let number = match *self {
+            Crs::Wgs84 => 4326,
+            Crs::Sirgas2000 => 4674,
+            Crs::Sirgas2000Utm(zone) => match zone.hemisphere {
+                Hemisphere::North => 31954 + zone.number as u32,
+                Hemisphere::South => 31960 + zone.number as u32,
+            },
+            Crs::Wgs84Utm(zone) => match zone.hemisphere {
+                Hemisphere::North => 32600 + zone.number as u32,
+                Hemisphere::South => 32700 + zone.number as u32,
+            },
+        };
+        format!("EPSG:{}", number)
+    }
+
+    // returns (lat, long) in WGS84
+    pub fn to_wgs84(&self, point: (f64, f64)) -> (f64, f64) {
+        match self {
+            Crs::Wgs84 | Crs::Sirgas2000 => point,
+            Crs::Sirgas2000Utm(zone) | Crs::Wgs84Utm(zone) => zone.to_wgs84(point.0, point.1),
+        }
+    }
+
+    // takes (lat, long) in WGS84
+    pub fn from_wgs84(&self, point: (f64, f64)) -> (f64, f64) {
+        match self {
+            Crs::Wgs84 | Crs::Sirgas2000 => point,
+            Crs::Sirgas2000Utm(zone) | Crs::Wgs84Utm(zone) => zone.from_wgs84(point.0, point.1),
+        }
+    }
+
+    pub fn area_to_wgs84(&self, area: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        area.iter().map(|point| self.to_wgs84(*point)).collect()
+    }
+
+    pub fn area_from_wgs84(&self, area: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        area.iter().map(|point| self.from_wgs84(*point)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utm(number: u8, hemisphere: Hemisphere) -> UtmZone {
+        UtmZone::new(number, hemisphere).unwrap()
+    }
+
+    #[test]
+    fn maps_sirgas_2000_utm_codes_to_their_zones() {
+        assert_eq!(
+            Crs::from_code("EPSG:31983"),
+            Some(Crs::Sirgas2000Utm(utm(23, Hemisphere::South)))
+        );
+        assert_eq!(
+            Crs::from_code("31965"),
+            Some(Crs::Sirgas2000Utm(utm(11, Hemisphere::North)))
+        );
+        assert_eq!(
+            Crs::from_code("31976"),
+            Some(Crs::Sirgas2000Utm(utm(22, Hemisphere::North)))
+        );
+        assert_eq!(
+            Crs::from_code("31977"),
+            Some(Crs::Sirgas2000Utm(utm(17, Hemisphere::South)))
+        );
+        assert_eq!(
+            Crs::from_code("31985"),
+            Some(Crs::Sirgas2000Utm(utm(25, Hemisphere::South)))
+        );
+    }
+
+    #[test]
+    fn maps_wgs84_utm_codes_to_their_zones() {
+        assert_eq!(
+            Crs::from_code("EPSG:32723"),
+            Some(Crs::Wgs84Utm(utm(23, Hemisphere::South)))
+        );
+        assert_eq!(
+            Crs::from_code("32601"),
+            Some(Crs::Wgs84Utm(utm(1, Hemisphere::North)))
+        );
+        assert_eq!(
+            Crs::from_code("32760"),
+            Some(Crs::Wgs84Utm(utm(60, Hemisphere::South)))
+        );
+    }
+
+    #[test]
+    fn accepts_geographic_codes_in_any_case() {
+        assert_eq!(Crs::from_code("EPSG:4326"), Some(Crs::Wgs84));
+        assert_eq!(Crs::from_code(" epsg:4674 "), Some(Crs::Sirgas2000));
+        assert_eq!(Crs::from_code("4326"), Some(Crs::Wgs84));
+    }
+
+    #[test]
+    fn rejects_unknown_codes() {
+        // outside the zones SIRGAS 2000 defines
+        assert_eq!(Crs::from_code("31964"), None);
+        assert_eq!(Crs::from_code("31986"), None);
+        assert_eq!(Crs::from_code("32661"), None);
+        assert_eq!(Crs::from_code("3857"), None);
+        assert_eq!(Crs::from_code("EPSG:"), None);
+        assert_eq!(Crs::from_code("UTM 23S"), None);
+    }
+
+    #[test]
+    fn codes_are_read_back_as_the_same_crs() {
+        let codes = (31965..=31985).chain(32601..=32660).chain(32701..=32760);
+        for number in codes.chain([4326, 4674]) {
+            let crs = Crs::from_code(&number.to_string()).unwrap();
+            assert_eq!(crs.code(), format!("EPSG:{}", number));
+        }
+    }
+
+    #[test]
+    fn prefers_sirgas_2000_where_it_defines_the_zone() {
+        assert_eq!(
+            Crs::utm_containing(-23.55, -46.63),
+            Crs::Sirgas2000Utm(utm(23, Hemisphere::South))
+        );
+        // Paris is in zone 31N, which SIRGAS 2000 does not define
+        assert_eq!(
+            Crs::utm_containing(48.85, 2.35),
+            Crs::Wgs84Utm(utm(31, Hemisphere::North))
+        );
+        assert_eq!(
+            Crs::utm_containing(-23.55, -46.63).name(),
+            "SIRGAS 2000 / UTM zone 23S"
+        );
+    }
+
+    #[test]
+    fn converts_areas_through_the_zone() {
+        let crs = Crs::from_code("EPSG:31983").unwrap();
+        let area = [(333287.021528, 7394586.093403), (333300.0, 7394600.0)];
+
+        let wgs84 = crs.area_to_wgs84(&area);
+        assert!((wgs84[0].0 - -23.550520).abs() < 1e-8);
+        assert!((wgs84[0].1 - -46.633309).abs() < 1e-8);
+
+        for (back, original) in crs.area_from_wgs84(&wgs84).iter().zip(area) {
+            assert!((back.0 - original.0).abs() < 0.001);
+            assert!((back.1 - original.1).abs() < 0.001);
+        }
+        assert_eq!(Crs::Sirgas2000.area_to_wgs84(&area), area.to_vec());
+    }
+}
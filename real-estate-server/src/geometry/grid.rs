@@ -0,0 +1,287 @@
+// Splits planar polygons, coordinates in meters as (easting, northing).
+
+// below this area a piece left by the split is taken as a sliver and dropped
+const MIN_PIECE_AREA_M2: f64 = 1.0;
+
+// Cuts the polygon into strips running along the depth direction, an azimuth in
+// degrees clockwise from the north. Strips share the same frontage, as close to
+// the requested one as the polygon width allows, and come ordered left to right
+// when looking along the depth direction. When that takes more than max_strips
+// strips nothing is built and the error has the amount it would take.
+pub fn split_into_strips(
+    points: &[(f64, f64)],
+    frontage: f64,
+    depth_azimuth: f64,
+    max_strips: usize,
+) -> Result<Vec<Vec<(f64, f64)>>, usize> {
+    let angle = depth_azimuth.to_radians();
+    let depth = (angle.sin(), angle.cos());
+    // the depth direction turned 90 degrees clockwise
+    let across = (angle.cos(), -angle.sin());
+
+    // (across, depth) coordinates
+    let local: Vec<(f64, f64)> = points
+        .iter()
+        .map(|p| (dot(*p, across), dot(*p, depth)))
+        .collect();
+
+    let min = local.iter().map(|p| p.0).fold(f64::MAX, f64::min);
+    let max = local.iter().map(|p| p.0).fold(f64::MIN, f64::max);
+    if local.len() < 3 || max <= min || frontage <= 0.0 {
+        return Ok(vec![]);
+    }
+
+    // checked before the conversion, a tiny frontage does not fit in an usize
+    let amount = ((max - min) / frontage).floor().max(1.0);
+    if amount > max_strips as f64 {
+        return Err(amount as usize);
+    }
+    let amount = amount as usize;
+    let width = (max - min) / amount as f64;
+    // neighbouring strips are cut at the very same position
+    let cut = |k: usize| {
+        if k == amount {
+            max
+        } else {
+            min + width * k as f64
+        }
+    };
+
+    let mut strips: Vec<Vec<(f64, f64)>> = vec![];
+    for i in 0..amount {
+        let piece = clip(&clip(&local, cut(i), true), cut(i + 1), false);
+        if planar_area(&piece) < MIN_PIECE_AREA_M2 {
+            continue;
+        }
+
+        strips.push(
+            piece
+                .iter()
+                .map(|(u, v)| (u * across.0 + v * depth.0, u * across.1 + v * depth.1))
+                .collect(),
+        );
+    }
+
+    Ok(strips)
+}
+
+pub fn planar_area(points: &[(f64, f64)]) -> f64 {
+    let mut sum = 0.0;
+    for i in 0..points.len() {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % points.len()];
+        sum += x1 * y2 - x2 * y1;
+    }
+    (sum / 2.0).abs()
+}
+
+fn dot(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+// Sutherland-Hodgman against the half plane u >= limit, or u <= limit when
+// keep_above is false
+fn clip(points: &[(f64, f64)], limit: f64, keep_above: bool) -> Vec<(f64, f64)> {
+    let inside = |p: &(f64, f64)| {
+        if keep_above {
+            p.0 >= limit
+        } else {
+            p.0 <= limit
+        }
+    };
+
+    let mut clipped: Vec<(f64, f64)> = vec![];
+    for i in 0..points.len() {
+        let current = points[i];
+        let next = points[(i + 1) % points.len()];
+
+        if inside(&current) {
+            clipped.push(current);
+        }
+        if inside(&current) != inside(&next) {
+            let t = (limit - current.0) / (next.0 - current.0);
+            clipped.push((limit, current.1 + t * (next.1 - current.1)));
+        }
+    }
+
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_STRIPS: usize = 500;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    fn total_area(pieces: &[Vec<(f64, f64)>]) -> f64 {
+        pieces.iter().map(|piece| planar_area(piece)).sum()
+    }
+
+    // the centroid of the vertices, enough to tell the pieces apart
+    fn center(piece: &[(f64, f64)]) -> (f64, f64) {
+        let amount = piece.len() as f64;
+        let sum = piece
+            .iter()
+            .fold((0.0, 0.0), |sum, p| (sum.0 + p.0, sum.1 + p.1));
+        (sum.0 / amount, sum.1 / amount)
+    }
+
+    #[test]
+    fn splits_a_rectangle_into_equal_lots() {
+        // 100 m of frontage along the east, 30 m deep to the north
+        let block = [(0.0, 0.0), (100.0, 0.0), (100.0, 30.0), (0.0, 30.0)];
+
+        let lots = split_into_strips(&block, 10.0, 0.0, MAX_STRIPS).unwrap();
+
+        assert_eq!(lots.len(), 10);
+        for lot in lots.iter() {
+            assert_close(planar_area(lot), 300.0);
+        }
+        // left to right looking north
+        for (index, lot) in lots.iter().enumerate() {
+            assert_close(center(lot).0, 5.0 + 10.0 * index as f64);
+        }
+    }
+
+    #[test]
+    fn widens_the_frontage_to_use_the_whole_block() {
+        let block = [(0.0, 0.0), (100.0, 0.0), (100.0, 30.0), (0.0, 30.0)];
+
+        // 8 lots of 12.5 m instead of 8 of 12 m and a narrow one
+        let lots = split_into_strips(&block, 12.0, 0.0, MAX_STRIPS).unwrap();
+
+        assert_eq!(lots.len(), 8);
+        for lot in lots.iter() {
+            assert_close(planar_area(lot), 12.5 * 30.0);
+        }
+    }
+
+    #[test]
+    fn splits_along_any_depth_direction() {
+        // the same block turned 30 degrees, with the depth still across its
+        // short side
+        let angle = 30_f64.to_radians();
+        let turn = |(x, y): (f64, f64)| {
+            (
+                x * angle.cos() + y * angle.sin(),
+                -x * angle.sin() + y * angle.cos(),
+            )
+        };
+        let block: Vec<(f64, f64)> = [(0.0, 0.0), (100.0, 0.0), (100.0, 30.0), (0.0, 30.0)]
+            .into_iter()
+            .map(turn)
+            .collect();
+
+        let lots = split_into_strips(&block, 20.0, 30.0, MAX_STRIPS).unwrap();
+
+        assert_eq!(lots.len(), 5);
+        for lot in lots.iter() {
+            assert_close(planar_area(lot), 600.0);
+        }
+    }
+
+    #[test]
+    fn lots_of_a_concave_block_add_up_to_its_area() {
+        // an L with a 60 m long arm and a 50 m deep one
+        let l_block = [
+            (0.0, 0.0),
+            (60.0, 0.0),
+            (60.0, 20.0),
+            (20.0, 20.0),
+            (20.0, 50.0),
+            (0.0, 50.0),
+        ];
+        let lots = split_into_strips(&l_block, 10.0, 0.0, MAX_STRIPS).unwrap();
+        assert_eq!(lots.len(), 6);
+        assert_close(planar_area(&l_block), 1800.0);
+        assert_close(total_area(&lots), 1800.0);
+        assert_close(planar_area(&lots[0]), 500.0);
+        assert_close(planar_area(&lots[5]), 200.0);
+
+        // a U whose gap falls inside the strips
+        let u_block = [
+            (0.0, 0.0),
+            (50.0, 0.0),
+            (50.0, 40.0),
+            (35.0, 40.0),
+            (35.0, 10.0),
+            (15.0, 10.0),
+            (15.0, 40.0),
+            (0.0, 40.0),
+        ];
+        let lots = split_into_strips(&u_block, 25.0, 0.0, MAX_STRIPS).unwrap();
+        assert_eq!(lots.len(), 2);
+        assert_close(total_area(&lots), planar_area(&u_block));
+        assert_close(total_area(&lots), 1400.0);
+    }
+
+    #[test]
+    fn drops_slivers_left_by_the_split() {
+        // a triangle whose tip, narrower than a square meter, is not a lot
+        let block = [(0.0, 0.0), (30.0, 0.0), (0.0, 30.0)];
+
+        let lots = split_into_strips(&block, 0.5, 0.0, MAX_STRIPS).unwrap();
+
+        assert!(lots.iter().all(|lot| planar_area(lot) >= MIN_PIECE_AREA_M2));
+        assert!(total_area(&lots) < planar_area(&block));
+    }
+
+    #[test]
+    fn neighbouring_lots_share_the_cut() {
+        // 100 m do not split evenly into 3 lots
+        let block = [(0.0, 0.0), (100.0, 0.0), (100.0, 30.0), (0.0, 30.0)];
+
+        let lots = split_into_strips(&block, 33.0, 0.0, MAX_STRIPS).unwrap();
+
+        assert_eq!(lots.len(), 3);
+        let east = |lot: &Vec<(f64, f64)>| lot.iter().map(|p| p.0).fold(f64::MIN, f64::max);
+        let west = |lot: &Vec<(f64, f64)>| lot.iter().map(|p| p.0).fold(f64::MAX, f64::min);
+        for pair in lots.windows(2) {
+            assert_eq!(east(&pair[0]), west(&pair[1]));
+        }
+        assert_eq!(east(&lots[2]), 100.0);
+    }
+
+    #[test]
+    fn refuses_more_strips_than_the_limit() {
+        let block = [(0.0, 0.0), (100.0, 0.0), (100.0, 30.0), (0.0, 30.0)];
+
+        assert_eq!(split_into_strips(&block, 10.0, 0.0, 9), Err(10));
+        assert_eq!(split_into_strips(&block, 10.0, 0.0, 10).unwrap().len(), 10);
+        // far more strips than an usize holds
+        assert!(split_into_strips(&block, 1e-300, 0.0, MAX_STRIPS).is_err());
+    }
+
+    #[test]
+    fn returns_nothing_for_degenerate_input() {
+        let block = [(0.0, 0.0), (100.0, 0.0), (100.0, 30.0), (0.0, 30.0)];
+
+        assert!(split_into_strips(&block[..2], 10.0, 0.0, MAX_STRIPS)
+            .unwrap()
+            .is_empty());
+        assert!(split_into_strips(&block, 0.0, 0.0, MAX_STRIPS)
+            .unwrap()
+            .is_empty());
+        assert!(split_into_strips(&block, -5.0, 0.0, MAX_STRIPS)
+            .unwrap()
+            .is_empty());
+        // no width across the depth direction
+        assert!(split_into_strips(
+            &[(0.0, 0.0), (0.0, 10.0), (0.0, 20.0)],
+            10.0,
+            0.0,
+            MAX_STRIPS
+        )
+        .unwrap()
+        .is_empty());
+    }
+}
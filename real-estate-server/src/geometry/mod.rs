@@ -0,0 +1,4 @@
+pub mod crs;
+pub mod grid;
+pub mod polygon;
+pub mod utm;
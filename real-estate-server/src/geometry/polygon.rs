@@ -0,0 +1,142 @@
+// Coordinates follow the rest of the server: (lat, long) pairs in WGS84 degrees.
+
+pub const EARTH_RADIUS_M: f64 = 6_371_008.8;
+
+// Projects the points onto a plane tangent to their mean latitude. The error is
+// negligible at the scale of a subdivision, which is all we need here.
+pub fn to_local_meters(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    if points.is_empty() {
+        return vec![];
+    }
+
+    let mean_lat = points.iter().map(|p| p.0).sum::<f64>() / points.len() as f64;
+    let cos_lat = mean_lat.to_radians().cos();
+
+    points
+        .iter()
+        .map(|(lat, long)| {
+            (
+                EARTH_RADIUS_M * long.to_radians() * cos_lat,
+                EARTH_RADIUS_M * lat.to_radians(),
+            )
+        })
+        .collect()
+}
+
+pub fn area_m2(points: &[(f64, f64)]) -> f64 {
+    if points.len() < 3 {
+        return 0.0;
+    }
+
+    let projected = to_local_meters(points);
+    let mut sum = 0.0;
+    for i in 0..projected.len() {
+        let (x1, y1) = projected[i];
+        let (x2, y2) = projected[(i + 1) % projected.len()];
+        sum += x1 * y2 - x2 * y1;
+    }
+
+    (sum / 2.0).abs()
+}
+
+// length of the open path through the points, in meters
+pub fn length_m(points: &[(f64, f64)]) -> f64 {
+    let projected = to_local_meters(points);
+    projected
+        .windows(2)
+        .map(|pair| ((pair[1].0 - pair[0].0).powi(2) + (pair[1].1 - pair[0].1).powi(2)).sqrt())
+        .sum()
+}
+
+// ray casting, points exactly on an edge may fall on either side
+pub fn contains_point(polygon: &[(f64, f64)], point: (f64, f64)) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for i in 0..polygon.len() {
+        let (lat_i, long_i) = polygon[i];
+        let (lat_j, long_j) = polygon[j];
+        if (lat_i > point.0) != (lat_j > point.0)
+            && point.1 < (long_j - long_i) * (point.0 - lat_i) / (lat_j - lat_i) + long_i
+        {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+// drops the closing vertex repeated by formats such as GeoJSON and shapefiles,
+// the server stores rings open
+pub fn open_ring(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let mut ring = points.to_vec();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+// checks an open ring, returning a description of every problem found
+pub fn validate_polygon(points: &[(f64, f64)]) -> Vec<String> {
+    let mut errors: Vec<String> = vec![];
+
+    for (lat, long) in points.iter() {
+        if !(-90.0..=90.0).contains(lat) || !(-180.0..=180.0).contains(long) {
+            errors.push(format!("Coordinate ({}, {}) is out of range", lat, long));
+        }
+    }
+
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            if points[i] == points[j] {
+                errors.push(format!("Vertex {} is repeated as vertex {}", i + 1, j + 1));
+            }
+        }
+    }
+
+    if points.len() < 3 {
+        errors.push(String::from("A polygon needs at least 3 vertices"));
+        return errors;
+    }
+
+    if area_m2(points) < 1e-6 {
+        errors.push(String::from("The polygon has no area"));
+    }
+
+    if is_self_intersecting(points) {
+        errors.push(String::from("The polygon boundary intersects itself"));
+    }
+
+    errors
+}
+
+pub fn is_self_intersecting(points: &[(f64, f64)]) -> bool {
+    let n = points.len();
+    for i in 0..n {
+        let a = (points[i], points[(i + 1) % n]);
+        for j in i + 1..n {
+            // adjacent edges share a vertex by construction
+            if j == i + 1 || (i == 0 && j == n - 1) {
+                continue;
+            }
+            let b = (points[j], points[(j + 1) % n]);
+            if segments_intersect(a.0, a.1, b.0, b.1) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn segments_intersect(p1: (f64, f64), p2: (f64, f64), q1: (f64, f64), q2: (f64, f64)) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+
+    ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+}
+
+fn orientation(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// GRS80, the ellipsoid of SIRGAS 2000. WGS84 differs from it by less than a
+// millimeter, so coordinates converted with it are taken as WGS84
+const SEMI_MAJOR_AXIS_M: f64 = 6_378_137.0;
+const FLATTENING: f64 = 1.0 / 298.257_222_101;
+const SCALE_FACTOR: f64 = 0.9996;
+const FALSE_EASTING_M: f64 = 500_000.0;
+const FALSE_NORTHING_SOUTH_M: f64 = 10_000_000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Hemisphere {
+    North,
+    South,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UtmZone {
+    // 1 to 60
+    pub number: u8,
+    pub hemisphere: Hemisphere,
+}
+
+impl UtmZone {
+    pub fn new(number: u8, hemisphere: Hemisphere) -> Option<UtmZone> {
+        if (1..=60).contains(&number) {
+            Some(UtmZone { number, hemisphere })
+        } else {
+            None
+        }
+    }
+
+    // the zone whose 6 degree band holds the point, ignoring the Norway and
+    // Svalbard exceptions
+    pub fn containing(lat: f64, long: f64) -> UtmZone {
+        let number = (((long + 180.0) / 6.0).floor() as i32 + 1).clamp(1, 60) as u8;
+        let hemisphere = if lat < 0.0 {
+            Hemisphere::South
+        } else {
+            Hemisphere::North
+        };
+        UtmZone { number, hemisphere }
+    }
+
+    fn central_meridian(&self) -> f64 {
+        (self.number as f64 * 6.0 - 183.0).to_radians()
+    }
+
+    // transverse Mercator, series from Snyder's "Map Projections - A Working
+    // Manual". Returns (easting, northing)
+    pub fn from_wgs84(&self, lat: f64, long: f64) -> (f64, f64) {
+        let e2 = FLATTENING * (2.0 - FLATTENING);
+        let ep2 = e2 / (1.0 - e2);
+
+        let phi = lat.to_radians();
+        let sin_phi = phi.sin();
+        let cos_phi = phi.cos();
+        let tan_phi = phi.tan();
+
+        let n = SEMI_MAJOR_AXIS_M / (1.0 - e2 * sin_phi.powi(2)).sqrt();
+        let t = tan_phi.powi(2);
+        let c = ep2 * cos_phi.powi(2);
+        let a = cos_phi * (long.to_radians() - self.central_meridian());
+
+        let m = SEMI_MAJOR_AXIS_M
+            * ((1.0 - e2 / 4.0 - 3.0 * e2.powi(2) / 64.0 - 5.0 * e2.powi(3) / 256.0) * phi
+                - (3.0 * e2 / 8.0 + 3.0 * e2.powi(2) / 32.0 + 45.0 * e2.powi(3) / 1024.0)
+                    * (2.0 * phi).sin()
+                + (15.0 * e2.powi(2) / 256.0 + 45.0 * e2.powi(3) / 1024.0) * (4.0 * phi).sin()
+                - (35.0 * e2.powi(3) / 3072.0) * (6.0 * phi).sin());
+
+        let easting = SCALE_FACTOR
+            * n
+            * (a + (1.0 - t + c) * a.powi(3) / 6.0
+                + (5.0 - 18.0 * t + t.powi(2) + 72.0 * c - 58.0 * ep2) * a.powi(5) / 120.0)
+            + FALSE_EASTING_M;
+
+        let northing = SCALE_FACTOR
+            * (m + n
+                * tan_phi
+                * (a.powi(2) / 2.0
+                    + (5.0 - t + 9.0 * c + 4.0 * c.powi(2)) * a.powi(4) / 24.0
+                    + (61.0 - 58.0 * t + t.powi(2) + 600.0 * c - 330.0 * ep2) * a.powi(6) / 720.0));
+
+        match self.hemisphere {
+            Hemisphere::North => (easting, northing),
+            Hemisphere::South => (easting, northing + FALSE_NORTHING_SOUTH_M),
+        }
+    }
+
+    // inverse transverse Mercator, series from Snyder's "Map Projections - A
+    // Working Manual", accurate to well under a meter inside the zone
+    pub fn to_wgs84(&self, easting: f64, northing: f64) -> (f64, f64) {
+        let e2 = FLATTENING * (2.0 - FLATTENING);
+        let ep2 = e2 / (1.0 - e2);
+
+        let x = easting - FALSE_EASTING_M;
+        let y = match self.hemisphere {
+            Hemisphere::North => northing,
+            Hemisphere::South => northing - FALSE_NORTHING_SOUTH_M,
+        };
+
+        let m = y / SCALE_FACTOR;
+        let mu = m
+            / (SEMI_MAJOR_AXIS_M
+                * (1.0 - e2 / 4.0 - 3.0 * e2.powi(2) / 64.0 - 5.0 * e2.powi(3) / 256.0));
+
+        let e1 = (1.0 - (1.0 - e2).sqrt()) / (1.0 + (1.0 - e2).sqrt());
+        let phi1 = mu
+            + (3.0 * e1 / 2.0 - 27.0 * e1.powi(3) / 32.0) * (2.0 * mu).sin()
+            + (21.0 * e1.powi(2) / 16.0 - 55.0 * e1.powi(4) / 32.0) * (4.0 * mu).sin()
+            + (151.0 * e1.powi(3) / 96.0) * (6.0 * mu).sin()
+            + (1097.0 * e1.powi(4) / 512.0) * (8.0 * mu).sin();
+
+        let sin_phi1 = phi1.sin();
+        let cos_phi1 = phi1.cos();
+        let tan_phi1 = phi1.tan();
+
+        let c1 = ep2 * cos_phi1.powi(2);
+        let t1 = tan_phi1.powi(2);
+        let n1 = SEMI_MAJOR_AXIS_M / (1.0 - e2 * sin_phi1.powi(2)).sqrt();
+        let r1 = SEMI_MAJOR_AXIS_M * (1.0 - e2) / (1.0 - e2 * sin_phi1.powi(2)).powf(1.5);
+        let d = x / (n1 * SCALE_FACTOR);
+
+        let lat = phi1
+            - (n1 * tan_phi1 / r1)
+                * (d.powi(2) / 2.0
+                    - (5.0 + 3.0 * t1 + 10.0 * c1 - 4.0 * c1.powi(2) - 9.0 * ep2) * d.powi(4)
+                        / 24.0
+                    + (61.0 + 90.0 * t1 + 298.0 * c1 + 45.0 * t1.powi(2)
+                        - 252.0 * ep2
+                        - 3.0 * c1.powi(2))
+                        * d.powi(6)
+                        / 720.0);
+
+        let long = self.central_meridian()
+            + (d - (1.0 + 2.0 * t1 + c1) * d.powi(3) / 6.0
+                + (5.0 - 2.0 * c1 + 28.0 * t1 - 3.0 * c1.powi(2) + 8.0 * ep2 + 24.0 * t1.powi(2))
+                    * d.powi(5)
+                    / 120.0)
+                / cos_phi1;
+
+        (lat.to_degrees(), long.to_degrees())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (lat, long) in SIRGAS 2000 and (easting, northing) in the zone, computed
+    // with Karney's sixth order Krüger series, the method PROJ uses for the
+    // SIRGAS 2000 / UTM projections, which is exact to a few nanometers
+    const ZONE_23S: [((f64, f64), (f64, f64)); 4] = [
+        // marco zero of São Paulo, Praça da Sé
+        ((-23.550520, -46.633309), (333287.021528, 7394586.093403)),
+        // Brasília, close to the western edge of the zone
+        ((-15.793889, -47.882778), (191141.085158, 8251747.156043)),
+        // Rio de Janeiro, east of the central meridian
+        ((-22.906800, -43.172900), (687394.593292, 7465634.127791)),
+        // on the central meridian
+        ((-20.0, -45.0), (500000.0, 7788518.692399)),
+    ];
+
+    fn zone(number: u8, hemisphere: Hemisphere) -> UtmZone {
+        UtmZone::new(number, hemisphere).unwrap()
+    }
+
+    fn assert_within_mm(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 0.001 && (actual.1 - expected.1).abs() < 0.001,
+            "{:?} is not {:?}",
+            actual,
+            expected
+        );
+    }
+
+    // a millimeter is about 1e-8 degrees
+    fn assert_same_point(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-8 && (actual.1 - expected.1).abs() < 1e-8,
+            "{:?} is not {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn projects_reference_points_of_epsg_31983() {
+        for ((lat, long), projected) in ZONE_23S {
+            assert_within_mm(zone(23, Hemisphere::South).from_wgs84(lat, long), projected);
+        }
+    }
+
+    #[test]
+    fn unprojects_reference_points_of_epsg_31983() {
+        for (point, (easting, northing)) in ZONE_23S {
+            assert_same_point(
+                zone(23, Hemisphere::South).to_wgs84(easting, northing),
+                point,
+            );
+        }
+    }
+
+    #[test]
+    fn projects_other_zones_and_the_northern_hemisphere() {
+        // Porto Alegre in EPSG:31982 and Boa Vista in EPSG:31974
+        assert_within_mm(
+            zone(22, Hemisphere::South).from_wgs84(-30.0346, -51.2177),
+            (479010.600975, 6677360.718470),
+        );
+        assert_within_mm(
+            zone(20, Hemisphere::North).from_wgs84(2.8235, -60.6758),
+            (758384.404168, 312342.381116),
+        );
+        assert_same_point(
+            zone(20, Hemisphere::North).to_wgs84(758384.404168, 312342.381116),
+            (2.8235, -60.6758),
+        );
+    }
+
+    #[test]
+    fn round_trips_within_millimeters_across_the_zone() {
+        let zone = zone(23, Hemisphere::South);
+        let mut lat = -33.0;
+        while lat <= 5.0 {
+            // from the western to the eastern edge of the zone
+            let mut long = -48.0;
+            while long <= -42.0 {
+                let (easting, northing) = zone.from_wgs84(lat, long);
+                let (back_lat, back_long) = zone.to_wgs84(easting, northing);
+                let (again_easting, again_northing) = zone.from_wgs84(back_lat, back_long);
+
+                assert_within_mm((again_easting, again_northing), (easting, northing));
+                assert_same_point((back_lat, back_long), (lat, long));
+                long += 0.5;
+            }
+            lat += 2.0;
+        }
+    }
+
+    #[test]
+    fn finds_the_zone_containing_a_point() {
+        assert_eq!(
+            UtmZone::containing(-23.55, -46.63),
+            zone(23, Hemisphere::South)
+        );
+        assert_eq!(
+            UtmZone::containing(2.82, -60.68),
+            zone(20, Hemisphere::North)
+        );
+        // bands start at their western meridian
+        assert_eq!(UtmZone::containing(-10.0, -48.0).number, 23);
+        assert_eq!(UtmZone::containing(0.0, -180.0).number, 1);
+        assert_eq!(UtmZone::containing(0.0, 180.0).number, 60);
+    }
+
+    #[test]
+    fn rejects_zones_out_of_range() {
+        assert_eq!(UtmZone::new(0, Hemisphere::South), None);
+        assert_eq!(UtmZone::new(61, Hemisphere::North), None);
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::{analytics_params::AnalyticsParams, analytics_report::AnalyticsReport},
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, RecordSales},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+// sale figures are kept from buyers, like the contracts
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/analytics",
+    tag = "analytics",
+    params(AnalyticsParams),
+    responses((status = 200, body = AnalyticsReport)),
+)]
+pub async fn analytics_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<RecordSales>,
+    Query(params): Query<AnalyticsParams>,
+) -> Response {
+    match app_state
+        .analytics_service
+        .get_report(params, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(report) => Json(report).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::api_key_dto::{ApiKeyCreationPayload, ApiKeyDto},
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ManageApiKeys},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/organizations/{organization_id}/api-keys",
+    tag = "api-keys",
+    params(("organization_id" = String, Path)),
+    request_body = ApiKeyCreationPayload,
+    responses((status = 201, description = "The only response holding the key itself", body = ApiKeyDto)),
+)]
+pub async fn api_key_creation_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageApiKeys>,
+    Path(organization_id): Path<String>,
+    Json(payload): Json<ApiKeyCreationPayload>,
+) -> Response {
+    match app_state
+        .api_key_service
+        .create(organization_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
+        // the only response carrying the plain key, it must not be cached or
+        // stored for idempotent replays
+        Ok(api_key) => (
+            StatusCode::CREATED,
+            [(header::CACHE_CONTROL, "no-store")],
+            Json(api_key),
+        )
+            .into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/organizations/{organization_id}/api-keys",
+    tag = "api-keys",
+    params(("organization_id" = String, Path)),
+    responses((status = 200, body = Vec<ApiKeyDto>)),
+)]
+pub async fn api_key_listing_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageApiKeys>,
+    Path(organization_id): Path<String>,
+) -> Response {
+    match app_state
+        .api_key_service
+        .get_by_organization(organization_id, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(api_keys) => Json(api_keys).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/real-estate/organizations/{organization_id}/api-keys/{api_key_id}",
+    tag = "api-keys",
+    params(("organization_id" = String, Path), ("api_key_id" = String, Path)),
+    responses((status = 204, description = "The key was revoked")),
+)]
+pub async fn api_key_revocation_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageApiKeys>,
+    Path((organization_id, api_key_id)): Path<(String, String)>,
+) -> Response {
+    match app_state
+        .api_key_service
+        .revoke(organization_id, api_key_id, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Request, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::{
+        auth_token_dto::AuthTokenDto, login_payload::LoginPayload,
+        refresh_token_payload::RefreshTokenPayload, role_update_payload::RoleUpdatePayload,
+        subscription_payload::SubscriptionPayload,
+    },
+    app_state::app_state::AppState,
+    auth::{
+        guard::{Authorized, ManageUsers},
+        user::User,
+    },
+    error::api_error::ApiError,
+};
+
+use super::get_error_response;
+
+// guards every route it is layered on, making the authenticated user available
+// to the handlers through the `Authorized` extractor. People authenticate with
+// "Bearer <jwt>", integrations with "ApiKey <key>".
+pub async fn auth_handler(
+    State(app_state): State<Arc<AppState>>,
+    mut req: Request,
+) -> Result<Request, Response> {
+    let maybe_auth_header = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|header| header.to_str().ok());
+
+    let auth_header = match maybe_auth_header {
+        Some(v) => v.to_string(),
+        None => {
+            return Err(get_error_response(Box::new(ApiError::Unauthorized(
+                String::from("Missing authentication header"),
+            ))))
+        }
+    };
+
+    let auth_result = match auth_header.strip_prefix("ApiKey ") {
+        Some(key) => app_state.api_key_service.authorize(key.to_string()).await,
+        None => app_state.auth_service.authorize(auth_header).await,
+    };
+
+    match auth_result {
+        Ok(user) => {
+            req.extensions_mut().insert(user);
+            Ok(req)
+        }
+        Err(err) => Err(get_error_response(err)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/login",
+    tag = "auth",
+    request_body = LoginPayload,
+    responses((status = 200, body = AuthTokenDto)),
+    security(()),
+)]
+pub async fn login_handler(
+    State(app_state): State<Arc<AppState>>,
+    Json(payload): Json<LoginPayload>,
+) -> Response {
+    match app_state.auth_service.login(payload).await {
+        Ok(token) => (StatusCode::OK, Json(token)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subscribe",
+    tag = "auth",
+    request_body = SubscriptionPayload,
+    responses((status = 201, body = User)),
+    security(()),
+)]
+pub async fn subscribe_handler(
+    State(app_state): State<Arc<AppState>>,
+    Json(payload): Json<SubscriptionPayload>,
+) -> Response {
+    match app_state.auth_service.subscribe(payload).await {
+        Ok(user) => (StatusCode::CREATED, Json(user)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/token/refresh",
+    tag = "auth",
+    request_body = RefreshTokenPayload,
+    responses((status = 200, body = AuthTokenDto)),
+    security(()),
+)]
+pub async fn token_refresh_handler(
+    State(app_state): State<Arc<AppState>>,
+    Json(payload): Json<RefreshTokenPayload>,
+) -> Response {
+    match app_state.auth_service.refresh(payload.refresh_token).await {
+        Ok(token) => (StatusCode::OK, Json(token)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/logout",
+    tag = "auth",
+    request_body = RefreshTokenPayload,
+    responses((status = 204, description = "The refresh token was revoked")),
+    security(()),
+)]
+pub async fn logout_handler(
+    State(app_state): State<Arc<AppState>>,
+    Json(payload): Json<RefreshTokenPayload>,
+) -> Response {
+    match app_state.auth_service.logout(payload.refresh_token).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/real-estate/users/{user_id}/sessions",
+    tag = "users",
+    params(("user_id" = String, Path)),
+    responses((status = 204, description = "Every refresh token of the user was revoked")),
+)]
+pub async fn user_sessions_revocation_handler(
+    State(app_state): State<Arc<AppState>>,
+    _: Authorized<ManageUsers>,
+    Path(user_id): Path<String>,
+) -> Response {
+    match app_state.auth_service.revoke_user_sessions(user_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/real-estate/users/{user_id}/role",
+    tag = "users",
+    params(("user_id" = String, Path)),
+    request_body = RoleUpdatePayload,
+    responses((status = 204, description = "The role was updated")),
+)]
+pub async fn user_role_update_handler(
+    State(app_state): State<Arc<AppState>>,
+    _: Authorized<ManageUsers>,
+    Path(user_id): Path<String>,
+    Json(payload): Json<RoleUpdatePayload>,
+) -> Response {
+    match app_state
+        .auth_service
+        .update_role(user_id, payload.role)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::{block_dto::BlockDto, block_summary::BlockSummary, lot_dto::LotDto},
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ManageSubdivisions, ViewSubdivisions},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/blocks",
+    tag = "blocks",
+    params(("subdivision_id" = String, Path)),
+    request_body = BlockDto,
+    responses((status = 201, description = "Name of the new block", body = String, content_type = "application/json")),
+)]
+pub async fn block_creation_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Json(payload): Json<BlockDto>,
+) -> Response {
+    match app_state
+        .block_service
+        .create(subdivision_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(name) => (StatusCode::CREATED, Json(name)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/blocks",
+    tag = "blocks",
+    params(("subdivision_id" = String, Path)),
+    responses((status = 200, body = Vec<BlockSummary>)),
+)]
+pub async fn block_listing_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(subdivision_id): Path<String>,
+) -> Response {
+    match app_state
+        .block_service
+        .get_summaries(subdivision_id, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(blocks) => Json(blocks).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/blocks/{block_name}/lots",
+    tag = "blocks",
+    params(("subdivision_id" = String, Path), ("block_name" = String, Path)),
+    responses((status = 200, body = Vec<LotDto>)),
+)]
+pub async fn block_lots_retrieval_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path((subdivision_id, block_name)): Path<(String, String)>,
+) -> Response {
+    match app_state
+        .block_service
+        .get_block_lots(subdivision_id, block_name, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(lots) => Json(lots).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::contract_template_dto::ContractTemplateDto,
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ManageContractTemplates},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/contract-template",
+    tag = "contracts",
+    params(("subdivision_id" = String, Path)),
+    responses((status = 200, body = ContractTemplateDto)),
+)]
+pub async fn contract_template_retrieval_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageContractTemplates>,
+    Path(subdivision_id): Path<String>,
+) -> Response {
+    match app_state
+        .contract_service
+        .get_template(subdivision_id, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(template) => Json(template).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/contract-template",
+    tag = "contracts",
+    params(("subdivision_id" = String, Path)),
+    request_body = ContractTemplateDto,
+    responses((status = 204, description = "The template was saved")),
+)]
+pub async fn contract_template_update_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageContractTemplates>,
+    Path(subdivision_id): Path<String>,
+    Json(payload): Json<ContractTemplateDto>,
+) -> Response {
+    match app_state
+        .contract_service
+        .save_template(subdivision_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
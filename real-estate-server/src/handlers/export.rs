@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    api_contracts::{export_params::ExportParams, job_dto::JobDto},
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ViewSubdivisions},
+    export::export_format::ExportFormat,
+    job::job::JobRequest,
+    openapi::BinaryFile,
+    organization::tenant_scope::TenantScope,
+};
+
+use super::{
+    get_error_response,
+    job::{enqueue_job, respond_async},
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/export",
+    tag = "exports",
+    params(("subdivision_id" = String, Path), ExportParams, ("Prefer" = Option<String>, Header, description = "respond-async to run it as a background job")),
+    responses((status = 200, description = "GeoJSON, KML, GPX or zipped shapefile, following the format", body = BinaryFile, content_type = "application/octet-stream"), (status = 202, description = "Queued as a background job", body = JobDto)),
+)]
+pub async fn subdivision_export_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Query(params): Query<ExportParams>,
+    headers: HeaderMap,
+) -> Response {
+    let format = params.format.unwrap_or(ExportFormat::GeoJson);
+    if respond_async(&headers) {
+        let request = JobRequest::SubdivisionExport {
+            subdivision_id,
+            format,
+        };
+        return enqueue_job(&app_state, &user, request, vec![]).await;
+    }
+
+    match app_state
+        .export_service
+        .export(subdivision_id, format, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(file) => (
+            [
+                (header::CONTENT_TYPE, file.content_type),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", file.file_name),
+                ),
+            ],
+            file.content,
+        )
+            .into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    app_state::app_state::AppState,
+    auth::{token::hash_bytes, user::User},
+    error::api_error::ApiError,
+    idempotency::{idempotency_record::IdempotencyRecord, service::IdempotentAttempt},
+};
+
+use super::get_error_response;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+const REPLAYED_HEADER: &str = "idempotent-replayed";
+const MAX_KEY_LENGTH: usize = 255;
+// the largest body any route accepts, the imports
+const MAX_BODY_SIZE: usize = 50 * 1024 * 1024;
+
+// lets clients retry a POST safely: the first request sent with an
+// `Idempotency-Key` header runs, later ones with the same key and payload get
+// its response back instead of running again. Layered inside the auth layer so
+// keys are kept per user or API key.
+pub async fn idempotency_handler(
+    State(app_state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if req.method() != Method::POST {
+        return next.run(req).await;
+    }
+
+    let key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => match value.to_str() {
+            Ok(key) if !key.trim().is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+            _ => {
+                return get_error_response(Box::new(ApiError::BadRequest(format!(
+                    "The Idempotency-Key header must be a text of 1 to {} characters",
+                    MAX_KEY_LENGTH
+                ))))
+            }
+        },
+        None => return next.run(req).await,
+    };
+
+    let owner_id = match req.extensions().get::<User>() {
+        Some(user) => user.principal_id(),
+        None => return next.run(req).await,
+    };
+
+    let (parts, body) = req.into_parts();
+    let body = match to_bytes(body, MAX_BODY_SIZE).await {
+        Ok(body) => body,
+        Err(_) => {
+            return get_error_response(Box::new(ApiError::PayloadTooLarge(String::from(
+                "The request body is too large",
+            ))))
+        }
+    };
+
+    let mut fingerprint = format!("{} {}\n", parts.method, parts.uri).into_bytes();
+    fingerprint.extend_from_slice(&body);
+
+    let service = &app_state.idempotency_service;
+    match service
+        .begin(owner_id.clone(), key.clone(), hash_bytes(&fingerprint))
+        .await
+    {
+        Ok(IdempotentAttempt::Proceed) => {}
+        Ok(IdempotentAttempt::Replay(record)) => return replay(record),
+        Ok(IdempotentAttempt::InProgress) => {
+            return get_error_response(Box::new(ApiError::Conflict(String::from(
+                "A request with this Idempotency-Key is still being processed",
+            ))))
+        }
+        Ok(IdempotentAttempt::Mismatch) => {
+            return get_error_response(Box::new(ApiError::validation(
+                "Idempotency-Key",
+                String::from("This Idempotency-Key was already used with a different request"),
+            )))
+        }
+        Err(err) => return get_error_response(err),
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    // server errors are worth retrying for real, and responses marked no-store
+    // carry secrets, like new API keys, that must not be kept around
+    let no_store = response
+        .headers()
+        .get(header::CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("no-store"));
+    if response.status().is_server_error() || no_store {
+        if let Err(err) = service.release(owner_id, key).await {
+            eprintln!("failed to release idempotency key: {}", err.message());
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(_) => {
+            let _ = service.release(owner_id, key).await;
+            return get_error_response(Box::new(ApiError::Internal(String::from(
+                "Failed to read the response",
+            ))));
+        }
+    };
+
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    if let Err(err) = service
+        .complete(
+            owner_id,
+            key,
+            parts.status.as_u16(),
+            content_type,
+            body.to_vec(),
+        )
+        .await
+    {
+        eprintln!("failed to store idempotent response: {}", err.message());
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn replay(record: IdempotencyRecord) -> Response {
+    let status_code = record
+        .status_code
+        .and_then(|code| StatusCode::from_u16(code as u16).ok())
+        .unwrap_or_default();
+    let mut response = (status_code, record.body.unwrap_or_default()).into_response();
+
+    let headers = response.headers_mut();
+    headers.remove(header::CONTENT_TYPE);
+    if let Some(value) = record
+        .content_type
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        headers.insert(header::CONTENT_TYPE, value);
+    }
+    headers.insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+
+    response
+}
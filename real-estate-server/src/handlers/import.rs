@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::{
+        dxf_import_params::DxfImportParams, geojson_import_payload::GeoJsonImportPayload,
+        import_report::ImportReport, job_dto::JobDto,
+        shapefile_import_params::ShapefileImportParams,
+        spreadsheet_import_params::SpreadsheetImportParams,
+    },
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ManageSubdivisions},
+    job::job::JobRequest,
+    openapi::BinaryFile,
+    organization::tenant_scope::TenantScope,
+};
+
+use super::{
+    get_error_response,
+    job::{enqueue_job, respond_async},
+};
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/import/geojson",
+    tag = "imports",
+    params(("Prefer" = Option<String>, Header, description = "respond-async to run it as a background job")),
+    request_body = GeoJsonImportPayload,
+    responses((status = 201, body = ImportReport), (status = 422, description = "Nothing was saved, the report tells why", body = ImportReport), (status = 202, description = "Queued as a background job", body = JobDto)),
+)]
+pub async fn geojson_import_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    headers: HeaderMap,
+    Json(payload): Json<GeoJsonImportPayload>,
+) -> Response {
+    if respond_async(&headers) {
+        let request = JobRequest::GeojsonImport { payload };
+        return enqueue_job(&app_state, &user, request, vec![]).await;
+    }
+
+    match app_state
+        .import_service
+        .import_geojson(payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(report) if report.created => (StatusCode::CREATED, Json(report)).into_response(),
+        Ok(report) => (StatusCode::UNPROCESSABLE_ENTITY, Json(report)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+// the body is the DXF file itself
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lots/import/dxf",
+    tag = "imports",
+    params(("subdivision_id" = String, Path), DxfImportParams, ("Prefer" = Option<String>, Header, description = "respond-async to run it as a background job")),
+    request_body(content = String, description = "The DXF drawing", content_type = "application/dxf"),
+    responses((status = 201, body = ImportReport), (status = 422, description = "Nothing was saved, the report tells why", body = ImportReport), (status = 202, description = "Queued as a background job", body = JobDto)),
+)]
+pub async fn dxf_import_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Query(params): Query<DxfImportParams>,
+    headers: HeaderMap,
+    content: String,
+) -> Response {
+    if respond_async(&headers) {
+        let request = JobRequest::DxfImport {
+            subdivision_id,
+            params,
+        };
+        return enqueue_job(&app_state, &user, request, content.into_bytes()).await;
+    }
+
+    match app_state
+        .import_service
+        .import_dxf(
+            subdivision_id,
+            params,
+            content,
+            &TenantScope::for_user(&user),
+        )
+        .await
+    {
+        Ok(report) if report.created => (StatusCode::CREATED, Json(report)).into_response(),
+        Ok(report) => (StatusCode::UNPROCESSABLE_ENTITY, Json(report)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+// the body is the zipped shapefile bundle
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/import/shapefile",
+    tag = "imports",
+    params(ShapefileImportParams, ("Prefer" = Option<String>, Header, description = "respond-async to run it as a background job")),
+    request_body(content = BinaryFile, description = "Zip archive with the .shp, .shx and .dbf files", content_type = "application/zip"),
+    responses((status = 201, body = ImportReport), (status = 422, description = "Nothing was saved, the report tells why", body = ImportReport), (status = 202, description = "Queued as a background job", body = JobDto)),
+)]
+pub async fn shapefile_import_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Query(params): Query<ShapefileImportParams>,
+    headers: HeaderMap,
+    content: Bytes,
+) -> Response {
+    if respond_async(&headers) {
+        let request = JobRequest::ShapefileImport { params };
+        return enqueue_job(&app_state, &user, request, content.to_vec()).await;
+    }
+
+    match app_state
+        .import_service
+        .import_shapefile(params, &content, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(report) if report.created => (StatusCode::CREATED, Json(report)).into_response(),
+        Ok(report) => (StatusCode::UNPROCESSABLE_ENTITY, Json(report)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+// the body is the CSV or XLSX file itself
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lots/import",
+    tag = "imports",
+    params(("subdivision_id" = String, Path), SpreadsheetImportParams, ("Prefer" = Option<String>, Header, description = "respond-async to run it as a background job")),
+    request_body(description = "CSV or XLSX file, one lot per row", content((BinaryFile = "text/csv"), (BinaryFile = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"))),
+    responses((status = 201, body = ImportReport), (status = 422, description = "Nothing was saved, the report tells why", body = ImportReport), (status = 202, description = "Queued as a background job", body = JobDto)),
+)]
+pub async fn spreadsheet_import_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Query(params): Query<SpreadsheetImportParams>,
+    headers: HeaderMap,
+    content: Bytes,
+) -> Response {
+    if respond_async(&headers) {
+        let request = JobRequest::SpreadsheetImport {
+            subdivision_id,
+            params,
+        };
+        return enqueue_job(&app_state, &user, request, content.to_vec()).await;
+    }
+
+    match app_state
+        .import_service
+        .import_spreadsheet(
+            subdivision_id,
+            params,
+            &content,
+            &TenantScope::for_user(&user),
+        )
+        .await
+    {
+        Ok(report) if report.created => (StatusCode::CREATED, Json(report)).into_response(),
+        Ok(report) => (StatusCode::UNPROCESSABLE_ENTITY, Json(report)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
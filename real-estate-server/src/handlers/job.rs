@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::job_dto::JobDto,
+    app_state::app_state::AppState,
+    auth::{
+        guard::{Authorized, ViewSubdivisions},
+        user::User,
+    },
+    job::job::JobRequest,
+    openapi::BinaryFile,
+};
+
+use super::get_error_response;
+
+// clients ask for a job instead of waiting on the response with
+// `Prefer: respond-async` (RFC 7240), getting 202 and the job to poll
+pub fn respond_async(headers: &HeaderMap) -> bool {
+    headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|preference| preference.trim().eq_ignore_ascii_case("respond-async"))
+}
+
+pub async fn enqueue_job(
+    app_state: &AppState,
+    user: &User,
+    request: JobRequest,
+    input: Vec<u8>,
+) -> Response {
+    match app_state.job_service.enqueue(request, input, user).await {
+        Ok(job) => (
+            StatusCode::ACCEPTED,
+            [(
+                header::LOCATION,
+                format!("/api/real-estate/jobs/{}", job.id),
+            )],
+            Json(job),
+        )
+            .into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/jobs/{job_id}",
+    tag = "jobs",
+    params(("job_id" = String, Path)),
+    responses((status = 200, body = JobDto)),
+)]
+pub async fn job_status_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(job_id): Path<String>,
+) -> Response {
+    match app_state.job_service.get(job_id, &user).await {
+        Ok(job) => Json(job).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/jobs/{job_id}/result",
+    tag = "jobs",
+    params(("job_id" = String, Path)),
+    responses((status = 200, description = "The exported file or the import report", body = BinaryFile, content_type = "application/octet-stream")),
+)]
+pub async fn job_result_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(job_id): Path<String>,
+) -> Response {
+    match app_state.job_service.get_result(job_id, &user).await {
+        Ok(file) => (
+            [
+                (header::CONTENT_TYPE, file.content_type),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", file.file_name),
+                ),
+            ],
+            file.content,
+        )
+            .into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/jobs/{job_id}/cancel",
+    tag = "jobs",
+    params(("job_id" = String, Path)),
+    responses((status = 200, body = JobDto)),
+)]
+pub async fn job_cancellation_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(job_id): Path<String>,
+) -> Response {
+    match app_state.job_service.cancel(job_id, &user).await {
+        Ok(job) => Json(job).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
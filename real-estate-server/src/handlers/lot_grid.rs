@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::{lot_dto::LotDto, lot_grid_payload::LotGridPayload},
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ManageSubdivisions},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lot-grid/preview",
+    tag = "lots",
+    params(("subdivision_id" = String, Path)),
+    request_body = LotGridPayload,
+    responses((status = 200, description = "The lots that would be created, nothing is saved", body = Vec<LotDto>)),
+)]
+pub async fn lot_grid_preview_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Json(payload): Json<LotGridPayload>,
+) -> Response {
+    match app_state
+        .lot_grid_service
+        .preview(subdivision_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(lots) => Json(lots).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lot-grid",
+    tag = "lots",
+    params(("subdivision_id" = String, Path)),
+    request_body = LotGridPayload,
+    responses((status = 201, body = Vec<LotDto>)),
+)]
+pub async fn lot_grid_creation_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Json(payload): Json<LotGridPayload>,
+) -> Response {
+    match app_state
+        .lot_grid_service
+        .commit(subdivision_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(lots) => (StatusCode::CREATED, Json(lots)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
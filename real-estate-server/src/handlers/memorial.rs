@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    api_contracts::memorial_params::MemorialParams,
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ViewSubdivisions},
+    memorial::memorial_format::MemorialFormat,
+    openapi::BinaryFile,
+    organization::tenant_scope::TenantScope,
+    pdf::text_layout::render_text_document,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lots/{lot_name}/memorial",
+    tag = "lots",
+    params(("subdivision_id" = String, Path), ("lot_name" = String, Path), MemorialParams),
+    responses((status = 200, description = "Plain text or PDF, following the format", content((String = "text/plain"), (BinaryFile = "application/pdf")))),
+)]
+pub async fn lot_memorial_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path((subdivision_id, lot_name)): Path<(String, String)>,
+    Query(params): Query<MemorialParams>,
+) -> Response {
+    let memorial = match app_state
+        .memorial_service
+        .generate(
+            subdivision_id,
+            lot_name.clone(),
+            &TenantScope::for_user(&user),
+        )
+        .await
+    {
+        Ok(memorial) => memorial,
+        Err(err) => return get_error_response(err),
+    };
+
+    match params.format.unwrap_or(MemorialFormat::Text) {
+        MemorialFormat::Text => (
+            [(
+                header::CONTENT_TYPE,
+                String::from("text/plain; charset=utf-8"),
+            )],
+            memorial.to_text(),
+        )
+            .into_response(),
+        MemorialFormat::Pdf => (
+            [
+                (header::CONTENT_TYPE, String::from("application/pdf")),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"memorial-{}.pdf\"", lot_name),
+                ),
+            ],
+            render_text_document(&memorial.title(), &memorial.body()),
+        )
+            .into_response(),
+    }
+}
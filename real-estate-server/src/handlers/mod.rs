@@ -1 +1,128 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{api_contracts::problem_details::ProblemDetails, error::app_error::DynAppError};
+
+pub mod analytics;
+pub mod api_key;
+pub mod auth;
+pub mod block;
+pub mod contract;
+pub mod export;
+pub mod idempotency;
+pub mod import;
+pub mod job;
+pub mod lot_grid;
+pub mod memorial;
+pub mod organization;
+pub mod plat;
+pub mod sale;
+pub mod sales_table;
+pub mod site_feature;
 pub mod subdivision;
+pub mod tile;
+
+pub fn get_error_response(error: DynAppError) -> Response {
+    let status_code = StatusCode::from_u16(error.status_code() as u16)
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let title = status_code.canonical_reason().unwrap_or_default().to_string();
+
+    // what went wrong inside the server, like a database message, stays in the
+    // logs
+    let detail = if status_code.is_server_error() {
+        eprintln!("{} {}: {}", status_code.as_u16(), error.code(), error.message());
+        title.clone()
+    } else {
+        error.message()
+    };
+
+    let problem = ProblemDetails {
+        problem_type: String::from("about:blank"),
+        title,
+        status: status_code.as_u16(),
+        detail,
+        code: error.code(),
+        errors: error.field_errors(),
+    };
+
+    (
+        status_code,
+        [(header::CONTENT_TYPE, "application/problem+json")],
+        Json(problem),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::error::api_error::{ApiError, FieldError};
+
+    async fn problem_of(error: ApiError) -> (StatusCode, String, Value) {
+        let response = get_error_response(Box::new(error));
+        let status = response.status();
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, content_type, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn validation_problems_list_the_field_errors() {
+        let error = ApiError::Validation {
+            message: String::from("Invalid lot polygon: too few vertices"),
+            errors: vec![
+                FieldError::new("area", String::from("too few vertices")),
+                FieldError::new("name", String::from("The lot has no name")),
+            ],
+        };
+
+        let (status, content_type, body) = problem_of(error).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(content_type, "application/problem+json");
+        assert_eq!(
+            body,
+            json!({
+                "type": "about:blank",
+                "title": "Unprocessable Entity",
+                "status": 422,
+                "detail": "Invalid lot polygon: too few vertices",
+                "code": "validation_failed",
+                "errors": [
+                    {"field": "area", "message": "too few vertices"},
+                    {"field": "name", "message": "The lot has no name"}
+                ]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn problems_without_field_errors_leave_them_out() {
+        let (status, _, body) =
+            problem_of(ApiError::NotFound(String::from("Sale abc not found"))).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["detail"], "Sale abc not found");
+        assert_eq!(body["code"], "not_found");
+        assert!(body.get("errors").is_none());
+    }
+
+    #[tokio::test]
+    async fn server_errors_only_show_their_title() {
+        let (status, _, body) =
+            problem_of(ApiError::Unavailable(String::from("connection refused"))).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["detail"], "Service Unavailable");
+        assert_eq!(body["code"], "service_unavailable");
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::{
+        organization_dto::OrganizationDto,
+        organization_member_payload::OrganizationMemberPayload,
+    },
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ManageOrganizations, ViewSubdivisions},
+    organization::{organization::Organization, tenant_scope::TenantScope},
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/organizations",
+    tag = "organizations",
+    request_body = OrganizationDto,
+    responses((status = 201, description = "Id of the new organization", body = String, content_type = "application/json")),
+)]
+pub async fn organization_creation_handler(
+    State(app_state): State<Arc<AppState>>,
+    _: Authorized<ManageOrganizations>,
+    Json(payload): Json<OrganizationDto>,
+) -> Response {
+    match app_state.organization_service.create(payload).await {
+        Ok(id) => (StatusCode::CREATED, Json(id)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/organizations",
+    tag = "organizations",
+    responses((status = 200, body = Vec<Organization>)),
+)]
+pub async fn organization_listing_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+) -> Response {
+    match app_state
+        .organization_service
+        .get_all(&TenantScope::for_user(&user))
+        .await
+    {
+        Ok(organizations) => Json(organizations).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/organizations/{organization_id}/members",
+    tag = "organizations",
+    params(("organization_id" = String, Path)),
+    request_body = OrganizationMemberPayload,
+    responses((status = 204, description = "The user joined the organization")),
+)]
+pub async fn organization_member_addition_handler(
+    State(app_state): State<Arc<AppState>>,
+    _: Authorized<ManageOrganizations>,
+    Path(organization_id): Path<String>,
+    Json(payload): Json<OrganizationMemberPayload>,
+) -> Response {
+    match app_state
+        .organization_service
+        .add_member(organization_id, payload.user_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/real-estate/organizations/{organization_id}/members/{user_id}",
+    tag = "organizations",
+    params(("organization_id" = String, Path), ("user_id" = String, Path)),
+    responses((status = 204, description = "The user left the organization")),
+)]
+pub async fn organization_member_removal_handler(
+    State(app_state): State<Arc<AppState>>,
+    _: Authorized<ManageOrganizations>,
+    Path((organization_id, user_id)): Path<(String, String)>,
+) -> Response {
+    match app_state
+        .organization_service
+        .remove_member(organization_id, user_id)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
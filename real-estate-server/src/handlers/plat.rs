@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    api_contracts::plat_params::PlatParams,
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ViewSubdivisions},
+    openapi::BinaryFile,
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/plat.svg",
+    tag = "maps",
+    params(("subdivision_id" = String, Path), PlatParams),
+    responses((status = 200, body = String, content_type = "image/svg+xml")),
+)]
+pub async fn plat_svg_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Query(params): Query<PlatParams>,
+) -> Response {
+    match app_state
+        .plat_service
+        .render_svg(
+            subdivision_id,
+            params.selected_lot,
+            params.width,
+            &TenantScope::for_user(&user),
+        )
+        .await
+    {
+        Ok(svg) => ([(header::CONTENT_TYPE, "image/svg+xml")], svg).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/plat.png",
+    tag = "maps",
+    params(("subdivision_id" = String, Path), PlatParams),
+    responses((status = 200, body = BinaryFile, content_type = "image/png")),
+)]
+pub async fn plat_png_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Query(params): Query<PlatParams>,
+) -> Response {
+    match app_state
+        .plat_service
+        .render_png(
+            subdivision_id,
+            params.selected_lot,
+            params.width,
+            &TenantScope::for_user(&user),
+        )
+        .await
+    {
+        Ok(png) => ([(header::CONTENT_TYPE, "image/png")], png).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::sale_dto::SaleDto,
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, RecordSales, ViewContracts},
+    openapi::BinaryFile,
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/sales",
+    tag = "sales",
+    request_body = SaleDto,
+    responses((status = 201, description = "Id of the new sale", body = String, content_type = "application/json")),
+)]
+pub async fn sale_creation_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<RecordSales>,
+    Json(payload): Json<SaleDto>,
+) -> Response {
+    match app_state
+        .sale_service
+        .create(payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(id) => (StatusCode::CREATED, Json(id)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/sales/{sale_id}/contract",
+    tag = "sales",
+    params(("sale_id" = String, Path)),
+    responses((status = 200, body = BinaryFile, content_type = "application/pdf")),
+)]
+pub async fn sale_contract_retrieval_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewContracts>,
+    Path(sale_id): Path<String>,
+) -> Response {
+    match app_state
+        .sale_service
+        .get_contract(sale_id.clone(), &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(contract) => (
+            [
+                (header::CONTENT_TYPE, String::from("application/pdf")),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"contract-{}.pdf\"", sale_id),
+                ),
+            ],
+            contract,
+        )
+            .into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    api_contracts::{job_dto::JobDto, sales_table_params::SalesTableParams},
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ViewSubdivisions},
+    job::job::JobRequest,
+    openapi::BinaryFile,
+    organization::tenant_scope::TenantScope,
+    sales_table::sales_table::file_name,
+};
+
+use super::{
+    get_error_response,
+    job::{enqueue_job, respond_async},
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/sales-table",
+    tag = "exports",
+    params(("subdivision_id" = String, Path), SalesTableParams, ("Prefer" = Option<String>, Header, description = "respond-async to run it as a background job")),
+    responses((status = 200, body = BinaryFile, content_type = "application/pdf"), (status = 202, description = "Queued as a background job", body = JobDto)),
+)]
+pub async fn sales_table_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Query(params): Query<SalesTableParams>,
+    headers: HeaderMap,
+) -> Response {
+    let available_only = params.available_only.unwrap_or(false);
+    if respond_async(&headers) {
+        let request = JobRequest::SalesTable {
+            subdivision_id,
+            available_only,
+        };
+        return enqueue_job(&app_state, &user, request, vec![]).await;
+    }
+
+    match app_state
+        .sales_table_service
+        .generate(
+            subdivision_id.clone(),
+            available_only,
+            &TenantScope::for_user(&user),
+        )
+        .await
+    {
+        Ok(sales_table) => (
+            [
+                (header::CONTENT_TYPE, String::from("application/pdf")),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", file_name(&subdivision_id)),
+                ),
+            ],
+            sales_table.to_pdf(),
+        )
+            .into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::{
+    api_contracts::{land_use_breakdown::LandUseBreakdown, site_feature_dto::SiteFeatureDto},
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ManageSubdivisions, ViewSubdivisions},
+    organization::tenant_scope::TenantScope,
+};
+
+use super::get_error_response;
+
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/features",
+    tag = "site-features",
+    params(("subdivision_id" = String, Path)),
+    request_body = SiteFeatureDto,
+    responses((status = 201, description = "Id of the new feature", body = String, content_type = "application/json")),
+)]
+pub async fn site_feature_creation_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Path(subdivision_id): Path<String>,
+    Json(payload): Json<SiteFeatureDto>,
+) -> Response {
+    match app_state
+        .site_feature_service
+        .create(subdivision_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(id) => (StatusCode::CREATED, Json(id)).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/features",
+    tag = "site-features",
+    params(("subdivision_id" = String, Path)),
+    responses((status = 200, body = Vec<SiteFeatureDto>)),
+)]
+pub async fn site_features_retrieval_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(subdivision_id): Path<String>,
+) -> Response {
+    match app_state
+        .site_feature_service
+        .get_features(subdivision_id, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(features) => Json(features).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/features/{feature_id}",
+    tag = "site-features",
+    params(("subdivision_id" = String, Path), ("feature_id" = String, Path)),
+    responses((status = 204, description = "The feature was removed")),
+)]
+pub async fn site_feature_removal_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
+    Path((subdivision_id, feature_id)): Path<(String, String)>,
+) -> Response {
+    match app_state
+        .site_feature_service
+        .delete(subdivision_id, feature_id, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/land-use",
+    tag = "site-features",
+    params(("subdivision_id" = String, Path)),
+    responses((status = 200, body = LandUseBreakdown)),
+)]
+pub async fn land_use_breakdown_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path(subdivision_id): Path<String>,
+) -> Response {
+    match app_state
+        .site_feature_service
+        .get_breakdown(subdivision_id, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(breakdown) => Json(breakdown).into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
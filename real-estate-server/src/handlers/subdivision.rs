@@ -1,70 +1,116 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Path, Query, Request, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::{IntoResponse, Response},
     Json,
 };
-use axum_macros::debug_handler;
 
 use crate::{
     api_contracts::{
-        lot_dto::LotDto, search_subdivision_params::SearchSubdivisionParams,
-        subdivision_dto::SubdivisionDto,
+        crs_params::CrsParams, lot_batch_report::LotBatchReport, lot_dto::LotDto,
+        search_subdivision_params::SearchSubdivisionParams, subdivision_dto::SubdivisionDto,
+        subdivision_preview::SubdivisionPreview,
     },
     app_state::app_state::AppState,
-    error::{
-        app_error::{AppError, DynAppError},
-        auth::{AuthError, AuthErrorStatusCode},
-        default::DefaultAppError,
-    },
-    subdivision::{self, lot::Lot, subdivision::Subdivision},
+    auth::guard::{Authorized, ManageSubdivisions, ViewSubdivisions},
+    error::api_error::ApiError,
+    organization::tenant_scope::TenantScope,
+    geometry::crs::Crs,
+    subdivision::service::resolve_crs,
 };
 
+use super::get_error_response;
+
 // #[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions",
+    tag = "subdivisions",
+    request_body = SubdivisionDto,
+    responses((status = 200, description = "Id of the new subdivision", body = String, content_type = "application/json")),
+)]
 pub async fn subdivision_creation_handler(
     State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
     Json(payload): Json<SubdivisionDto>,
 ) -> Response {
-    match app_state.subdivision_service.create(payload).await {
+    match app_state
+        .subdivision_service
+        .create(payload, &TenantScope::for_user(&user))
+        .await
+    {
         Ok(id) => Json(id).into_response(),
         Err(err) => get_error_response(err),
     }
 }
 
 // #[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lots",
+    tag = "lots",
+    params(("subdivision_id" = String, Path)),
+    request_body = LotDto,
+    responses((status = 200, description = "Id of the new lot", body = String, content_type = "application/json")),
+)]
 pub async fn lot_creation_handler(
     State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
     Path(subdivision_id): Path<String>,
     Json(payload): Json<LotDto>,
 ) -> Response {
-    match app_state.subdivision_service.create_lot(payload).await {
+    match app_state
+        .subdivision_service
+        .create_lot(subdivision_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
         Ok(id) => Json(id).into_response(),
         Err(err) => get_error_response(err),
     }
 }
 
 // #[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lots/batch-creation",
+    tag = "lots",
+    params(("subdivision_id" = String, Path)),
+    request_body = Vec<LotDto>,
+    responses((status = 200, body = LotBatchReport)),
+)]
 pub async fn lots_creation_handler(
     State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ManageSubdivisions>,
     Path(subdivision_id): Path<String>,
     Json(payload): Json<Box<[LotDto]>>,
 ) -> Response {
-    match app_state.subdivision_service.create_lots(payload).await {
-        Ok(lots) => Json(
-            lots.into_iter()
-                .map(|elem: Lot| -> String { format!("{}-{}", elem.name, elem.subdivision_id) })
-                .collect::<Vec<String>>(),
-        )
-        .into_response(),
+    match app_state
+        .subdivision_service
+        .create_lots_batch(subdivision_id, payload, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(report) => Json(report).into_response(),
         Err(err) => get_error_response(err),
     }
 }
 
 // #[debug_handler]
-pub async fn subdivision_listing_handler(State(app_state): State<Arc<AppState>>) -> Response {
-    match app_state.subdivision_service.get_all().await {
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions",
+    tag = "subdivisions",
+    responses((status = 200, body = Vec<SubdivisionPreview>)),
+)]
+pub async fn subdivision_listing_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+) -> Response {
+    match app_state
+        .subdivision_service
+        .get_all(&TenantScope::for_user(&user))
+        .await
+    {
         Ok(subdivisions) => {
             Json(subdivisions).into_response()
         }
@@ -72,153 +118,125 @@ pub async fn subdivision_listing_handler(State(app_state): State<Arc<AppState>>)
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/search",
+    tag = "subdivisions",
+    params(SearchSubdivisionParams),
+    responses((status = 200, body = Vec<SubdivisionDto>)),
+)]
 pub async fn subdivision_searching_handler(
     State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
     Query(params): Query<SearchSubdivisionParams>,
 ) -> Response {
+    let scope = TenantScope::for_user(&user);
+    let crs = match resolve_crs(params.crs.clone()) {
+        Ok(crs) => crs,
+        Err(err) => return get_error_response(err),
+    };
+
     match params.name.clone() {
         Some(name) => {
-            let maybe_subdivisions = app_state.subdivision_service.search_by_name(name).await;
+            let maybe_subdivisions = app_state
+                .subdivision_service
+                .search_by_name(name, &scope)
+                .await;
 
             match maybe_subdivisions {
                 Ok(subdivisions) => {
-                    return Json(subdivisions).into_response();
+                    Json(subdivisions_in_crs(subdivisions, &crs)).into_response()
                 }
-                Err(err) => return get_error_response(err),
-            };
+                Err(err) => get_error_response(err),
+            }
         }
         None => match params.lat {
             None => {
-                return get_error_response(Box::new(DefaultAppError {
-                    message: Some(String::from(
-                        "Invalid searching params. Missing name and geolocation data",
-                    )),
-                    status_code: 500,
-                }))
+                get_error_response(Box::new(ApiError::BadRequest(String::from(
+                    "Invalid searching params. Missing name and geolocation data",
+                ))))
             }
             Some(lat) => match params.long {
                 Some(long) => {
                     let coords = (lat, long);
                     let maybe_subdivisions = app_state
                         .subdivision_service
-                        .search_by_location(coords)
+                        .search_by_location(coords, &scope)
                         .await;
 
                     match maybe_subdivisions {
                         Ok(subdivisions) => {
-                            return Json(subdivisions).into_response();
+                            Json(subdivisions_in_crs(subdivisions, &crs)).into_response()
                         }
-                        Err(err) => return get_error_response(err),
+                        Err(err) => get_error_response(err),
                     }
                 }
                 None => {
-                    return get_error_response(Box::new(DefaultAppError {
-                        message: Some(String::from("Invalid searching params. Missing long value")),
-                        status_code: 500,
-                    }))
+                    get_error_response(Box::new(ApiError::BadRequest(String::from(
+                        "Invalid searching params. Missing long value",
+                    ))))
                 }
             },
         },
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/subdivisions/{subdivision_id}/lots",
+    tag = "lots",
+    params(("subdivision_id" = String, Path), CrsParams),
+    responses((status = 200, body = Vec<LotDto>)),
+)]
 pub async fn subdivision_lots_retrieval_handler(
     State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
     Path(subdivision_id): Path<String>,
+    Query(params): Query<CrsParams>,
 ) -> Response {
-    match app_state.subdivision_service.get_subdivision_lots(subdivision_id).await {
+    let crs = match resolve_crs(params.crs) {
+        Ok(crs) => crs,
+        Err(err) => return get_error_response(err),
+    };
+
+    match app_state
+        .subdivision_service
+        .get_subdivision_lots(subdivision_id, &TenantScope::for_user(&user))
+        .await
+    {
         Ok(lots) => {
-            Json(lots).into_response()
+            Json(lots_in_crs(lots, &crs)).into_response()
         }
         Err(err) => get_error_response(err),
     }
 }
 
-// pub async fn auth_handler(State(app_state): State<Arc<AppState>>, mut req: Request) -> Request {
-//     if req.uri().path().contains("/api/park-here/login")
-//         || req.uri().path().contains("/api/park-here/subscribe")
-//     {
-//         return req;
-//     }
-
-//     let maybe_auth_header = req
-//         .headers()
-//         .get(header::AUTHORIZATION)
-//         .and_then(|header| header.to_str().ok());
-
-//     let auth_header = match maybe_auth_header {
-//         Some(v) => v,
-//         None => {
-//             req.extensions_mut().insert(Err::<User, AuthError>(AuthError {
-//                 message: Some(String::from("Missing authentication header")),
-//                 status_code: AuthErrorStatusCode::UNAUTHORIZED
-//             }));
-//             return req
-//         }
-//     };
-
-//     match app_state
-//         .auth_service
-//         .authorize(auth_header.to_string())
-//         .await
-//     {
-//         Ok(user) => {
-//             req.extensions_mut().insert(Ok::<User, AuthError>(user));
-//             req
-//         }
-//         Err(err) => {
-//             req.extensions_mut().insert(Err::<User, AuthError>(AuthError {
-//                 message: Some(String::from("Invalid authentication token")),
-//                 status_code: AuthErrorStatusCode::UNAUTHORIZED
-//             }));
-//             req
-//         },
-//     }
-// }
-
-// pub async fn login_handler(
-//     State(app_state): State<Arc<AppState>>,
-//     Json(payload): Json<LoginPayload>,
-// ) -> Response {
-//     // let login_result = app_state.auth_service.login(payload).await;
-
-//     // match login_result {
-//     //     Ok(jwt) => (StatusCode::OK,  Json(jwt)).into_response(),
-//     //     Err(err) =>  get_error_response(err)
-//     // }
-// }
-
-// pub async fn subscribe_handler(
-//     State(app_state): State<Arc<AppState>>,
-//     Json(payload): Json<SubscriptionPayload>,
-// ) -> Response {
-//     // let sub_result = app_state.auth_service.subscribe(payload).await;
-
-//     // match sub_result {
-//     //     Ok(user) => (StatusCode::CREATED, Json(user)).into_response(),
-//     //     Err(err) => get_error_response(err),
-//     // }
-// }
-
-fn get_error_response(error: DynAppError) -> Response {
-    print!("{}", error.status_code());
-    match StatusCode::from_u16(error.status_code() as u16) {
-        Ok(status_code) => (status_code, Json(error.in_short())).into_response(),
-        Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, Json(error.in_short())).into_response(),
+// areas are stored in WGS84 and only converted when another CRS is requested
+fn subdivisions_in_crs(subdivisions: Vec<SubdivisionDto>, crs: &Crs) -> Vec<SubdivisionDto> {
+    if *crs == Crs::Wgs84 {
+        return subdivisions;
     }
+
+    subdivisions
+        .into_iter()
+        .map(|mut subdivision| {
+            subdivision.area = Box::new(crs.area_from_wgs84(&subdivision.area));
+            subdivision.crs = Some(crs.code());
+            subdivision
+        })
+        .collect()
 }
 
-// fn get_auth_extension(mut req: Request) -> Result<User, AuthError> {
-//     match req.extensions_mut().get::<User>() {
-//         Some(user) => Ok(user.clone()),
-//         None => {
-//             match req.extensions_mut().get::<AuthError>() {
-//                 Some(err) => Err(err.clone()),
-//                 None => Err(AuthError {
-//                     message: Some(String::from("Authentication error")),
-//                     status_code: AuthErrorStatusCode::UNAUTHORIZED
-//                 })
-//             }
-//         }
-//     }
-// }
+fn lots_in_crs(lots: Vec<LotDto>, crs: &Crs) -> Vec<LotDto> {
+    if *crs == Crs::Wgs84 {
+        return lots;
+    }
+
+    lots.into_iter()
+        .map(|mut lot| {
+            lot.area = Box::new(crs.area_from_wgs84(&lot.area));
+            lot.crs = Some(crs.code());
+            lot
+        })
+        .collect()
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::{
+    app_state::app_state::AppState,
+    auth::guard::{Authorized, ViewSubdivisions},
+    error::api_error::ApiError,
+    openapi::BinaryFile,
+    organization::tenant_scope::TenantScope,
+    tile::tile::TileCoord,
+};
+
+use super::get_error_response;
+
+// the last segment comes as "{y}.mvt", the router can't split it
+#[utoipa::path(
+    get,
+    path = "/api/real-estate/tiles/{z}/{x}/{tile}",
+    tag = "maps",
+    params(("z" = u8, Path), ("x" = u32, Path), ("tile" = String, Path, description = "The tile row followed by .mvt, such as 1234.mvt")),
+    responses((status = 200, body = BinaryFile, content_type = "application/vnd.mapbox-vector-tile")),
+)]
+pub async fn tile_handler(
+    State(app_state): State<Arc<AppState>>,
+    Authorized(user, _): Authorized<ViewSubdivisions>,
+    Path((z, x, tile)): Path<(u8, u32, String)>,
+) -> Response {
+    let coord = match tile
+        .strip_suffix(".mvt")
+        .and_then(|y| y.parse::<u32>().ok())
+        .and_then(|y| TileCoord::new(z, x, y))
+    {
+        Some(coord) => coord,
+        None => {
+            return get_error_response(Box::new(ApiError::BadRequest(format!(
+                "Invalid tile {}/{}/{}",
+                z, x, tile
+            ))))
+        }
+    };
+
+    match app_state
+        .tile_service
+        .get_tile(coord, &TenantScope::for_user(&user))
+        .await
+    {
+        Ok(content) => (
+            [(header::CONTENT_TYPE, "application/vnd.mapbox-vector-tile")],
+            content,
+        )
+            .into_response(),
+        Err(err) => get_error_response(err),
+    }
+}
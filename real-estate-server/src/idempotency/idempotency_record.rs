@@ -0,0 +1,14 @@
+use chrono::NaiveDateTime;
+
+// the response given to the first request sent with a key, status_code stays
+// empty while that request is still running
+#[derive(Clone)]
+pub struct IdempotencyRecord {
+    pub owner_id: String,
+    pub key: String,
+    pub request_hash: String,
+    pub status_code: Option<i32>,
+    pub content_type: Option<String>,
+    pub body: Option<Vec<u8>>,
+    pub created_at: NaiveDateTime,
+}
@@ -0,0 +1,3 @@
+pub mod idempotency_record;
+mod repo;
+pub mod service;
@@ -0,0 +1,108 @@
+use chrono::NaiveDateTime;
+use postgres::Row;
+
+use crate::{database::storage::Storage, error::app_error::DynAppError};
+
+use super::idempotency_record::IdempotencyRecord;
+
+#[derive(Clone)]
+pub struct IdempotencyRepo {
+    storage: Storage,
+}
+
+impl IdempotencyRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    // returns 0 when the key is already taken, the primary key settles races
+    // between requests arriving at the same time
+    pub async fn reserve(
+        &self,
+        owner_id: String,
+        key: String,
+        request_hash: String,
+        created_at: NaiveDateTime,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                idempotency_key
+                    (owner_id, i_key, request_hash, created_at)
+                VALUES
+                    ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING;",
+        );
+
+        self.storage
+            .exec(cmd, &[&owner_id, &key, &request_hash, &created_at])
+            .await
+    }
+
+    pub async fn get(
+        &self,
+        owner_id: String,
+        key: String,
+    ) -> Result<Option<IdempotencyRecord>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT *
+            FROM
+                idempotency_key
+            WHERE
+                owner_id = $1 and i_key = $2;",
+        );
+
+        let rows = self.storage.query(cmd, &[&owner_id, &key]).await?;
+
+        Ok(rows.first().map(record_from_row))
+    }
+
+    pub async fn complete(
+        &self,
+        owner_id: String,
+        key: String,
+        status_code: i32,
+        content_type: Option<String>,
+        body: Vec<u8>,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE idempotency_key
+            SET status_code = $3, content_type = $4, body = $5
+            WHERE owner_id = $1 and i_key = $2",
+        );
+
+        self.storage
+            .exec(cmd, &[&owner_id, &key, &status_code, &content_type, &body])
+            .await
+    }
+
+    pub async fn delete(&self, owner_id: String, key: String) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "DELETE FROM idempotency_key
+            WHERE owner_id = $1 and i_key = $2",
+        );
+
+        self.storage.exec(cmd, &[&owner_id, &key]).await
+    }
+
+    pub async fn delete_older_than(&self, created_at: NaiveDateTime) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "DELETE FROM idempotency_key
+            WHERE created_at < $1",
+        );
+
+        self.storage.exec(cmd, &[&created_at]).await
+    }
+}
+
+fn record_from_row(row: &Row) -> IdempotencyRecord {
+    IdempotencyRecord {
+        owner_id: row.get("owner_id"),
+        key: row.get("i_key"),
+        request_hash: row.get("request_hash"),
+        status_code: row.get("status_code"),
+        content_type: row.get("content_type"),
+        body: row.get("body"),
+        created_at: row.get("created_at"),
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{Duration, Utc};
+
+use crate::{database::storage::Storage, error::app_error::DynAppError};
+
+use super::{idempotency_record::IdempotencyRecord, repo::IdempotencyRepo};
+
+// how long a stored response can be replayed
+const KEY_TTL_HOURS: i64 = 24;
+// a request running for longer than this is assumed lost, e.g. the server
+// restarted in the middle of it, and its key can be used again
+const IN_PROGRESS_TIMEOUT_MINUTES: i64 = 10;
+
+pub enum IdempotentAttempt {
+    // first time the key is seen, the request runs and its response is stored
+    Proceed,
+    Replay(IdempotencyRecord),
+    InProgress,
+    // the key was used before with a different method, path or body
+    Mismatch,
+}
+
+#[derive(Clone)]
+pub struct IdempotencyService {
+    repo: IdempotencyRepo,
+}
+
+impl IdempotencyService {
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            repo: IdempotencyRepo::new(storage),
+        }
+    }
+
+    // keys belong to whoever sent them, two clients picking the same key never
+    // see each other's responses
+    pub async fn begin(
+        &self,
+        owner_id: String,
+        key: String,
+        request_hash: String,
+    ) -> Result<IdempotentAttempt, DynAppError> {
+        let now = Utc::now().naive_utc();
+        self.repo
+            .delete_older_than(now - Duration::hours(KEY_TTL_HOURS))
+            .await?;
+
+        // a second round only happens when the record found is gone or was lost
+        for _ in 0..2 {
+            let reserved = self
+                .repo
+                .reserve(owner_id.clone(), key.clone(), request_hash.clone(), now)
+                .await?;
+            if reserved == 1 {
+                return Ok(IdempotentAttempt::Proceed);
+            }
+
+            let record = match self.repo.get(owner_id.clone(), key.clone()).await? {
+                Some(record) => record,
+                None => continue,
+            };
+
+            if record.request_hash != request_hash {
+                return Ok(IdempotentAttempt::Mismatch);
+            }
+
+            if record.status_code.is_some() {
+                return Ok(IdempotentAttempt::Replay(record));
+            }
+
+            if record.created_at > now - Duration::minutes(IN_PROGRESS_TIMEOUT_MINUTES) {
+                return Ok(IdempotentAttempt::InProgress);
+            }
+
+            self.repo.delete(owner_id.clone(), key.clone()).await?;
+        }
+
+        Ok(IdempotentAttempt::InProgress)
+    }
+
+    pub async fn complete(
+        &self,
+        owner_id: String,
+        key: String,
+        status_code: u16,
+        content_type: Option<String>,
+        body: Vec<u8>,
+    ) -> Result<(), DynAppError> {
+        self.repo
+            .complete(owner_id, key, status_code as i32, content_type, body)
+            .await?;
+        Ok(())
+    }
+
+    // gives the key back when there is no response worth replaying, so the
+    // client can retry with it
+    pub async fn release(&self, owner_id: String, key: String) -> Result<(), DynAppError> {
+        self.repo.delete(owner_id, key).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api_contracts::import_report::{FeatureReport, ImportReport},
+    geometry::polygon::{contains_point, validate_polygon},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FeatureKind {
+    Subdivision,
+    Lot,
+}
+
+// a feature read from an imported file, before it is validated and saved
+#[derive(Clone)]
+pub struct DraftFeature {
+    // position of the feature in the source file
+    pub index: usize,
+    pub kind: FeatureKind,
+    pub name: Option<String>,
+    // (lat, long) pairs, open ring
+    pub area: Vec<(f64, f64)>,
+    // lots only, when the source carries it
+    pub price: Option<f64>,
+    // lots only, the block must already exist in the subdivision
+    pub block_name: Option<String>,
+    pub errors: Vec<String>,
+}
+
+// whatever an import produced, regardless of its source format
+#[derive(Clone, Default)]
+pub struct PlatDraft {
+    pub features: Vec<DraftFeature>,
+    // problems that do not belong to a single feature
+    pub errors: Vec<String>,
+}
+
+impl PlatDraft {
+    pub fn boundary(&self) -> Option<&DraftFeature> {
+        self.features
+            .iter()
+            .find(|feature| feature.kind == FeatureKind::Subdivision)
+    }
+
+    pub fn lots(&self) -> Vec<&DraftFeature> {
+        self.features
+            .iter()
+            .filter(|feature| feature.kind == FeatureKind::Lot)
+            .collect()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+            && self
+                .features
+                .iter()
+                .all(|feature| feature.errors.is_empty())
+    }
+
+    // expects a single subdivision boundary among the features
+    pub fn validate(&mut self) {
+        let boundaries = self
+            .features
+            .iter()
+            .filter(|feature| feature.kind == FeatureKind::Subdivision)
+            .count();
+        if boundaries != 1 {
+            self.errors.push(format!(
+                "Expected exactly one subdivision boundary, found {}",
+                boundaries
+            ));
+        }
+
+        let boundary_area = self.boundary().map(|boundary| boundary.area.clone());
+        self.validate_features(boundary_area.as_deref());
+    }
+
+    // validates every feature on its own and the lots against each other and,
+    // when informed, against the boundary they must fall in. Used directly when
+    // the lots go to a subdivision that already exists
+    pub fn validate_features(&mut self, boundary_area: Option<&[(f64, f64)]>) {
+        if self.lots().is_empty() {
+            self.errors.push(String::from("No lots were found"));
+        }
+
+        let mut names: HashMap<String, usize> = HashMap::new();
+        for feature in self.features.iter_mut() {
+            feature.errors.extend(validate_polygon(&feature.area));
+
+            match &feature.name {
+                Some(name) if !name.trim().is_empty() => {
+                    if feature.kind == FeatureKind::Lot {
+                        if let Some(first_index) = names.get(name) {
+                            feature.errors.push(format!(
+                                "The lot name {} is already used by feature {}",
+                                name, first_index
+                            ));
+                        } else {
+                            names.insert(name.clone(), feature.index);
+                        }
+                    }
+                }
+                _ => feature.errors.push(String::from("The feature has no name")),
+            }
+
+            if feature.kind == FeatureKind::Lot && !feature.area.is_empty() {
+                if let Some(boundary_area) = boundary_area {
+                    // lots usually share edges with the boundary, so their centroid
+                    // is tested instead of every vertex
+                    if !contains_point(boundary_area, centroid(&feature.area)) {
+                        feature.errors.push(String::from(
+                            "The lot is outside of the subdivision boundary",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn report(&self, subdivision_id: String, created: bool) -> ImportReport {
+        ImportReport {
+            subdivision_id,
+            created,
+            errors: self.errors.clone(),
+            features: self
+                .features
+                .iter()
+                .map(|feature| FeatureReport {
+                    index: feature.index,
+                    kind: feature.kind,
+                    name: feature.name.clone(),
+                    status: String::from(match (feature.errors.is_empty(), created) {
+                        (false, _) => "invalid",
+                        (true, true) => "created",
+                        (true, false) => "valid",
+                    }),
+                    errors: feature.errors.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn centroid(points: &[(f64, f64)]) -> (f64, f64) {
+    let amount = points.len() as f64;
+    (
+        points.iter().map(|p| p.0).sum::<f64>() / amount,
+        points.iter().map(|p| p.1).sum::<f64>() / amount,
+    )
+}
@@ -0,0 +1,511 @@
+use crate::geometry::{
+    polygon::{contains_point, open_ring},
+    utm::UtmZone,
+};
+
+use super::draft::{DraftFeature, FeatureKind, PlatDraft};
+
+pub struct DxfMapping {
+    // layer holding the closed lot polylines
+    pub lot_layer: String,
+    // layer holding the TEXT or MTEXT lot names
+    pub label_layer: String,
+    // zone the drawing coordinates are in, eastings on 10 and northings on 20
+    pub zone: UtmZone,
+}
+
+// an entity of the ENTITIES section, as its group code / value pairs
+struct RawEntity {
+    kind: String,
+    pairs: Vec<(i32, String)>,
+}
+
+impl RawEntity {
+    fn value(&self, code: i32) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(pair_code, _)| *pair_code == code)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn layer(&self) -> &str {
+        self.value(8).unwrap_or("0")
+    }
+
+    fn on_layer(&self, layer: &str) -> bool {
+        // layer names are case insensitive in AutoCAD
+        self.layer().eq_ignore_ascii_case(layer)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.value(70)
+            .and_then(|value| value.parse::<i32>().ok())
+            .map(|flags| flags & 1 == 1)
+            .unwrap_or(false)
+    }
+
+    // pairs of 10 and 20 codes, in the order they appear
+    fn points(&self) -> Vec<(f64, f64)> {
+        let mut points: Vec<(f64, f64)> = vec![];
+        let mut x: Option<f64> = None;
+        for (code, value) in self.pairs.iter() {
+            match code {
+                10 => x = value.parse::<f64>().ok(),
+                20 => {
+                    if let (Some(x), Ok(y)) = (x, value.parse::<f64>()) {
+                        points.push((x, y));
+                    }
+                    x = None;
+                }
+                _ => {}
+            }
+        }
+        points
+    }
+}
+
+struct Label {
+    text: String,
+    position: (f64, f64),
+}
+
+// only ASCII DXF is supported, arcs in polylines (bulges) are read as straight
+// edges between their vertices
+pub fn read_dxf(content: &str, mapping: &DxfMapping) -> Result<PlatDraft, String> {
+    let entities = read_entities(content)?;
+
+    let mut polylines: Vec<Vec<(f64, f64)>> = vec![];
+    let mut labels: Vec<Label> = vec![];
+
+    let mut position = 0;
+    while position < entities.len() {
+        let entity = &entities[position];
+        match entity.kind.as_str() {
+            "LWPOLYLINE" if entity.on_layer(&mapping.lot_layer) => {
+                if let Some(ring) = closed_ring(entity.points(), entity.is_closed()) {
+                    polylines.push(ring);
+                }
+            }
+            // the old style polyline keeps its vertices in the entities after it
+            "POLYLINE" => {
+                let mut points: Vec<(f64, f64)> = vec![];
+                while position + 1 < entities.len() && entities[position + 1].kind == "VERTEX" {
+                    position += 1;
+                    points.extend(entities[position].points());
+                }
+                if entity.on_layer(&mapping.lot_layer) {
+                    if let Some(ring) = closed_ring(points, entity.is_closed()) {
+                        polylines.push(ring);
+                    }
+                }
+            }
+            "TEXT" | "MTEXT" if entity.on_layer(&mapping.label_layer) => {
+                if let Some(position) = entity.points().first() {
+                    labels.push(Label {
+                        text: label_text(entity),
+                        position: *position,
+                    });
+                }
+            }
+            _ => {}
+        }
+        position += 1;
+    }
+
+    let mut draft = PlatDraft::default();
+    for (index, ring) in polylines.into_iter().enumerate() {
+        let mut feature = DraftFeature {
+            index,
+            kind: FeatureKind::Lot,
+            name: None,
+            area: vec![],
+            price: None,
+            block_name: None,
+            errors: vec![],
+        };
+
+        // containment is tested on the drawing coordinates, before reprojecting
+        let inside: Vec<&Label> = labels
+            .iter()
+            .filter(|label| !label.text.is_empty() && contains_point(&ring, label.position))
+            .collect();
+        // lots left without a name are reported by the draft validation
+        match inside.as_slice() {
+            [] => {}
+            [label] => feature.name = Some(label.text.clone()),
+            _ => feature.errors.push(format!(
+                "More than one label was found inside the polyline: {}",
+                inside
+                    .iter()
+                    .map(|label| label.text.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+        }
+
+        // (lat, long) out of (easting, northing)
+        feature.area = ring
+            .iter()
+            .map(|(easting, northing)| mapping.zone.to_wgs84(*easting, *northing))
+            .collect();
+
+        draft.features.push(feature);
+    }
+
+    Ok(draft)
+}
+
+fn read_entities(content: &str) -> Result<Vec<RawEntity>, String> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    // editors often leave blank lines after the closing EOF
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    if !lines.len().is_multiple_of(2) {
+        return Err(String::from(
+            "The DXF file is truncated, only ASCII DXF files are supported",
+        ));
+    }
+
+    let mut entities: Vec<RawEntity> = vec![];
+    let mut in_entities = false;
+    let mut current: Option<RawEntity> = None;
+
+    for (pair_index, pair) in lines.chunks(2).enumerate() {
+        let code = pair[0].trim().parse::<i32>().map_err(|_| {
+            format!(
+                "Invalid group code at line {}, only ASCII DXF files are supported",
+                pair_index * 2 + 1
+            )
+        })?;
+        let value = pair[1].trim();
+
+        if code == 0 {
+            if let Some(entity) = current.take() {
+                entities.push(entity);
+            }
+            match value {
+                "ENDSEC" => in_entities = false,
+                _ if in_entities => {
+                    current = Some(RawEntity {
+                        kind: value.to_string(),
+                        pairs: vec![],
+                    })
+                }
+                _ => {}
+            }
+        } else if code == 2 && value == "ENTITIES" {
+            in_entities = true;
+        } else if let Some(entity) = current.as_mut() {
+            entity.pairs.push((code, value.to_string()));
+        }
+    }
+
+    Ok(entities)
+}
+
+// a polyline counts as closed when flagged so or when it ends where it started
+fn closed_ring(points: Vec<(f64, f64)>, flagged_closed: bool) -> Option<Vec<(f64, f64)>> {
+    let ends_at_start = points.len() > 3 && points.first() == points.last();
+    if flagged_closed || ends_at_start {
+        Some(open_ring(&points))
+    } else {
+        None
+    }
+}
+
+// MTEXT splits long texts over 3 codes followed by a last 1 code, and carries
+// inline formatting such as {\fArial|b1;Q1-L01} or \P for new lines
+fn label_text(entity: &RawEntity) -> String {
+    let mut raw = String::new();
+    for (code, value) in entity.pairs.iter() {
+        if *code == 3 || *code == 1 {
+            raw.push_str(value);
+        }
+    }
+
+    let mut text = String::new();
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' | '}' => {}
+            '\\' => match chars.next() {
+                Some('P') => text.push(' '),
+                Some(escaped @ ('\\' | '{' | '}')) => text.push(escaped),
+                // formatting codes run until the next semicolon
+                Some(_) => {
+                    for skipped in chars.by_ref() {
+                        if skipped == ';' {
+                            break;
+                        }
+                    }
+                }
+                None => {}
+            },
+            _ => text.push(c),
+        }
+    }
+
+    text.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::utm::Hemisphere;
+
+    // two lots in SIRGAS 2000 / UTM zone 23S, in São Paulo: a closed
+    // LWPOLYLINE named by a TEXT and an old style POLYLINE ending where it
+    // started, named by a formatted MTEXT. The street and its label are on
+    // layers that are not imported
+    const PLAT: &str = r"  0
+SECTION
+  2
+HEADER
+  9
+$ACADVER
+  1
+AC1015
+  0
+ENDSEC
+  0
+SECTION
+  2
+ENTITIES
+  0
+LWPOLYLINE
+  8
+LOTES
+ 90
+4
+ 70
+1
+ 10
+333000.0
+ 20
+7394000.0
+ 10
+333020.0
+ 20
+7394000.0
+ 10
+333020.0
+ 20
+7394030.0
+ 10
+333000.0
+ 20
+7394030.0
+  0
+TEXT
+  8
+NOMES
+ 10
+333010.0
+ 20
+7394015.0
+  1
+L01
+  0
+POLYLINE
+  8
+LOTES
+ 70
+0
+  0
+VERTEX
+  8
+LOTES
+ 10
+333020.0
+ 20
+7394000.0
+  0
+VERTEX
+  8
+LOTES
+ 10
+333040.0
+ 20
+7394000.0
+  0
+VERTEX
+  8
+LOTES
+ 10
+333040.0
+ 20
+7394030.0
+  0
+VERTEX
+  8
+LOTES
+ 10
+333020.0
+ 20
+7394030.0
+  0
+VERTEX
+  8
+LOTES
+ 10
+333020.0
+ 20
+7394000.0
+  0
+SEQEND
+  8
+LOTES
+  0
+MTEXT
+  8
+NOMES
+ 10
+333030.0
+ 20
+7394010.0
+  1
+{\fArial|b1;Q1-L02}
+  0
+LWPOLYLINE
+  8
+RUAS
+ 90
+4
+ 70
+1
+ 10
+333000.0
+ 20
+7393980.0
+ 10
+333040.0
+ 20
+7393980.0
+ 10
+333040.0
+ 20
+7394000.0
+ 10
+333000.0
+ 20
+7394000.0
+  0
+TEXT
+  8
+RUAS
+ 10
+333020.0
+ 20
+7393990.0
+  1
+Rua A
+  0
+ENDSEC
+  0
+EOF";
+
+    fn mapping() -> DxfMapping {
+        DxfMapping {
+            // layers match whatever their case
+            lot_layer: String::from("lotes"),
+            label_layer: String::from("Nomes"),
+            zone: UtmZone::new(23, Hemisphere::South).unwrap(),
+        }
+    }
+
+    #[test]
+    fn reads_the_lots_of_the_lot_layer() {
+        let draft = read_dxf(PLAT, &mapping()).unwrap();
+
+        assert!(draft.errors.is_empty());
+        assert_eq!(draft.features.len(), 2);
+        for feature in draft.features.iter() {
+            assert_eq!(feature.kind, FeatureKind::Lot);
+            // stored open, the repeated closing vertex is dropped
+            assert_eq!(feature.area.len(), 4);
+            assert!(feature.errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn names_lots_after_the_label_inside_them() {
+        let draft = read_dxf(PLAT, &mapping()).unwrap();
+
+        assert_eq!(draft.features[0].name.as_deref(), Some("L01"));
+        assert_eq!(draft.features[1].name.as_deref(), Some("Q1-L02"));
+    }
+
+    #[test]
+    fn reports_polylines_with_more_than_one_label() {
+        // a second TEXT inside the first lot, at the end of the ENTITIES section
+        let label = "  0\nTEXT\n  8\nNOMES\n 10\n333005.0\n 20\n7394005.0\n  1\nL99\n";
+        let end = PLAT.rfind("  0\nENDSEC").unwrap();
+        let content = format!("{}{}{}", &PLAT[..end], label, &PLAT[end..]);
+
+        let draft = read_dxf(&content, &mapping()).unwrap();
+
+        assert_eq!(draft.features[0].name, None);
+        assert_eq!(
+            draft.features[0].errors,
+            vec![String::from(
+                "More than one label was found inside the polyline: L01, L99"
+            )]
+        );
+        assert_eq!(draft.features[1].name.as_deref(), Some("Q1-L02"));
+    }
+
+    #[test]
+    fn reprojects_the_drawing_to_wgs84() {
+        let draft = read_dxf(PLAT, &mapping()).unwrap();
+
+        let zone = mapping().zone;
+        let area = &draft.features[0].area;
+        assert_eq!(area[0], zone.to_wgs84(333000.0, 7394000.0));
+        assert_eq!(area[2], zone.to_wgs84(333020.0, 7394030.0));
+
+        let (lat, long) = area[0];
+        assert!((-23.57..-23.54).contains(&lat), "{}", lat);
+        assert!((-46.64..-46.61).contains(&long), "{}", long);
+        // northings grow to the north
+        assert!(area[2].0 > area[0].0);
+    }
+
+    #[test]
+    fn accepts_blank_lines_after_the_end_of_file() {
+        for ending in ["\n", "\n\n", "\r\n\r\n", "\n  \n"] {
+            let content = format!("{}{}", PLAT, ending);
+
+            let draft = read_dxf(&content, &mapping()).unwrap();
+            assert_eq!(draft.features.len(), 2, "{:?}", ending);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let content = PLAT.trim_end_matches("EOF");
+
+        let err = read_dxf(content, &mapping()).err().unwrap();
+        assert!(err.starts_with("The DXF file is truncated"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_binary_files() {
+        let content = "AutoCAD Binary DXF\r\n\u{1a}\0";
+
+        assert!(read_dxf(content, &mapping()).is_err());
+    }
+
+    #[test]
+    fn strips_mtext_formatting() {
+        let entity = RawEntity {
+            kind: String::from("MTEXT"),
+            pairs: vec![
+                (3, String::from("{\\fArial|b1;Lote\\P")),
+                (1, String::from("12 \\{A\\}}")),
+            ],
+        };
+
+        assert_eq!(label_text(&entity), "Lote 12 {A}");
+    }
+}
@@ -0,0 +1,94 @@
+use geojson::{Feature, FeatureCollection, JsonValue, Value};
+
+use crate::geometry::polygon::open_ring;
+
+use super::draft::{DraftFeature, FeatureKind, PlatDraft};
+
+pub struct GeoJsonMapping {
+    // property holding the subdivision and lot names
+    pub name_property: String,
+    // property telling the boundary apart from the lots: the boundary has it set
+    // to "subdivision", every other feature is a lot
+    pub kind_property: String,
+}
+
+pub fn read_feature_collection(
+    collection: &FeatureCollection,
+    mapping: &GeoJsonMapping,
+) -> PlatDraft {
+    let mut draft = PlatDraft::default();
+
+    for (index, feature) in collection.features.iter().enumerate() {
+        let kind = match property_as_string(feature, &mapping.kind_property) {
+            Some(kind) if kind.eq_ignore_ascii_case("subdivision") => FeatureKind::Subdivision,
+            _ => FeatureKind::Lot,
+        };
+
+        let mut draft_feature = DraftFeature {
+            index,
+            kind,
+            name: property_as_string(feature, &mapping.name_property),
+            area: vec![],
+            price: None,
+            block_name: None,
+            errors: vec![],
+        };
+
+        match read_polygon(feature) {
+            Ok(area) => draft_feature.area = area,
+            Err(err) => draft_feature.errors.push(err),
+        }
+
+        draft.features.push(draft_feature);
+    }
+
+    draft
+}
+
+// only the exterior ring is kept, the server has no notion of holes
+fn read_polygon(feature: &Feature) -> Result<Vec<(f64, f64)>, String> {
+    let geometry = match &feature.geometry {
+        Some(geometry) => geometry,
+        None => return Err(String::from("The feature has no geometry")),
+    };
+
+    let exterior = match &geometry.value {
+        Value::Polygon(rings) => rings.first(),
+        Value::MultiPolygon(polygons) if polygons.len() == 1 => polygons[0].first(),
+        Value::MultiPolygon(_) => {
+            return Err(String::from(
+                "MultiPolygons with more than one polygon are not supported",
+            ))
+        }
+        other => {
+            return Err(format!(
+                "Expected a Polygon geometry, found {}",
+                other.type_name()
+            ))
+        }
+    };
+
+    let exterior = match exterior {
+        Some(ring) => ring,
+        None => return Err(String::from("The polygon has no rings")),
+    };
+
+    let mut points: Vec<(f64, f64)> = vec![];
+    for position in exterior.iter() {
+        if position.len() < 2 {
+            return Err(String::from("Positions need a longitude and a latitude"));
+        }
+        // GeoJSON positions are [long, lat]
+        points.push((position[1], position[0]));
+    }
+
+    Ok(open_ring(&points))
+}
+
+fn property_as_string(feature: &Feature, property: &str) -> Option<String> {
+    match feature.property(property) {
+        Some(JsonValue::String(value)) => Some(value.clone()),
+        Some(JsonValue::Number(value)) => Some(value.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,6 @@
+pub mod draft;
+pub mod dxf;
+pub mod geojson;
+pub mod service;
+pub mod shapefile;
+pub mod spreadsheet;
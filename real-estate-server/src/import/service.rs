@@ -0,0 +1,262 @@
+use crate::{
+    api_contracts::{
+        dxf_import_params::DxfImportParams, geojson_import_payload::GeoJsonImportPayload,
+        import_report::ImportReport, lot_dto::LotDto,
+        shapefile_import_params::ShapefileImportParams,
+        spreadsheet_import_params::SpreadsheetImportParams, subdivision_dto::SubdivisionDto,
+    },
+    error::{api_error::ApiError, app_error::DynAppError},
+    geometry::utm::{Hemisphere, UtmZone},
+    organization::tenant_scope::TenantScope,
+    subdivision::service::{resolve_crs, resolve_organization, SubdivisionService},
+};
+
+use super::{
+    draft::PlatDraft,
+    dxf::{read_dxf, DxfMapping},
+    geojson::{read_feature_collection, GeoJsonMapping},
+    shapefile::{read_shapefile_bundle, ShapefileMapping},
+    spreadsheet::read_spreadsheet,
+};
+
+#[derive(Clone)]
+pub struct ImportService {
+    subdivision_service: SubdivisionService,
+}
+
+impl ImportService {
+    pub fn new(subdivision_service: SubdivisionService) -> Self {
+        Self {
+            subdivision_service,
+        }
+    }
+
+    pub async fn import_geojson(
+        &self,
+        payload: GeoJsonImportPayload,
+        scope: &TenantScope,
+    ) -> Result<ImportReport, DynAppError> {
+        let mapping = GeoJsonMapping {
+            name_property: payload.name_property.unwrap_or(String::from("name")),
+            kind_property: payload.kind_property.unwrap_or(String::from("kind")),
+        };
+
+        let draft = read_feature_collection(&payload.feature_collection, &mapping);
+        self.commit(
+            draft,
+            payload.subdivision_id,
+            payload.organization_id,
+            scope,
+        )
+        .await
+    }
+
+    pub async fn import_shapefile(
+        &self,
+        params: ShapefileImportParams,
+        content: &[u8],
+        scope: &TenantScope,
+    ) -> Result<ImportReport, DynAppError> {
+        let mapping = ShapefileMapping {
+            name_field: params.name_field.unwrap_or(String::from("NAME")),
+            kind_field: params.kind_field.unwrap_or(String::from("KIND")),
+            price_field: params.price_field,
+        };
+
+        let draft = match read_shapefile_bundle(content, &mapping) {
+            Ok(draft) => draft,
+            Err(err) => return Err(Box::new(ApiError::BadRequest(err))),
+        };
+        self.commit(draft, params.subdivision_id, params.organization_id, scope)
+            .await
+    }
+
+    // the lots go to an existing subdivision through the regular batch creation
+    pub async fn import_dxf(
+        &self,
+        subdivision_id: String,
+        params: DxfImportParams,
+        content: String,
+        scope: &TenantScope,
+    ) -> Result<ImportReport, DynAppError> {
+        let zone = match UtmZone::new(
+            params.utm_zone,
+            params.hemisphere.unwrap_or(Hemisphere::South),
+        ) {
+            Some(zone) => zone,
+            None => {
+                return Err(Box::new(ApiError::BadRequest(format!(
+                    "Invalid UTM zone {}",
+                    params.utm_zone
+                ))))
+            }
+        };
+
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+
+        let mapping = DxfMapping {
+            lot_layer: params.lot_layer,
+            label_layer: params.label_layer,
+            zone,
+        };
+        let mut draft = match read_dxf(&content, &mapping) {
+            Ok(draft) => draft,
+            Err(err) => return Err(Box::new(ApiError::BadRequest(err))),
+        };
+
+        draft.validate_features(Some(&subdivision.area));
+        self.check_existing_names(&mut draft, subdivision_id.clone(), scope)
+            .await?;
+
+        if !draft.is_valid() {
+            return Ok(draft.report(subdivision_id, false));
+        }
+
+        let lots = to_lot_dtos(&draft, &subdivision_id);
+        self.subdivision_service
+            .insert_lots(subdivision_id.clone(), &lots, None, scope)
+            .await?;
+
+        Ok(draft.report(subdivision_id, true))
+    }
+
+    // one lot per row. Nothing is written unless every row is valid, except in
+    // partial mode, where the valid rows are created and the report lists the
+    // rest
+    pub async fn import_spreadsheet(
+        &self,
+        subdivision_id: String,
+        params: SpreadsheetImportParams,
+        content: &[u8],
+        scope: &TenantScope,
+    ) -> Result<ImportReport, DynAppError> {
+        let crs = resolve_crs(params.crs)?;
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+
+        let mut draft = match read_spreadsheet(content, crs) {
+            Ok(draft) => draft,
+            Err(err) => return Err(Box::new(ApiError::BadRequest(err))),
+        };
+
+        draft.validate_features(Some(&subdivision.area));
+        self.check_existing_names(&mut draft, subdivision_id.clone(), scope)
+            .await?;
+
+        let block_names = self
+            .subdivision_service
+            .get_block_names(subdivision_id.clone())
+            .await?;
+        for feature in draft.features.iter_mut() {
+            if let Some(block_name) = &feature.block_name {
+                if !block_names.contains(block_name) {
+                    feature
+                        .errors
+                        .push(format!("Block {} not found in the subdivision", block_name));
+                }
+            }
+        }
+
+        let partial = params.partial.unwrap_or(false);
+        let lots = to_lot_dtos(&draft, &subdivision_id);
+        if !draft.errors.is_empty() || lots.is_empty() || (!partial && !draft.is_valid()) {
+            return Ok(draft.report(subdivision_id, false));
+        }
+
+        self.subdivision_service
+            .insert_lots(subdivision_id.clone(), &lots, None, scope)
+            .await?;
+
+        Ok(draft.report(subdivision_id, true))
+    }
+
+    async fn check_existing_names(
+        &self,
+        draft: &mut PlatDraft,
+        subdivision_id: String,
+        scope: &TenantScope,
+    ) -> Result<(), DynAppError> {
+        let existing_names: Vec<String> = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id, scope)
+            .await?
+            .into_iter()
+            .map(|lot| lot.name)
+            .collect();
+        for feature in draft.features.iter_mut() {
+            if let Some(name) = &feature.name {
+                if existing_names.contains(name) {
+                    feature.errors.push(format!(
+                        "A lot named {} already exists in the subdivision",
+                        name
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // saves the draft only when every feature in it is valid, otherwise nothing is
+    // written and the report tells what to fix
+    pub async fn commit(
+        &self,
+        mut draft: PlatDraft,
+        subdivision_id: String,
+        organization_id: Option<String>,
+        scope: &TenantScope,
+    ) -> Result<ImportReport, DynAppError> {
+        let organization_id = resolve_organization(organization_id, scope)?;
+
+        draft.validate();
+        if !draft.is_valid() {
+            return Ok(draft.report(subdivision_id, false));
+        }
+
+        // validate() guarantees the boundary exists and every feature has a name
+        let boundary = draft.boundary().unwrap();
+        let subdivision_dto = SubdivisionDto {
+            id: subdivision_id.clone(),
+            name: boundary.name.clone().unwrap_or_default(),
+            area: Box::new(boundary.area.clone()),
+            lots: None,
+            organization_id: Some(organization_id),
+            crs: None,
+        };
+
+        let lots = to_lot_dtos(&draft, &subdivision_id);
+
+        self.subdivision_service
+            .create_with_lots(subdivision_dto, lots, scope)
+            .await?;
+
+        Ok(draft.report(subdivision_id, true))
+    }
+}
+
+// only the valid lots, which validation guarantees to have a name
+fn to_lot_dtos(draft: &PlatDraft, subdivision_id: &str) -> Vec<LotDto> {
+    draft
+        .lots()
+        .into_iter()
+        .filter(|lot| lot.errors.is_empty())
+        .map(|lot| {
+            let name = lot.name.clone().unwrap_or_default();
+            LotDto {
+                id: format!("{}-{}", name, subdivision_id),
+                area: Box::new(lot.area.clone()),
+                name,
+                subdivision_id: subdivision_id.to_string(),
+                price: lot.price,
+                block_name: lot.block_name.clone(),
+                status: None,
+                crs: None,
+            }
+        })
+        .collect()
+}
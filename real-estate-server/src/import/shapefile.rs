@@ -0,0 +1,238 @@
+use std::io::{Cursor, Read};
+
+use zip::ZipArchive;
+
+use crate::{
+    geometry::polygon::open_ring,
+    shapefile::{
+        dbf::{read_dbf, DbfTable},
+        prj::{read_prj, ShapefileCrs},
+        shp::{read_shp, signed_area, Shape},
+    },
+};
+
+use super::{
+    draft::{DraftFeature, FeatureKind, PlatDraft},
+    spreadsheet::parse_number,
+};
+
+// a few megabytes of zip can unpack to gigabytes, no entry of a real bundle
+// comes close to this
+const MAX_ENTRY_SIZE: u64 = 200 * 1024 * 1024;
+
+pub struct ShapefileMapping {
+    // attribute holding the subdivision and lot names
+    pub name_field: String,
+    // attribute set to "subdivision" on the boundary, every other feature is a lot
+    pub kind_field: String,
+    // attribute holding the lot prices, if any
+    pub price_field: Option<String>,
+}
+
+// the bundle is a zip with one .shp and its .dbf and .prj next to it
+pub fn read_shapefile_bundle(
+    bytes: &[u8],
+    mapping: &ShapefileMapping,
+) -> Result<PlatDraft, String> {
+    let mut archive = match ZipArchive::new(Cursor::new(bytes)) {
+        Ok(archive) => archive,
+        Err(_) => return Err(String::from("The shapefile bundle is not a valid zip file")),
+    };
+
+    let shp = read_entry(&mut archive, "shp", MAX_ENTRY_SIZE)?;
+    let dbf = read_entry(&mut archive, "dbf", MAX_ENTRY_SIZE)?;
+    let prj = read_entry(&mut archive, "prj", MAX_ENTRY_SIZE)?;
+
+    let shapes = read_shp(&shp)?;
+    let table = read_dbf(&dbf)?;
+    let crs = read_prj(&String::from_utf8_lossy(&prj))?;
+
+    if shapes.len() != table.records.len() {
+        return Err(format!(
+            "The .shp file has {} shapes but the .dbf file has {} records",
+            shapes.len(),
+            table.records.len()
+        ));
+    }
+    if !table.has_field(&mapping.name_field) {
+        return Err(format!("The .dbf file has no {} field", mapping.name_field));
+    }
+
+    let mut draft = PlatDraft::default();
+    for (index, shape) in shapes.into_iter().enumerate() {
+        draft
+            .features
+            .push(read_feature(index, shape, &table, &crs, mapping));
+    }
+
+    Ok(draft)
+}
+
+fn read_feature(
+    index: usize,
+    shape: Shape,
+    table: &DbfTable,
+    crs: &ShapefileCrs,
+    mapping: &ShapefileMapping,
+) -> DraftFeature {
+    let kind = match table.value(index, &mapping.kind_field) {
+        Some(kind) if kind.eq_ignore_ascii_case("subdivision") => FeatureKind::Subdivision,
+        _ => FeatureKind::Lot,
+    };
+
+    let mut feature = DraftFeature {
+        index,
+        kind,
+        name: table.value(index, &mapping.name_field).map(String::from),
+        area: vec![],
+        price: None,
+        block_name: None,
+        errors: vec![],
+    };
+
+    if let Some(price_field) = &mapping.price_field {
+        if let Some(price) = table.value(index, price_field) {
+            // decimal commas are common in Brazilian tables
+            match parse_number(price) {
+                Some(price) if price >= 0.0 => feature.price = Some(price),
+                _ => feature.errors.push(format!("Invalid price {}", price)),
+            }
+        }
+    }
+
+    let rings = match shape {
+        Shape::Polygon(rings) => rings,
+        Shape::Null => {
+            feature
+                .errors
+                .push(String::from("The feature has no geometry"));
+            return feature;
+        }
+        Shape::Unsupported(shape_type) => {
+            feature.errors.push(format!(
+                "Expected a polygon shape, found shape type {}",
+                shape_type
+            ));
+            return feature;
+        }
+    };
+
+    // outer rings are the clockwise ones, holes are dropped as the server has no
+    // notion of them
+    let outer: Vec<&Vec<(f64, f64)>> = rings
+        .iter()
+        .filter(|ring| signed_area(ring) < 0.0)
+        .collect();
+    let ring = match outer.as_slice() {
+        [ring] => *ring,
+        [] => {
+            feature
+                .errors
+                .push(String::from("The polygon has no outer ring"));
+            return feature;
+        }
+        _ => {
+            feature.errors.push(String::from(
+                "Polygons with more than one part are not supported",
+            ));
+            return feature;
+        }
+    };
+
+    let points: Vec<(f64, f64)> = ring
+        .iter()
+        .map(|(x, y)| match crs {
+            ShapefileCrs::Geographic => (*y, *x),
+            ShapefileCrs::Utm(zone) => zone.to_wgs84(*x, *y),
+        })
+        .collect();
+    feature.area = open_ring(&points);
+
+    feature
+}
+
+// the size the entry declares is checked first, and the read stops past
+// max_size in case it lies
+fn read_entry(
+    archive: &mut ZipArchive<Cursor<&[u8]>>,
+    extension: &str,
+    max_size: u64,
+) -> Result<Vec<u8>, String> {
+    let suffix = format!(".{}", extension);
+    let too_large = || {
+        format!(
+            "The .{} file is larger than {} MB",
+            extension,
+            max_size / (1024 * 1024)
+        )
+    };
+    for position in 0..archive.len() {
+        let entry = match archive.by_index(position) {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        if !entry.name().to_lowercase().ends_with(&suffix) {
+            continue;
+        }
+        if entry.size() > max_size {
+            return Err(too_large());
+        }
+
+        let mut content: Vec<u8> = vec![];
+        return match entry.take(max_size + 1).read_to_end(&mut content) {
+            Ok(_) if content.len() as u64 > max_size => Err(too_large()),
+            Ok(_) => Ok(content),
+            Err(_) => Err(format!("The .{} file could not be read", extension)),
+        };
+    }
+
+    Err(format!("The shapefile bundle has no .{} file", extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+    use super::*;
+
+    fn bundle(files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (name, content) in files.iter() {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn reads_entries_by_extension() {
+        let bytes = bundle(&[("lots.SHP", vec![1, 2, 3]), ("lots.dbf", vec![4])]);
+        let mut archive = ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+
+        assert_eq!(read_entry(&mut archive, "shp", 1024), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            read_entry(&mut archive, "prj", 1024),
+            Err(String::from("The shapefile bundle has no .prj file"))
+        );
+    }
+
+    #[test]
+    fn refuses_entries_past_the_size_limit() {
+        // zeros compress to almost nothing, like in a zip bomb
+        let bytes = bundle(&[("lots.shp", vec![0; 4 * 1024 * 1024])]);
+        assert!(bytes.len() < 64 * 1024);
+        let mut archive = ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+
+        assert_eq!(
+            read_entry(&mut archive, "shp", 1024 * 1024),
+            Err(String::from("The .shp file is larger than 1 MB"))
+        );
+        assert_eq!(
+            read_entry(&mut archive, "shp", 4 * 1024 * 1024).map(|content| content.len()),
+            Ok(4 * 1024 * 1024)
+        );
+    }
+}
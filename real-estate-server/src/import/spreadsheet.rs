@@ -0,0 +1,396 @@
+use std::io::Cursor;
+
+use calamine::{Data, Reader, Xlsx};
+
+use crate::{
+    geometry::{
+        crs::Crs,
+        polygon::{area_m2, open_ring},
+    },
+    shapefile::dbf::decode,
+};
+
+use super::draft::{DraftFeature, FeatureKind, PlatDraft};
+
+// declared areas may differ this much from the drawn polygon, rounding included
+const AREA_TOLERANCE: f64 = 0.01;
+
+// columns are found by their header, in English or Portuguese
+const NAME_HEADERS: [&str; 3] = ["name", "nome", "lote"];
+const BLOCK_HEADERS: [&str; 2] = ["block", "quadra"];
+const AREA_HEADERS: [&str; 1] = ["area"];
+const PRICE_HEADERS: [&str; 3] = ["price", "preco", "valor"];
+const COORDINATES_HEADERS: [&str; 3] = ["coordinates", "coordenadas", "vertices"];
+
+struct Columns {
+    name: usize,
+    block: Option<usize>,
+    area: Option<usize>,
+    price: Option<usize>,
+    coordinates: usize,
+}
+
+// One lot per row below a header row. The coordinates cell lists the vertices
+// separated by semicolons, each as two numbers in the order of the crs, such as
+// "-23.5505 -46.6333; -23.5506 -46.6320; ...". XLSX files are told apart from
+// CSV by their zip signature.
+pub fn read_spreadsheet(content: &[u8], crs: Crs) -> Result<PlatDraft, String> {
+    let rows = if content.starts_with(b"PK\x03\x04") {
+        read_xlsx(content)?
+    } else {
+        read_csv(content)?
+    };
+
+    let mut draft = PlatDraft::default();
+    let mut rows = rows.into_iter().enumerate();
+    let columns = match rows.next() {
+        Some((_, header)) => match find_columns(&header) {
+            Ok(columns) => columns,
+            Err(err) => {
+                draft.errors.push(err);
+                return Ok(draft);
+            }
+        },
+        None => {
+            draft.errors.push(String::from("The spreadsheet is empty"));
+            return Ok(draft);
+        }
+    };
+
+    for (position, row) in rows {
+        if row.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        // numbered like the spreadsheet shows it, the header being row 1
+        draft
+            .features
+            .push(read_row(position + 1, &row, &columns, crs));
+    }
+
+    Ok(draft)
+}
+
+fn read_row(index: usize, row: &[String], columns: &Columns, crs: Crs) -> DraftFeature {
+    let cell = |column: Option<usize>| {
+        column
+            .and_then(|column| row.get(column))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut feature = DraftFeature {
+        index,
+        kind: FeatureKind::Lot,
+        name: cell(Some(columns.name)).map(String::from),
+        area: vec![],
+        price: None,
+        block_name: cell(columns.block).map(String::from),
+        errors: vec![],
+    };
+
+    if let Some(price) = cell(columns.price) {
+        match parse_number(price) {
+            Some(price) if price >= 0.0 => feature.price = Some(price),
+            _ => feature.errors.push(format!("Invalid price {}", price)),
+        }
+    }
+
+    match cell(Some(columns.coordinates)).map(parse_coordinates) {
+        Some(Ok(points)) => feature.area = open_ring(&crs.area_to_wgs84(&points)),
+        Some(Err(err)) => feature.errors.push(err),
+        None => feature
+            .errors
+            .push(String::from("The lot has no coordinates")),
+    }
+
+    if let Some(declared) = cell(columns.area) {
+        match parse_number(declared) {
+            Some(declared) if feature.area.len() >= 3 => {
+                let drawn = area_m2(&feature.area);
+                if (drawn - declared).abs() > declared.abs() * AREA_TOLERANCE {
+                    feature.errors.push(format!(
+                        "The declared area of {:.2} m² does not match the {:.2} m² of the coordinates",
+                        declared, drawn
+                    ));
+                }
+            }
+            Some(_) => {}
+            None => feature.errors.push(format!("Invalid area {}", declared)),
+        }
+    }
+
+    feature
+}
+
+fn find_columns(header: &[String]) -> Result<Columns, String> {
+    let find = |aliases: &[&str]| {
+        header
+            .iter()
+            .position(|title| aliases.contains(&normalize_header(title).as_str()))
+    };
+
+    let name = find(&NAME_HEADERS);
+    let coordinates = find(&COORDINATES_HEADERS);
+    match (name, coordinates) {
+        (Some(name), Some(coordinates)) => Ok(Columns {
+            name,
+            block: find(&BLOCK_HEADERS),
+            area: find(&AREA_HEADERS),
+            price: find(&PRICE_HEADERS),
+            coordinates,
+        }),
+        _ => Err(String::from(
+            "The header must have a name and a coordinates column",
+        )),
+    }
+}
+
+// "Área (m²)" becomes "area"
+fn normalize_header(title: &str) -> String {
+    title
+        .split('(')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' => 'a',
+            'é' | 'ê' => 'e',
+            'í' => 'i',
+            'ó' | 'ô' | 'õ' => 'o',
+            'ú' => 'u',
+            'ç' => 'c',
+            _ => c,
+        })
+        .collect()
+}
+
+fn parse_coordinates(value: &str) -> Result<Vec<(f64, f64)>, String> {
+    let mut points: Vec<(f64, f64)> = vec![];
+    for pair in value
+        .split(';')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        // "x y" with either decimal separator, or "x,y" with decimal points
+        let parts: Vec<&str> = match pair.split_whitespace().collect::<Vec<&str>>() {
+            parts if parts.len() == 2 => parts,
+            _ => pair.split(',').map(str::trim).collect(),
+        };
+
+        match parts.as_slice() {
+            [x, y] => match (parse_number(x), parse_number(y)) {
+                (Some(x), Some(y)) => points.push((x, y)),
+                _ => return Err(format!("Invalid vertex {}", pair)),
+            },
+            _ => return Err(format!("Invalid vertex {}", pair)),
+        }
+    }
+
+    Ok(points)
+}
+
+// accepts 1234.56, 1234,56, 1.234,56 and 1,234.56, whatever separator comes
+// last being the decimal one. Amounts in reais follow the Brazilian format, so
+// R$ 1.234 is 1234. Shapefile attributes are read the same way
+pub fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let reais = value.starts_with("R$");
+    let value: String = value
+        .trim_start_matches("R$")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    let normalized = match (value.rfind(','), value.rfind('.')) {
+        (Some(comma), Some(point)) if comma > point => value.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => value.replace(',', ""),
+        (Some(_), None) => value.replace(',', "."),
+        (None, Some(point)) if reais && value.len() - point == 4 => value.replace('.', ""),
+        _ => value,
+    };
+
+    normalized
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+fn read_xlsx(content: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let mut workbook = match Xlsx::new(Cursor::new(content)) {
+        Ok(workbook) => workbook,
+        Err(_) => return Err(String::from("The XLSX file could not be read")),
+    };
+
+    // only the first sheet is imported
+    let range = match workbook.worksheet_range_at(0) {
+        Some(Ok(range)) => range,
+        _ => return Err(String::from("The XLSX file has no readable sheet")),
+    };
+
+    Ok(range
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|cell| match cell {
+                    Data::Empty => String::new(),
+                    _ => cell.to_string(),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+// Brazilian spreadsheets export with semicolons, since the comma is the
+// decimal separator. Whichever appears more on the first line wins
+fn read_csv(content: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let text = decode(content);
+    let text = text.trim_start_matches('\u{feff}');
+    let first_line = text.lines().next().unwrap_or_default();
+    let delimiter = if first_line.matches(';').count() > first_line.matches(',').count() {
+        b';'
+    } else {
+        b','
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let mut rows: Vec<Vec<String>> = vec![];
+    for record in reader.records() {
+        match record {
+            Ok(record) => rows.push(record.iter().map(String::from).collect()),
+            Err(err) => return Err(format!("The CSV file could not be read: {}", err)),
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COORDINATES: &str =
+        "-23.5500 -46.6300; -23.5500 -46.6299; -23.5501 -46.6299; -23.5501 -46.6300";
+
+    fn header(titles: &[&str]) -> Vec<String> {
+        titles.iter().map(|title| title.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_brazilian_and_english_numbers() {
+        assert_eq!(parse_number("1.234,56"), Some(1234.56));
+        assert_eq!(parse_number("1234.56"), Some(1234.56));
+        assert_eq!(parse_number("1234,56"), Some(1234.56));
+        assert_eq!(parse_number("1,234.56"), Some(1234.56));
+        assert_eq!(parse_number(" -46.6333 "), Some(-46.6333));
+    }
+
+    #[test]
+    fn parses_amounts_in_reais() {
+        assert_eq!(parse_number("R$ 1.234"), Some(1234.0));
+        assert_eq!(parse_number("R$ 1.234.567"), Some(1234567.0));
+        assert_eq!(parse_number("R$ 1.234,56"), Some(1234.56));
+        assert_eq!(parse_number("R$ 99.90"), Some(99.9));
+    }
+
+    #[test]
+    fn rejects_empty_and_invalid_cells() {
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("   "), None);
+        assert_eq!(parse_number("R$"), None);
+        assert_eq!(parse_number("abc"), None);
+        assert_eq!(parse_number("NaN"), None);
+    }
+
+    #[test]
+    fn finds_portuguese_columns() {
+        let columns = find_columns(&header(&[
+            "Quadra",
+            "Lote",
+            "Área (m²)",
+            "Preço",
+            "Coordenadas",
+        ]))
+        .unwrap();
+
+        assert_eq!(columns.block, Some(0));
+        assert_eq!(columns.name, 1);
+        assert_eq!(columns.area, Some(2));
+        assert_eq!(columns.price, Some(3));
+        assert_eq!(columns.coordinates, 4);
+    }
+
+    #[test]
+    fn finds_english_columns_in_any_order() {
+        let columns = find_columns(&header(&["coordinates", "Price", "NAME"])).unwrap();
+
+        assert_eq!(columns.name, 2);
+        assert_eq!(columns.coordinates, 0);
+        assert_eq!(columns.price, Some(1));
+        assert_eq!(columns.block, None);
+        assert_eq!(columns.area, None);
+    }
+
+    #[test]
+    fn requires_name_and_coordinates_columns() {
+        assert!(find_columns(&header(&["Lote", "Preço"])).is_err());
+        assert!(find_columns(&header(&["Quadra", "Coordenadas"])).is_err());
+    }
+
+    #[test]
+    fn reads_semicolon_separated_rows() {
+        let csv = format!(
+            "Lote;Quadra;Preço;Coordenadas\nL1;A;R$ 1.234,56;\"{}\"\nL2;;;\"{}\"\n",
+            COORDINATES, COORDINATES
+        );
+
+        let draft = read_spreadsheet(csv.as_bytes(), Crs::Wgs84).unwrap();
+
+        assert!(draft.errors.is_empty());
+        assert_eq!(draft.features.len(), 2);
+        let first = &draft.features[0];
+        // the row number the spreadsheet shows, the header being row 1
+        assert_eq!(first.index, 2);
+        assert_eq!(first.name.as_deref(), Some("L1"));
+        assert_eq!(first.block_name.as_deref(), Some("A"));
+        assert_eq!(first.price, Some(1234.56));
+        assert_eq!(first.area.len(), 4);
+        assert_eq!(first.area[0], (-23.55, -46.63));
+        assert!(first.errors.is_empty());
+
+        // empty cells are missing values, not errors
+        let second = &draft.features[1];
+        assert_eq!(second.block_name, None);
+        assert_eq!(second.price, None);
+        assert!(second.errors.is_empty());
+    }
+
+    #[test]
+    fn reports_invalid_cells_of_a_row() {
+        let csv = format!(
+            "name,price,area,coordinates\nL1,-10,1,\"{}\"\nL2,abc,,\n",
+            COORDINATES
+        );
+
+        let draft = read_spreadsheet(csv.as_bytes(), Crs::Wgs84).unwrap();
+
+        let first = &draft.features[0];
+        assert_eq!(first.errors.len(), 2);
+        assert_eq!(first.errors[0], "Invalid price -10");
+        assert!(first.errors[1].starts_with("The declared area of 1.00 m²"));
+
+        let second = &draft.features[1];
+        assert_eq!(
+            second.errors,
+            vec![
+                String::from("Invalid price abc"),
+                String::from("The lot has no coordinates")
+            ]
+        );
+    }
+}
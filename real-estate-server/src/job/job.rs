@@ -0,0 +1,144 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api_contracts::{
+        dxf_import_params::DxfImportParams, geojson_import_payload::GeoJsonImportPayload,
+        shapefile_import_params::ShapefileImportParams,
+        spreadsheet_import_params::SpreadsheetImportParams,
+    },
+    export::export_format::ExportFormat,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn value_str(&self) -> &'static str {
+        match *self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn from_value_str(value: &str) -> Option<JobStatus> {
+        match value {
+            "queued" => Some(JobStatus::Queued),
+            "running" => Some(JobStatus::Running),
+            "succeeded" => Some(JobStatus::Succeeded),
+            "failed" => Some(JobStatus::Failed),
+            "cancelled" => Some(JobStatus::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    GeojsonImport,
+    ShapefileImport,
+    DxfImport,
+    SpreadsheetImport,
+    SubdivisionExport,
+    SalesTable,
+}
+
+impl JobKind {
+    pub fn value_str(&self) -> &'static str {
+        match *self {
+            JobKind::GeojsonImport => "geojson_import",
+            JobKind::ShapefileImport => "shapefile_import",
+            JobKind::DxfImport => "dxf_import",
+            JobKind::SpreadsheetImport => "spreadsheet_import",
+            JobKind::SubdivisionExport => "subdivision_export",
+            JobKind::SalesTable => "sales_table",
+        }
+    }
+
+    pub fn from_value_str(value: &str) -> Option<JobKind> {
+        match value {
+            "geojson_import" => Some(JobKind::GeojsonImport),
+            "shapefile_import" => Some(JobKind::ShapefileImport),
+            "dxf_import" => Some(JobKind::DxfImport),
+            "spreadsheet_import" => Some(JobKind::SpreadsheetImport),
+            "subdivision_export" => Some(JobKind::SubdivisionExport),
+            "sales_table" => Some(JobKind::SalesTable),
+            _ => None,
+        }
+    }
+}
+
+// everything a worker needs to run the job besides the uploaded file, which is
+// kept apart as raw bytes
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JobRequest {
+    GeojsonImport {
+        payload: GeoJsonImportPayload,
+    },
+    ShapefileImport {
+        params: ShapefileImportParams,
+    },
+    DxfImport {
+        subdivision_id: String,
+        params: DxfImportParams,
+    },
+    SpreadsheetImport {
+        subdivision_id: String,
+        params: SpreadsheetImportParams,
+    },
+    SubdivisionExport {
+        subdivision_id: String,
+        format: ExportFormat,
+    },
+    SalesTable {
+        subdivision_id: String,
+        available_only: bool,
+    },
+}
+
+impl JobRequest {
+    pub fn kind(&self) -> JobKind {
+        match self {
+            JobRequest::GeojsonImport { .. } => JobKind::GeojsonImport,
+            JobRequest::ShapefileImport { .. } => JobKind::ShapefileImport,
+            JobRequest::DxfImport { .. } => JobKind::DxfImport,
+            JobRequest::SpreadsheetImport { .. } => JobKind::SpreadsheetImport,
+            JobRequest::SubdivisionExport { .. } => JobKind::SubdivisionExport,
+            JobRequest::SalesTable { .. } => JobKind::SalesTable,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Job {
+    pub id: String,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    // the JobRequest, as JSON
+    pub request: String,
+    // the user or API key that enqueued the job, the only one who sees it
+    pub owner_id: String,
+    // scope of the owner when the job was enqueued, None means every organization
+    pub organization_ids: Option<Vec<String>>,
+    pub progress: i32,
+    pub attempts: i32,
+    pub cancel_requested: bool,
+    pub error: Option<String>,
+    pub result_file_name: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+}
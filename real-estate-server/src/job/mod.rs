@@ -0,0 +1,3 @@
+pub mod job;
+mod repo;
+pub mod service;
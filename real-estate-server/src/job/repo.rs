@@ -0,0 +1,278 @@
+use chrono::NaiveDateTime;
+use postgres::Row;
+
+use crate::{
+    database::storage::Storage, error::app_error::DynAppError, export::exported_file::ExportedFile,
+};
+
+use super::job::{Job, JobKind, JobStatus};
+
+// every column but the uploaded file and the result, which can be large and are
+// read on their own
+const JOB_COLUMNS: &str = "id, kind, j_status, request, owner_id, organization_ids, progress,
+    attempts, cancel_requested, error, result_file_name, created_at, started_at, finished_at";
+
+#[derive(Clone)]
+pub struct JobRepo {
+    storage: Storage,
+}
+
+impl JobRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn create(&self, job: Job, input: Vec<u8>) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                job
+                    (id, kind, j_status, request, input, owner_id, organization_ids, progress,
+                    attempts, cancel_requested, run_after, created_at)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, 0, 0, false, $8, $8);",
+        );
+
+        self.storage
+            .exec(
+                cmd,
+                &[
+                    &job.id,
+                    &job.kind.value_str().to_string(),
+                    &job.status.value_str().to_string(),
+                    &job.request,
+                    &input,
+                    &job.owner_id,
+                    &job.organization_ids,
+                    &job.created_at,
+                ],
+            )
+            .await
+    }
+
+    pub async fn get(&self, id: String, owner_id: String) -> Result<Option<Job>, DynAppError> {
+        let cmd = format!(
+            "
+            SELECT {}
+            FROM
+                job
+            WHERE
+                id = $1 and owner_id = $2;",
+            JOB_COLUMNS
+        );
+
+        let rows = self.storage.query(cmd, &[&id, &owner_id]).await?;
+
+        Ok(rows.first().map(job_from_row))
+    }
+
+    pub async fn get_input(&self, id: String) -> Result<Vec<u8>, DynAppError> {
+        let cmd = String::from("SELECT input FROM job WHERE id = $1;");
+
+        let rows = self.storage.query(cmd, &[&id]).await?;
+
+        Ok(rows
+            .first()
+            .and_then(|row| row.get::<_, Option<Vec<u8>>>("input"))
+            .unwrap_or_default())
+    }
+
+    pub async fn get_result(
+        &self,
+        id: String,
+        owner_id: String,
+    ) -> Result<Option<ExportedFile>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT result_file_name, result_content_type, result_content
+            FROM
+                job
+            WHERE
+                id = $1 and owner_id = $2 and j_status = 'succeeded';",
+        );
+
+        let rows = self.storage.query(cmd, &[&id, &owner_id]).await?;
+
+        Ok(rows.first().map(|row| ExportedFile {
+            file_name: row.get("result_file_name"),
+            content_type: row.get("result_content_type"),
+            content: row.get("result_content"),
+        }))
+    }
+
+    // takes the oldest job that is due, or one whose worker stopped sending
+    // heartbeats. SKIP LOCKED keeps workers from taking the same job
+    pub async fn claim_next(
+        &self,
+        now: NaiveDateTime,
+        stale_before: NaiveDateTime,
+    ) -> Result<Option<Job>, DynAppError> {
+        let cmd = format!(
+            "
+            UPDATE job
+            SET
+                j_status = 'running', attempts = attempts + 1, progress = 0,
+                started_at = $1, heartbeat_at = $1
+            WHERE id = (
+                SELECT id
+                FROM
+                    job
+                WHERE
+                    (j_status = 'queued' and run_after <= $1)
+                    or (j_status = 'running' and heartbeat_at < $2)
+                ORDER BY
+                    created_at
+                FOR UPDATE SKIP LOCKED
+                LIMIT 1
+            )
+            RETURNING {};",
+            JOB_COLUMNS
+        );
+
+        let rows = self.storage.query(cmd, &[&now, &stale_before]).await?;
+
+        Ok(rows.first().map(job_from_row))
+    }
+
+    // keeps the job claimed, telling whether its owner asked to cancel it
+    pub async fn heartbeat(&self, id: String, now: NaiveDateTime) -> Result<bool, DynAppError> {
+        let cmd = String::from(
+            "UPDATE job
+            SET heartbeat_at = $2
+            WHERE id = $1
+            RETURNING cancel_requested",
+        );
+
+        let rows = self.storage.query(cmd, &[&id, &now]).await?;
+
+        Ok(rows
+            .first()
+            .map(|row| row.get("cancel_requested"))
+            .unwrap_or(true))
+    }
+
+    pub async fn set_progress(&self, id: String, progress: i32) -> Result<u64, DynAppError> {
+        let cmd = String::from("UPDATE job SET progress = $2 WHERE id = $1");
+
+        self.storage.exec(cmd, &[&id, &progress]).await
+    }
+
+    pub async fn succeed(
+        &self,
+        id: String,
+        result: ExportedFile,
+        now: NaiveDateTime,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE job
+            SET
+                j_status = 'succeeded', progress = 100, input = null, error = null,
+                result_file_name = $2, result_content_type = $3, result_content = $4,
+                finished_at = $5
+            WHERE id = $1",
+        );
+
+        self.storage
+            .exec(
+                cmd,
+                &[
+                    &id,
+                    &result.file_name,
+                    &result.content_type,
+                    &result.content,
+                    &now,
+                ],
+            )
+            .await
+    }
+
+    // puts the job back in the queue, to run again at run_after
+    pub async fn retry(
+        &self,
+        id: String,
+        error: String,
+        run_after: NaiveDateTime,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE job
+            SET j_status = 'queued', progress = 0, error = $2, run_after = $3
+            WHERE id = $1",
+        );
+
+        self.storage.exec(cmd, &[&id, &error, &run_after]).await
+    }
+
+    // ends the job as failed or cancelled, dropping the uploaded file
+    pub async fn finish(
+        &self,
+        id: String,
+        status: JobStatus,
+        error: Option<String>,
+        now: NaiveDateTime,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE job
+            SET j_status = $2, error = $3, input = null, finished_at = $4
+            WHERE id = $1",
+        );
+
+        self.storage
+            .exec(cmd, &[&id, &status.value_str().to_string(), &error, &now])
+            .await
+    }
+
+    // a queued job is cancelled right away, a running one when its worker
+    // notices the request on the next heartbeat
+    pub async fn request_cancellation(
+        &self,
+        id: String,
+        owner_id: String,
+        now: NaiveDateTime,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "UPDATE job
+            SET
+                cancel_requested = true,
+                j_status = CASE WHEN j_status = 'queued' THEN 'cancelled' ELSE j_status END,
+                input = CASE WHEN j_status = 'queued' THEN null ELSE input END,
+                finished_at = CASE WHEN j_status = 'queued' THEN $3 ELSE finished_at END
+            WHERE
+                id = $1 and owner_id = $2 and j_status in ('queued', 'running')",
+        );
+
+        self.storage.exec(cmd, &[&id, &owner_id, &now]).await
+    }
+
+    pub async fn delete_finished_before(
+        &self,
+        finished_at: NaiveDateTime,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "DELETE FROM job
+            WHERE finished_at < $1",
+        );
+
+        self.storage.exec(cmd, &[&finished_at]).await
+    }
+}
+
+fn job_from_row(row: &Row) -> Job {
+    let kind: String = row.get("kind");
+    let status: String = row.get("j_status");
+    Job {
+        id: row.get("id"),
+        // only this module writes these columns, the fallbacks never show up
+        kind: JobKind::from_value_str(&kind).unwrap_or(JobKind::SubdivisionExport),
+        status: JobStatus::from_value_str(&status).unwrap_or(JobStatus::Failed),
+        request: row.get("request"),
+        owner_id: row.get("owner_id"),
+        organization_ids: row.get("organization_ids"),
+        progress: row.get("progress"),
+        attempts: row.get("attempts"),
+        cancel_requested: row.get("cancel_requested"),
+        error: row.get("error"),
+        result_file_name: row.get("result_file_name"),
+        created_at: row.get("created_at"),
+        started_at: row.get("started_at"),
+        finished_at: row.get("finished_at"),
+    }
+}
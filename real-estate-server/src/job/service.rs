@@ -0,0 +1,375 @@
+use std::{sync::Arc, time::Duration as StdDuration};
+
+use chrono::{Duration, Utc};
+use tokio::{sync::Notify, time::sleep};
+use uuid::Uuid;
+
+use crate::{
+    api_contracts::{import_report::ImportReport, job_dto::JobDto},
+    auth::user::User,
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+    export::{exported_file::ExportedFile, service::ExportService},
+    import::service::ImportService,
+    organization::tenant_scope::TenantScope,
+    sales_table::{sales_table::file_name, service::SalesTableService},
+};
+
+use super::{
+    job::{Job, JobRequest, JobStatus},
+    repo::JobRepo,
+};
+
+pub const WORKER_COUNT: usize = 4;
+// server errors are retried, everything else is the request's fault and would
+// fail the same way again
+const MAX_ATTEMPTS: i32 = 3;
+const RETRY_DELAY_SECONDS: i64 = 30;
+// idle workers look for jobs this often, enqueueing wakes one up right away
+const POLL_INTERVAL: StdDuration = StdDuration::from_secs(5);
+const HEARTBEAT_INTERVAL: StdDuration = StdDuration::from_secs(2);
+// a running job without heartbeats for this long lost its worker, e.g. the
+// server restarted, and is taken again
+const STALE_AFTER_SECONDS: i64 = 60;
+const RETENTION_DAYS: i64 = 7;
+
+// progress reported at each stage, the services doing the work give no finer
+// measure
+const PROGRESS_STARTED: i32 = 10;
+const PROGRESS_STORING: i32 = 90;
+
+#[derive(Clone)]
+pub struct JobService {
+    repo: JobRepo,
+    import_service: ImportService,
+    export_service: ExportService,
+    sales_table_service: SalesTableService,
+    wake: Arc<Notify>,
+}
+
+impl JobService {
+    pub fn new(
+        storage: Storage,
+        import_service: ImportService,
+        export_service: ExportService,
+        sales_table_service: SalesTableService,
+    ) -> Self {
+        Self {
+            repo: JobRepo::new(storage),
+            import_service,
+            export_service,
+            sales_table_service,
+            wake: Arc::new(Notify::new()),
+        }
+    }
+
+    // the job runs later with the scope the user has now
+    pub async fn enqueue(
+        &self,
+        request: JobRequest,
+        input: Vec<u8>,
+        user: &User,
+    ) -> Result<JobDto, DynAppError> {
+        let now = Utc::now().naive_utc();
+        self.repo
+            .delete_finished_before(now - Duration::days(RETENTION_DAYS))
+            .await?;
+
+        let serialized_request = match serde_json::to_string(&request) {
+            Ok(serialized_request) => serialized_request,
+            Err(err) => return Err(Box::new(ApiError::Internal(err.to_string()))),
+        };
+
+        let job = Job {
+            id: Uuid::new_v4().to_string(),
+            kind: request.kind(),
+            status: JobStatus::Queued,
+            request: serialized_request,
+            owner_id: user.principal_id(),
+            organization_ids: TenantScope::for_user(user).organization_ids,
+            progress: 0,
+            attempts: 0,
+            cancel_requested: false,
+            error: None,
+            result_file_name: None,
+            created_at: now,
+            started_at: None,
+            finished_at: None,
+        };
+
+        self.repo.create(job.clone(), input).await?;
+        self.wake.notify_one();
+        Ok(to_dto(job))
+    }
+
+    pub async fn get(&self, id: String, user: &User) -> Result<JobDto, DynAppError> {
+        Ok(to_dto(self.get_owned(id, user).await?))
+    }
+
+    pub async fn get_result(&self, id: String, user: &User) -> Result<ExportedFile, DynAppError> {
+        let job = self.get_owned(id.clone(), user).await?;
+        if job.status != JobStatus::Succeeded {
+            return Err(Box::new(ApiError::Conflict(format!(
+                "Job {} has no result, it is {}",
+                id,
+                job.status.value_str()
+            ))));
+        }
+
+        match self
+            .repo
+            .get_result(id.clone(), user.principal_id())
+            .await?
+        {
+            Some(result) => Ok(result),
+            None => Err(not_found(&id)),
+        }
+    }
+
+    pub async fn cancel(&self, id: String, user: &User) -> Result<JobDto, DynAppError> {
+        let now = Utc::now().naive_utc();
+        let updated = self
+            .repo
+            .request_cancellation(id.clone(), user.principal_id(), now)
+            .await?;
+
+        let job = self.get_owned(id.clone(), user).await?;
+        if updated == 0 {
+            return Err(Box::new(ApiError::Conflict(format!(
+                "Job {} already finished",
+                id
+            ))));
+        }
+
+        Ok(to_dto(job))
+    }
+
+    pub fn start_workers(&self, count: usize) {
+        for _ in 0..count {
+            let service = self.clone();
+            tokio::spawn(async move { service.work().await });
+        }
+    }
+
+    async fn work(&self) {
+        loop {
+            let now = Utc::now().naive_utc();
+            let stale_before = now - Duration::seconds(STALE_AFTER_SECONDS);
+            match self.repo.claim_next(now, stale_before).await {
+                Ok(Some(job)) => self.run(job).await,
+                Ok(None) => {
+                    tokio::select! {
+                        _ = self.wake.notified() => {}
+                        _ = sleep(POLL_INTERVAL) => {}
+                    }
+                }
+                Err(err) => {
+                    eprintln!("failed to take a job: {}", err.message());
+                    sleep(POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    async fn run(&self, job: Job) {
+        // jobs taken over from a lost worker may have been cancelled or have used
+        // up their attempts meanwhile
+        if job.cancel_requested {
+            self.finish(&job, JobStatus::Cancelled, None).await;
+            return;
+        }
+        if job.attempts > MAX_ATTEMPTS {
+            let error = job
+                .error
+                .clone()
+                .unwrap_or(String::from("The job was interrupted too many times"));
+            self.finish(&job, JobStatus::Failed, Some(error)).await;
+            return;
+        }
+
+        let outcome = tokio::select! {
+            result = self.execute(&job) => Some(result),
+            _ = self.watch(job.id.clone()) => None,
+        };
+
+        let now = Utc::now().naive_utc();
+        let saved = match outcome {
+            // dropping the execution future above is what stops the work
+            None => {
+                self.finish(&job, JobStatus::Cancelled, None).await;
+                return;
+            }
+            Some(Ok(result)) => {
+                let _ = self
+                    .repo
+                    .set_progress(job.id.clone(), PROGRESS_STORING)
+                    .await;
+                self.repo.succeed(job.id.clone(), result, now).await
+            }
+            Some(Err(err)) if err.status_code() >= 500 && job.attempts < MAX_ATTEMPTS => {
+                let run_after = now + Duration::seconds(RETRY_DELAY_SECONDS * job.attempts as i64);
+                self.repo
+                    .retry(job.id.clone(), err.message(), run_after)
+                    .await
+            }
+            Some(Err(err)) => {
+                self.repo
+                    .finish(job.id.clone(), JobStatus::Failed, Some(err.message()), now)
+                    .await
+            }
+        };
+
+        // the job stays running and is taken again once its heartbeat is stale
+        if let Err(err) = saved {
+            eprintln!("failed to save job {}: {}", job.id, err.message());
+        }
+    }
+
+    async fn finish(&self, job: &Job, status: JobStatus, error: Option<String>) {
+        let now = Utc::now().naive_utc();
+        if let Err(err) = self.repo.finish(job.id.clone(), status, error, now).await {
+            eprintln!("failed to save job {}: {}", job.id, err.message());
+        }
+    }
+
+    // returns only when the owner cancels the job, keeping its heartbeat going
+    // until then
+    async fn watch(&self, id: String) {
+        loop {
+            sleep(HEARTBEAT_INTERVAL).await;
+            match self
+                .repo
+                .heartbeat(id.clone(), Utc::now().naive_utc())
+                .await
+            {
+                Ok(true) => return,
+                Ok(false) => {}
+                Err(err) => eprintln!("failed to update job {}: {}", id, err.message()),
+            }
+        }
+    }
+
+    async fn execute(&self, job: &Job) -> Result<ExportedFile, DynAppError> {
+        let request: JobRequest = match serde_json::from_str(&job.request) {
+            Ok(request) => request,
+            Err(err) => {
+                return Err(Box::new(ApiError::BadRequest(format!(
+                    "Unreadable job request: {}",
+                    err
+                ))))
+            }
+        };
+        let input = self.repo.get_input(job.id.clone()).await?;
+        self.repo
+            .set_progress(job.id.clone(), PROGRESS_STARTED)
+            .await?;
+
+        let scope = TenantScope {
+            organization_ids: job.organization_ids.clone(),
+        };
+
+        let report = match request {
+            JobRequest::GeojsonImport { payload } => {
+                self.import_service.import_geojson(payload, &scope).await?
+            }
+            JobRequest::ShapefileImport { params } => {
+                self.import_service
+                    .import_shapefile(params, &input, &scope)
+                    .await?
+            }
+            JobRequest::DxfImport {
+                subdivision_id,
+                params,
+            } => {
+                let content = match String::from_utf8(input) {
+                    Ok(content) => content,
+                    Err(_) => {
+                        return Err(Box::new(ApiError::BadRequest(String::from(
+                            "The DXF file is not valid UTF-8 text",
+                        ))))
+                    }
+                };
+                self.import_service
+                    .import_dxf(subdivision_id, params, content, &scope)
+                    .await?
+            }
+            JobRequest::SpreadsheetImport {
+                subdivision_id,
+                params,
+            } => {
+                self.import_service
+                    .import_spreadsheet(subdivision_id, params, &input, &scope)
+                    .await?
+            }
+            JobRequest::SubdivisionExport {
+                subdivision_id,
+                format,
+            } => {
+                return self
+                    .export_service
+                    .export(subdivision_id, format, &scope)
+                    .await
+            }
+            JobRequest::SalesTable {
+                subdivision_id,
+                available_only,
+            } => {
+                let sales_table = self
+                    .sales_table_service
+                    .generate(subdivision_id.clone(), available_only, &scope)
+                    .await?;
+                return Ok(ExportedFile {
+                    file_name: file_name(&subdivision_id),
+                    content_type: String::from("application/pdf"),
+                    content: sales_table.to_pdf(),
+                });
+            }
+        };
+
+        report_file(report)
+    }
+
+    async fn get_owned(&self, id: String, user: &User) -> Result<Job, DynAppError> {
+        match self.repo.get(id.clone(), user.principal_id()).await? {
+            Some(job) => Ok(job),
+            None => Err(not_found(&id)),
+        }
+    }
+}
+
+// imports end with their report, rejected ones included, the job only fails
+// when the import could not run at all
+fn report_file(report: ImportReport) -> Result<ExportedFile, DynAppError> {
+    match serde_json::to_vec(&report) {
+        Ok(content) => Ok(ExportedFile {
+            file_name: format!("import-{}.json", report.subdivision_id),
+            content_type: String::from("application/json"),
+            content,
+        }),
+        Err(err) => Err(Box::new(ApiError::Internal(err.to_string()))),
+    }
+}
+
+fn not_found(id: &str) -> DynAppError {
+    Box::new(ApiError::NotFound(format!("Job {} not found", id)))
+}
+
+fn to_dto(job: Job) -> JobDto {
+    let result_url = match job.status {
+        JobStatus::Succeeded => Some(format!("/api/real-estate/jobs/{}/result", job.id)),
+        _ => None,
+    };
+
+    JobDto {
+        id: job.id,
+        kind: job.kind,
+        status: job.status,
+        progress: job.progress,
+        attempts: job.attempts,
+        error: job.error,
+        created_at: job.created_at,
+        started_at: job.started_at,
+        finished_at: job.finished_at,
+        result_url,
+    }
+}
@@ -1,8 +1,6 @@
-use postgres_types::ToSql;
-
 use crate::{
     database::storage::Storage,
-    error::{app_error::DynAppError, default::DefaultAppError},
+    error::{api_error::ApiError, app_error::DynAppError},
 };
 
 use super::location::Location;
@@ -14,7 +12,7 @@ pub struct LocationRepo {
 
 impl LocationRepo {
     pub fn new(storage: Storage) -> Self {
-        Self { storage: storage }
+        Self { storage }
     }
 
     pub async fn get_location(&self, id: String) -> Result<Location, DynAppError> {
@@ -30,10 +28,10 @@ impl LocationRepo {
         let rows = self.storage.query(cmd, &[&id]).await?;
 
         if rows.len() != 1 {
-            return Err(Box::new(DefaultAppError {
-                message: Some(format!("Unexpected number of results: {}", rows.len())),
-                status_code: 500,
-            }));
+            return Err(Box::new(ApiError::Internal(format!(
+                "Unexpected number of results: {}",
+                rows.len()
+            ))));
         }
 
         let location = Location {
@@ -61,10 +59,10 @@ impl LocationRepo {
         let rows = self.storage.query(cmd, &[&coords.0, &coords.1]).await?;
 
         if rows.len() != 1 {
-            return Err(Box::new(DefaultAppError {
-                message: Some(format!("Unexpected number of results: {}", rows.len())),
-                status_code: 500,
-            }));
+            return Err(Box::new(ApiError::Internal(format!(
+                "Unexpected number of results: {}",
+                rows.len()
+            ))));
         }
 
         let location = Location {
@@ -90,34 +88,4 @@ impl LocationRepo {
             .exec(cmd, &[&location.id, &location.lat, &location.long])
             .await
     }
-
-    pub async fn save_locations(&self, locations: Box<[Location]>) -> Result<u64, DynAppError> {
-        let amount = locations.len();
-
-        let mut values_statement = String::from("VALUES\n   ");
-        for i in 0..amount {
-            let base = i * 3;
-            values_statement +=
-                format!("(${}, ${}, ${}),\n", base + 1, base + 2, base + 3).as_str();
-        }
-
-        values_statement += ";";
-
-        let cmd = format!(
-            "INSERT INTO
-                app_location 
-                    (id, lat, long)
-                {}",
-            values_statement
-        );
-
-        let mut params: Vec<&(dyn ToSql + Sync)> = vec![];
-        for loc in locations.iter() {
-            params.push(&loc.id);
-            params.push(&loc.lat);
-            params.push(&loc.long);
-        }
-
-        self.storage.exec(cmd, &params).await
-    }
 }
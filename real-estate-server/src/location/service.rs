@@ -1,6 +1,6 @@
 use crate::{
     database::storage::Storage,
-    error::{app_error::DynAppError, default::DefaultAppError},
+    error::{api_error::ApiError, app_error::DynAppError},
 };
 
 use super::{location::Location, repo::LocationRepo};
@@ -34,13 +34,10 @@ impl LocationService {
         if rows_amount == 1 {
             Ok(location)
         } else {
-            Err(Box::new(DefaultAppError {
-                message: Some(format!(
-                    "Unexpected number of rows created: {}",
-                    rows_amount
-                )),
-                status_code: 500,
-            }))
+            Err(Box::new(ApiError::Internal(format!(
+                "Unexpected number of rows created: {}",
+                rows_amount
+            ))))
         }
     }
 
@@ -50,10 +47,10 @@ impl LocationService {
     ) -> Result<Location, DynAppError> {
         match self.get_location_by_coords(coords).await {
             Ok(location) => Ok(location),
-            Err(err) => {
+            Err(_err) => {
                 // need to check the error message
                 let created_location = Location {
-                    id: format!("{}-{}", coords.0.to_string(), coords.1.to_string()),
+                    id: format!("{}-{}", coords.0, coords.1),
                     lat: coords.0,
                     long: coords.1,
                 };
@@ -0,0 +1,300 @@
+use crate::{
+    api_contracts::{block_dto::BlockDto, lot_dto::LotDto, lot_grid_payload::LotGridPayload},
+    block::service::BlockService,
+    error::{api_error::ApiError, app_error::DynAppError},
+    geometry::{
+        crs::Crs,
+        grid::split_into_strips,
+        polygon::{contains_point, validate_polygon},
+    },
+    organization::tenant_scope::TenantScope,
+    subdivision::service::{resolve_crs, SubdivisionService},
+};
+
+// a typo in the frontage should not make the server build thousands of lots
+const MAX_LOTS: usize = 500;
+
+#[derive(Clone)]
+pub struct LotGridService {
+    subdivision_service: SubdivisionService,
+    block_service: BlockService,
+}
+
+impl LotGridService {
+    pub fn new(subdivision_service: SubdivisionService, block_service: BlockService) -> Self {
+        Self {
+            subdivision_service,
+            block_service,
+        }
+    }
+
+    // proposes the lots without saving them. They come in the CRS of the block
+    pub async fn preview(
+        &self,
+        subdivision_id: String,
+        payload: LotGridPayload,
+        scope: &TenantScope,
+    ) -> Result<Vec<LotDto>, DynAppError> {
+        let crs = resolve_crs(payload.crs.clone())?;
+        let lots = self.generate(subdivision_id, &payload, scope).await?;
+
+        if crs == Crs::Wgs84 {
+            return Ok(lots);
+        }
+        Ok(lots
+            .into_iter()
+            .map(|mut lot| {
+                lot.area = Box::new(crs.area_from_wgs84(&lot.area));
+                lot.crs = Some(crs.code());
+                lot
+            })
+            .collect())
+    }
+
+    // generates the same lots the preview shows and saves them, creating the
+    // block with the sketched polygon when the subdivision does not have it yet
+    pub async fn commit(
+        &self,
+        subdivision_id: String,
+        payload: LotGridPayload,
+        scope: &TenantScope,
+    ) -> Result<Vec<LotDto>, DynAppError> {
+        let lots = self
+            .generate(subdivision_id.clone(), &payload, scope)
+            .await?;
+
+        let existing: Vec<String> = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id.clone(), scope)
+            .await?
+            .into_iter()
+            .map(|lot| lot.name)
+            .collect();
+        let taken: Vec<String> = lots
+            .iter()
+            .filter(|lot| existing.contains(&lot.name))
+            .map(|lot| lot.name.clone())
+            .collect();
+        if !taken.is_empty() {
+            return Err(Box::new(ApiError::Conflict(format!(
+                "The subdivision already has the lots {}",
+                taken.join(", ")
+            ))));
+        }
+
+        let blocks = self
+            .block_service
+            .get_blocks(subdivision_id.clone(), scope)
+            .await?;
+        let new_block = if blocks.iter().any(|block| block.name == payload.block_name) {
+            None
+        } else {
+            let block_dto = BlockDto {
+                name: payload.block_name.clone(),
+                area: payload.area.clone(),
+                crs: payload.crs.clone(),
+            };
+            Some(
+                self.block_service
+                    .prepare(subdivision_id.clone(), &block_dto, scope)
+                    .await?,
+            )
+        };
+
+        self.subdivision_service
+            .insert_lots(subdivision_id, &lots, new_block, scope)
+            .await?;
+        Ok(lots)
+    }
+
+    async fn generate(
+        &self,
+        subdivision_id: String,
+        payload: &LotGridPayload,
+        scope: &TenantScope,
+    ) -> Result<Vec<LotDto>, DynAppError> {
+        if payload.frontage.is_nan() || payload.frontage <= 0.0 {
+            return Err(invalid(
+                "frontage",
+                String::from("The frontage must be positive"),
+            ));
+        }
+
+        let block = resolve_crs(payload.crs.clone())?.area_to_wgs84(&payload.area);
+        let errors = validate_polygon(&block);
+        if !errors.is_empty() {
+            return Err(Box::new(ApiError::field_problems(
+                "area",
+                "Invalid block polygon",
+                errors,
+            )));
+        }
+
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+        let outside = block
+            .iter()
+            .filter(|point| !contains_point(&subdivision.area, **point))
+            .count();
+        // vertices on the subdivision boundary may test either way
+        if outside == block.len() {
+            return Err(invalid(
+                "area",
+                String::from("The block is outside of the subdivision"),
+            ));
+        }
+
+        let amount = block.len() as f64;
+        let plane = Crs::utm_containing(
+            block.iter().map(|p| p.0).sum::<f64>() / amount,
+            block.iter().map(|p| p.1).sum::<f64>() / amount,
+        );
+        let strips = match split_into_strips(
+            &plane.area_from_wgs84(&block),
+            payload.frontage,
+            payload.depth_azimuth,
+            MAX_LOTS,
+        ) {
+            Ok(strips) => strips,
+            Err(needed) => {
+                return Err(invalid(
+                    "frontage",
+                    format!(
+                        "The frontage would split the block into {} lots, the limit is {}",
+                        needed, MAX_LOTS
+                    ),
+                ))
+            }
+        };
+
+        let first_number = payload.first_number.unwrap_or(1);
+        Ok(strips
+            .into_iter()
+            .enumerate()
+            .map(|(position, strip)| {
+                let name = format!(
+                    "{}-L{:02}",
+                    payload.block_name,
+                    first_number + position as u32
+                );
+                LotDto {
+                    id: format!("{}-{}", name, subdivision_id),
+                    area: Box::new(plane.area_to_wgs84(&strip)),
+                    name,
+                    subdivision_id: subdivision_id.clone(),
+                    price: None,
+                    block_name: Some(payload.block_name.clone()),
+                    status: None,
+                    crs: None,
+                }
+            })
+            .collect())
+    }
+}
+
+fn invalid(field: &str, message: String) -> DynAppError {
+    Box::new(ApiError::validation(field, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api_contracts::subdivision_dto::SubdivisionDto, database::storage::Storage,
+        location::service::LocationService, tile::tile_cache::TileCache,
+    };
+
+    fn ok<T>(result: Result<T, DynAppError>) -> T {
+        result.unwrap_or_else(|err| panic!("{}", err.message()))
+    }
+
+    // a 40m x 30m block, south-west corner first
+    fn block_area(south: f64, west: f64) -> Vec<(f64, f64)> {
+        let north = south + 30.0 / 110_850.0;
+        let east = west + 40.0 / 102_000.0;
+        vec![(south, west), (south, east), (north, east), (north, west)]
+    }
+
+    #[tokio::test]
+    async fn commits_grids_whose_lots_share_vertices() {
+        let storage = match Storage::for_tests() {
+            Some(storage) => storage,
+            None => {
+                eprintln!("REAL_ESTATE_TEST_DB_HOST is not set, skipping");
+                return;
+            }
+        };
+        let scope = TenantScope::unrestricted();
+        let subdivision_service = SubdivisionService::new(
+            storage.clone(),
+            LocationService::new(storage.clone()),
+            TileCache::new(),
+        );
+        let block_service = BlockService::new(storage.clone(), subdivision_service.clone());
+        let service = LotGridService::new(subdivision_service.clone(), block_service.clone());
+
+        let suffix = uuid::Uuid::new_v4().to_string();
+        let organization_id = format!("grid-test-{}", suffix);
+        ok(storage
+            .exec(
+                String::from("INSERT INTO organization (id, o_name) VALUES ($1, $2);"),
+                &[&organization_id, &organization_id],
+            )
+            .await);
+        // each run gets its own corner of the map, so its vertices are new
+        let base = -23.5 + (suffix.as_bytes()[0] as f64) * 0.001;
+        let subdivision_id = ok(subdivision_service
+            .create(
+                SubdivisionDto {
+                    id: format!("grid-test-{}", suffix),
+                    name: String::from("Grid test"),
+                    area: Box::new(vec![
+                        (base - 0.01, -46.61),
+                        (base - 0.01, -46.59),
+                        (base + 0.01, -46.59),
+                        (base + 0.01, -46.61),
+                    ]),
+                    lots: None,
+                    organization_id: Some(organization_id),
+                    crs: None,
+                },
+                &scope,
+            )
+            .await);
+
+        let grid = |block_name: &str, area: Vec<(f64, f64)>| LotGridPayload {
+            block_name: String::from(block_name),
+            area: Box::new(area),
+            crs: None,
+            frontage: 10.0,
+            depth_azimuth: 0.0,
+            first_number: None,
+        };
+        let first_block = block_area(base, -46.6);
+        let lots = ok(service
+            .commit(
+                subdivision_id.clone(),
+                grid("Q1", first_block.clone()),
+                &scope,
+            )
+            .await);
+        assert_eq!(lots.len(), 4);
+
+        // the second block shares its western side with the first one
+        let second_block = block_area(base, first_block[1].1);
+        ok(service
+            .commit(subdivision_id.clone(), grid("Q2", second_block), &scope)
+            .await);
+
+        let saved = ok(subdivision_service
+            .get_subdivision_lots(subdivision_id.clone(), &scope)
+            .await);
+        assert_eq!(saved.len(), 8);
+        assert!(saved.iter().all(|lot| lot.area.len() >= 4));
+        let blocks = ok(block_service.get_blocks(subdivision_id, &scope).await);
+        let names: Vec<&str> = blocks.iter().map(|block| block.name.as_str()).collect();
+        assert_eq!(names, vec!["Q1", "Q2"]);
+    }
+}
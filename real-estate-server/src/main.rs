@@ -1,39 +1,111 @@
+#![allow(clippy::module_inception)]
+
 use axum::{
-    routing::{get, patch, post},
+    extract::DefaultBodyLimit,
+    middleware::{from_fn_with_state, map_request_with_state},
+    routing::{delete, get, post, put},
     Error, Router,
 };
-use std::{error::Error as StdError, net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc};
 use tokio::net::TcpListener;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
+pub mod analytics;
 pub mod api_contracts;
+pub mod api_key;
 pub mod app_state;
 pub mod auth;
+pub mod block;
+pub mod cli;
+pub mod contract;
 pub mod database;
 pub mod error;
+pub mod export;
+pub mod geometry;
 pub mod handlers;
+pub mod idempotency;
+pub mod import;
+pub mod job;
 pub mod location;
+pub mod lot_grid;
+pub mod memorial;
+pub mod openapi;
+pub mod organization;
+pub mod pdf;
+pub mod plat;
+pub mod sale;
+pub mod sales_table;
+pub mod shapefile;
+pub mod site_feature;
 pub mod subdivision;
+pub mod tile;
 
+use handlers::analytics::analytics_handler;
+use handlers::api_key::{
+    api_key_creation_handler, api_key_listing_handler, api_key_revocation_handler,
+};
+use handlers::auth::{
+    auth_handler, login_handler, logout_handler, subscribe_handler, token_refresh_handler,
+    user_role_update_handler, user_sessions_revocation_handler,
+};
+use handlers::block::{
+    block_creation_handler, block_listing_handler, block_lots_retrieval_handler,
+};
+use handlers::contract::{contract_template_retrieval_handler, contract_template_update_handler};
+use handlers::export::subdivision_export_handler;
+use handlers::idempotency::idempotency_handler;
+use handlers::import::{
+    dxf_import_handler, geojson_import_handler, shapefile_import_handler,
+    spreadsheet_import_handler,
+};
+use handlers::job::{job_cancellation_handler, job_result_handler, job_status_handler};
+use handlers::lot_grid::{lot_grid_creation_handler, lot_grid_preview_handler};
+use handlers::memorial::lot_memorial_handler;
+use handlers::organization::{
+    organization_creation_handler, organization_listing_handler,
+    organization_member_addition_handler, organization_member_removal_handler,
+};
+use handlers::plat::{plat_png_handler, plat_svg_handler};
+use handlers::sale::{sale_contract_retrieval_handler, sale_creation_handler};
+use handlers::sales_table::sales_table_handler;
+use handlers::site_feature::{
+    land_use_breakdown_handler, site_feature_creation_handler, site_feature_removal_handler,
+    site_features_retrieval_handler,
+};
 use handlers::subdivision::{
     lot_creation_handler, lots_creation_handler, subdivision_creation_handler,
     subdivision_listing_handler, subdivision_searching_handler, subdivision_lots_retrieval_handler
 };
+use handlers::tile::tile_handler;
 
-use crate::{app_state::app_state::AppState};
+use crate::{app_state::app_state::AppState, job::service::WORKER_COUNT, openapi::ApiDoc};
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() {
-    start_web_server().await;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        start_web_server().await.unwrap();
+        return;
+    }
+
+    if let Err(err) = cli::run(args).await {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
 }
 
 async fn start_web_server() -> Result<(), Error> {
-    let app_state = Arc::new(AppState::new());
+    let app_state = match AppState::new() {
+        Ok(app_state) => Arc::new(app_state),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+    app_state.job_service.start_workers(WORKER_COUNT);
 
     let app = Router::new()
-        .route(
-            "/api/real-estate/health-check",
-            get(|| async { "Real Estate server is online" }),
-        )
         .route(
             "/api/real-estate/subdivisions",
             post(subdivision_creation_handler),
@@ -46,6 +118,23 @@ async fn start_web_server() -> Result<(), Error> {
             "/api/real-estate/subdivisions/:subdivision_id/lots/batch-creation",
             post(lots_creation_handler),
         )
+        .route(
+            "/api/real-estate/subdivisions/import/geojson",
+            post(geojson_import_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/import/shapefile",
+            post(shapefile_import_handler).layer(DefaultBodyLimit::max(50 * 1024 * 1024)),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/lots/import/dxf",
+            // CAD drawings easily go past the default 2MB limit
+            post(dxf_import_handler).layer(DefaultBodyLimit::max(50 * 1024 * 1024)),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/lots/import",
+            post(spreadsheet_import_handler).layer(DefaultBodyLimit::max(50 * 1024 * 1024)),
+        )
         .route(
             "/api/real-estate/subdivisions/search",
             get(subdivision_searching_handler),
@@ -58,7 +147,119 @@ async fn start_web_server() -> Result<(), Error> {
             "/api/real-estate/subdivisions/:subdivision_id/lots",
             get(subdivision_lots_retrieval_handler),
         )
-        // .route_layer(map_request_with_state(app_state.clone(), auth_handler))
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/blocks",
+            post(block_creation_handler).get(block_listing_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/blocks/:block_name/lots",
+            get(block_lots_retrieval_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/features",
+            post(site_feature_creation_handler).get(site_features_retrieval_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/features/:feature_id",
+            delete(site_feature_removal_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/land-use",
+            get(land_use_breakdown_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/plat.svg",
+            get(plat_svg_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/plat.png",
+            get(plat_png_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/sales-table",
+            get(sales_table_handler),
+        )
+        .route("/api/real-estate/tiles/:z/:x/:tile", get(tile_handler))
+        .route("/api/real-estate/analytics", get(analytics_handler))
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/lot-grid/preview",
+            post(lot_grid_preview_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/lot-grid",
+            post(lot_grid_creation_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/lots/:lot_name/memorial",
+            get(lot_memorial_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/export",
+            get(subdivision_export_handler),
+        )
+        .route("/api/real-estate/jobs/:job_id", get(job_status_handler))
+        .route(
+            "/api/real-estate/jobs/:job_id/result",
+            get(job_result_handler),
+        )
+        .route(
+            "/api/real-estate/jobs/:job_id/cancel",
+            post(job_cancellation_handler),
+        )
+        .route(
+            "/api/real-estate/subdivisions/:subdivision_id/contract-template",
+            get(contract_template_retrieval_handler).put(contract_template_update_handler),
+        )
+        .route("/api/real-estate/sales", post(sale_creation_handler))
+        .route(
+            "/api/real-estate/sales/:sale_id/contract",
+            get(sale_contract_retrieval_handler),
+        )
+        .route(
+            "/api/real-estate/users/:user_id/sessions",
+            delete(user_sessions_revocation_handler),
+        )
+        .route(
+            "/api/real-estate/users/:user_id/role",
+            put(user_role_update_handler),
+        )
+        .route(
+            "/api/real-estate/organizations",
+            post(organization_creation_handler).get(organization_listing_handler),
+        )
+        .route(
+            "/api/real-estate/organizations/:organization_id/members",
+            post(organization_member_addition_handler),
+        )
+        .route(
+            "/api/real-estate/organizations/:organization_id/members/:user_id",
+            delete(organization_member_removal_handler),
+        )
+        .route(
+            "/api/real-estate/organizations/:organization_id/api-keys",
+            post(api_key_creation_handler).get(api_key_listing_handler),
+        )
+        .route(
+            "/api/real-estate/organizations/:organization_id/api-keys/:api_key_id",
+            delete(api_key_revocation_handler),
+        )
+        // runs after authentication, idempotency keys belong to the authenticated
+        // user. The public routes below hand out credentials, which are never stored
+        .route_layer(from_fn_with_state(app_state.clone(), idempotency_handler))
+        // only the routes above this layer require authentication
+        .route_layer(map_request_with_state(app_state.clone(), auth_handler))
+        .route(
+            "/api/real-estate/health-check",
+            get(|| async { "Real Estate server is online" }),
+        )
+        .route("/api/real-estate/login", post(login_handler))
+        .route("/api/real-estate/subscribe", post(subscribe_handler))
+        .route("/api/real-estate/token/refresh", post(token_refresh_handler))
+        .route("/api/real-estate/logout", post(logout_handler))
+        .merge(
+            SwaggerUi::new("/api/real-estate/docs")
+                .url("/api/real-estate/openapi.json", ApiDoc::openapi()),
+        )
         .with_state(app_state);
 
     let addr = SocketAddr::from(([192, 168, 0, 9], 5000));
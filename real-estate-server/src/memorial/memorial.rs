@@ -0,0 +1,372 @@
+use crate::{geometry::crs::Crs, pdf::number_format::format_decimal};
+
+// two edges are taken as shared when both ends of one lie this close to the
+// line of the other and they overlap for at least this length, in meters
+const ADJACENCY_TOLERANCE_M: f64 = 0.1;
+
+// a polygon the lot may border, described the way the memorial names it, such
+// as "o Lote Q1-L02"
+pub struct Confrontant {
+    pub description: String,
+    // (lat, long) pairs, open ring
+    pub area: Vec<(f64, f64)>,
+}
+
+pub struct MemorialVertex {
+    pub name: String,
+    pub easting: f64,
+    pub northing: f64,
+}
+
+pub struct MemorialSegment {
+    // azimuth from the north, clockwise, in degrees
+    pub azimuth: f64,
+    pub distance: f64,
+    pub confrontants: Vec<String>,
+}
+
+// The metes-and-bounds description registries ask for. Vertices are named V1,
+// V2... clockwise from the northernmost one, segment i goes from vertex i to the
+// next, and coordinates are UTM on the zone holding the lot.
+pub struct Memorial {
+    pub subdivision_name: String,
+    pub lot_name: String,
+    pub crs: Crs,
+    pub area: f64,
+    pub perimeter: f64,
+    pub vertices: Vec<MemorialVertex>,
+    pub segments: Vec<MemorialSegment>,
+}
+
+impl Memorial {
+    pub fn new(
+        subdivision_name: String,
+        lot_name: String,
+        lot_area: &[(f64, f64)],
+        confrontants: &[Confrontant],
+    ) -> Memorial {
+        let amount = lot_area.len().max(1) as f64;
+        let crs = Crs::utm_containing(
+            lot_area.iter().map(|p| p.0).sum::<f64>() / amount,
+            lot_area.iter().map(|p| p.1).sum::<f64>() / amount,
+        );
+
+        let mut points: Vec<(f64, f64)> = crs.area_from_wgs84(lot_area);
+        if signed_area(&points) > 0.0 {
+            points.reverse();
+        }
+        if let Some(start) = northernmost(&points) {
+            points.rotate_left(start);
+        }
+
+        let projected: Vec<(String, Vec<(f64, f64)>)> = confrontants
+            .iter()
+            .map(|confrontant| {
+                (
+                    confrontant.description.clone(),
+                    crs.area_from_wgs84(&confrontant.area),
+                )
+            })
+            .collect();
+
+        let mut segments: Vec<MemorialSegment> = vec![];
+        for i in 0..points.len() {
+            let from = points[i];
+            let to = points[(i + 1) % points.len()];
+
+            let mut confronting: Vec<String> = vec![];
+            for (description, area) in projected.iter() {
+                let borders = (0..area.len())
+                    .any(|j| edges_overlap((from, to), (area[j], area[(j + 1) % area.len()])));
+                if borders && !confronting.contains(description) {
+                    confronting.push(description.clone());
+                }
+            }
+
+            segments.push(MemorialSegment {
+                azimuth: azimuth(from, to),
+                distance: distance(from, to),
+                confrontants: confronting,
+            });
+        }
+
+        Memorial {
+            subdivision_name,
+            lot_name,
+            crs,
+            // on the UTM plane, like the distances
+            area: signed_area(&points).abs(),
+            perimeter: segments.iter().map(|segment| segment.distance).sum(),
+            vertices: points
+                .iter()
+                .enumerate()
+                .map(|(i, (easting, northing))| MemorialVertex {
+                    name: format!("V{}", i + 1),
+                    easting: *easting,
+                    northing: *northing,
+                })
+                .collect(),
+            segments,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        format!("MEMORIAL DESCRITIVO - LOTE {}", self.lot_name)
+    }
+
+    // written in Portuguese, the language of the registries that receive it
+    pub fn body(&self) -> String {
+        let mut body = format!(
+            "Loteamento: {}\nLote: {}\nÁrea: {} m²\nPerímetro: {} m\nSistema de coordenadas: {} ({})\n\n",
+            self.subdivision_name,
+            self.lot_name,
+            format_decimal(self.area, 2),
+            format_decimal(self.perimeter, 2),
+            self.crs.name(),
+            self.crs.code()
+        );
+
+        if self.vertices.is_empty() {
+            return body;
+        }
+
+        body += &format!(
+            "Inicia-se a descrição deste perímetro no vértice {}",
+            self.describe_vertex(0)
+        );
+        for (i, segment) in self.segments.iter().enumerate() {
+            let confrontants = if segment.confrontants.is_empty() {
+                String::from("área não identificada")
+            } else {
+                join_names(&segment.confrontants)
+            };
+
+            body += &format!(
+                "; deste, segue confrontando com {}, com azimute de {} e distância de {} m, até o vértice ",
+                confrontants,
+                format_dms(segment.azimuth),
+                format_decimal(segment.distance, 2)
+            );
+
+            let next = (i + 1) % self.vertices.len();
+            if next == 0 {
+                body += &format!(
+                    "{}, ponto inicial da descrição deste perímetro.",
+                    self.vertices[0].name
+                );
+            } else {
+                body += &self.describe_vertex(next);
+            }
+        }
+
+        body
+    }
+
+    pub fn to_text(&self) -> String {
+        format!("{}\n\n{}\n", self.title(), self.body())
+    }
+
+    fn describe_vertex(&self, position: usize) -> String {
+        let vertex = &self.vertices[position];
+        format!(
+            "{}, de coordenadas N {} m e E {} m",
+            vertex.name,
+            format_decimal(vertex.northing, 2),
+            format_decimal(vertex.easting, 2)
+        )
+    }
+}
+
+fn signed_area(points: &[(f64, f64)]) -> f64 {
+    let mut sum = 0.0;
+    for i in 0..points.len() {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % points.len()];
+        sum += x1 * y2 - x2 * y1;
+    }
+    sum / 2.0
+}
+
+// position of the northernmost vertex, the westernmost among ties
+fn northernmost(points: &[(f64, f64)]) -> Option<usize> {
+    (0..points.len()).max_by(|a, b| {
+        let (east_a, north_a) = points[*a];
+        let (east_b, north_b) = points[*b];
+        north_a.total_cmp(&north_b).then(east_b.total_cmp(&east_a))
+    })
+}
+
+fn azimuth(from: (f64, f64), to: (f64, f64)) -> f64 {
+    (to.0 - from.0)
+        .atan2(to.1 - from.1)
+        .to_degrees()
+        .rem_euclid(360.0)
+}
+
+fn distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+    (to.0 - from.0).hypot(to.1 - from.1)
+}
+
+fn edges_overlap(edge: ((f64, f64), (f64, f64)), other: ((f64, f64), (f64, f64))) -> bool {
+    let length = distance(edge.0, edge.1);
+    if length < ADJACENCY_TOLERANCE_M {
+        return false;
+    }
+
+    // unit vector along the edge and the normal to it
+    let direction = (
+        (edge.1 .0 - edge.0 .0) / length,
+        (edge.1 .1 - edge.0 .1) / length,
+    );
+    let offset = |point: (f64, f64)| {
+        let relative = (point.0 - edge.0 .0, point.1 - edge.0 .1);
+        (
+            relative.0 * direction.0 + relative.1 * direction.1,
+            (relative.0 * direction.1 - relative.1 * direction.0).abs(),
+        )
+    };
+
+    let (along_start, away_start) = offset(other.0);
+    let (along_end, away_end) = offset(other.1);
+    if away_start > ADJACENCY_TOLERANCE_M || away_end > ADJACENCY_TOLERANCE_M {
+        return false;
+    }
+
+    let overlap = along_start.max(along_end).min(length) - along_start.min(along_end).max(0.0);
+    overlap >= ADJACENCY_TOLERANCE_M
+}
+
+fn join_names(names: &[String]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.clone(),
+        [rest @ .., last] => format!("{} e {}", rest.join(", "), last),
+    }
+}
+
+// degrees, minutes and seconds, as in 90°00'00"
+fn format_dms(degrees: f64) -> String {
+    let mut total_seconds = (degrees * 3600.0).round() as i64;
+    total_seconds = total_seconds.rem_euclid(360 * 3600);
+    format!(
+        "{}°{:02}'{:02}\"",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 20 m square aligned to the UTM grid, counter-clockwise from the
+    // south-west corner, in (easting, northing)
+    const SQUARE: [(f64, f64); 4] = [
+        (330000.0, 7400000.0),
+        (330020.0, 7400000.0),
+        (330020.0, 7400020.0),
+        (330000.0, 7400020.0),
+    ];
+
+    fn shifted(easting: f64) -> Vec<(f64, f64)> {
+        SQUARE.iter().map(|(e, n)| (e + easting, *n)).collect()
+    }
+
+    #[test]
+    fn formats_azimuths_in_degrees_minutes_and_seconds() {
+        assert_eq!(format_dms(0.0), "0°00'00\"");
+        assert_eq!(format_dms(90.0), "90°00'00\"");
+        assert_eq!(format_dms(45.5125), "45°30'45\"");
+        // rounded to the second, carrying into the minutes and degrees
+        assert_eq!(format_dms(12.999_9), "13°00'00\"");
+        assert_eq!(format_dms(359.999_9), "0°00'00\"");
+        assert_eq!(format_dms(-90.0), "270°00'00\"");
+    }
+
+    #[test]
+    fn measures_azimuths_clockwise_from_the_north() {
+        let origin = (0.0, 0.0);
+        assert_eq!(azimuth(origin, (0.0, 10.0)), 0.0);
+        assert_eq!(azimuth(origin, (10.0, 0.0)), 90.0);
+        assert_eq!(azimuth(origin, (0.0, -10.0)), 180.0);
+        assert_eq!(azimuth(origin, (-10.0, 0.0)), 270.0);
+        assert!((azimuth(origin, (10.0, 10.0)) - 45.0).abs() < 1e-9);
+
+        assert_eq!(distance(origin, (3.0, 4.0)), 5.0);
+    }
+
+    #[test]
+    fn detects_shared_edges() {
+        let edge = ((0.0, 0.0), (0.0, 20.0));
+
+        // the same edge walked the other way, as a neighbour holds it
+        assert!(edges_overlap(edge, ((0.0, 20.0), (0.0, 0.0))));
+        // only part of it, a few centimeters off
+        assert!(edges_overlap(edge, ((0.05, 5.0), (0.05, 30.0))));
+        // parallel but too far
+        assert!(!edges_overlap(edge, ((1.0, 0.0), (1.0, 20.0))));
+        // on the same line without overlapping
+        assert!(!edges_overlap(edge, ((0.0, 20.0), (0.0, 40.0))));
+        // touching at a corner only
+        assert!(!edges_overlap(edge, ((0.0, 20.0), (20.0, 20.0))));
+    }
+
+    #[test]
+    fn describes_a_square_clockwise_from_the_north_west_corner() {
+        let crs = Crs::utm_containing(-23.5, -46.6);
+        let lot = crs.area_to_wgs84(&SQUARE);
+        let east = Confrontant {
+            description: String::from("o Lote Q1-L02"),
+            area: crs.area_to_wgs84(&shifted(20.0)),
+        };
+        let apart = Confrontant {
+            description: String::from("o Lote Q1-L09"),
+            area: crs.area_to_wgs84(&shifted(40.0)),
+        };
+
+        let memorial = Memorial::new(
+            String::from("Jardim"),
+            String::from("Q1-L01"),
+            &lot,
+            &[east, apart],
+        );
+
+        let first = &memorial.vertices[0];
+        assert!((first.easting - 330000.0).abs() < 0.01);
+        assert!((first.northing - 7400020.0).abs() < 0.01);
+
+        let azimuths: Vec<String> = memorial
+            .segments
+            .iter()
+            .map(|segment| format_dms(segment.azimuth))
+            .collect();
+        assert_eq!(
+            azimuths,
+            vec!["90°00'00\"", "180°00'00\"", "270°00'00\"", "0°00'00\""]
+        );
+        for segment in memorial.segments.iter() {
+            assert!((segment.distance - 20.0).abs() < 0.01);
+        }
+        assert!((memorial.area - 400.0).abs() < 0.1);
+        assert!((memorial.perimeter - 80.0).abs() < 0.01);
+
+        let confrontants: Vec<&[String]> = memorial
+            .segments
+            .iter()
+            .map(|segment| segment.confrontants.as_slice())
+            .collect();
+        assert_eq!(
+            confrontants,
+            vec![
+                &[][..],
+                &[String::from("o Lote Q1-L02")][..],
+                &[][..],
+                &[][..]
+            ]
+        );
+        assert!(memorial
+            .body()
+            .contains("segue confrontando com o Lote Q1-L02, com azimute de 180°00'00\""));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod memorial;
+pub mod memorial_format;
+pub mod service;
@@ -0,0 +1,61 @@
+use crate::{
+    error::app_error::DynAppError, organization::tenant_scope::TenantScope,
+    subdivision::service::SubdivisionService,
+};
+
+use super::memorial::{Confrontant, Memorial};
+
+#[derive(Clone)]
+pub struct MemorialService {
+    subdivision_service: SubdivisionService,
+}
+
+impl MemorialService {
+    pub fn new(subdivision_service: SubdivisionService) -> Self {
+        Self {
+            subdivision_service,
+        }
+    }
+
+    // the lot confronts the other lots of the subdivision and, on the edges it
+    // shares with the boundary, whatever lies outside of the subdivision
+    pub async fn generate(
+        &self,
+        subdivision_id: String,
+        lot_name: String,
+        scope: &TenantScope,
+    ) -> Result<Memorial, DynAppError> {
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+        let lot = self
+            .subdivision_service
+            .get_lot(subdivision_id.clone(), lot_name.clone(), scope)
+            .await?;
+        let lots = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id, scope)
+            .await?;
+
+        let mut confrontants: Vec<Confrontant> = lots
+            .into_iter()
+            .filter(|other| other.name != lot.name)
+            .map(|other| Confrontant {
+                description: format!("o Lote {}", other.name),
+                area: *other.area,
+            })
+            .collect();
+        confrontants.push(Confrontant {
+            description: format!("a divisa do loteamento {}", subdivision.name),
+            area: *subdivision.area,
+        });
+
+        Ok(Memorial::new(
+            subdivision.name,
+            lot.name,
+            &lot.area,
+            &confrontants,
+        ))
+    }
+}
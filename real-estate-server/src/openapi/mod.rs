@@ -0,0 +1,226 @@
+use utoipa::{
+    openapi::{
+        path::{Operation, ParameterBuilder, ParameterIn},
+        security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+        ContentBuilder, Ref, RefOr, ResponseBuilder, Type,
+    },
+    Modify, OpenApi, ToSchema,
+};
+
+use crate::{
+    api_contracts::problem_details::ProblemDetails, export::export_format::ExportFormat,
+    geometry::utm::Hemisphere, handlers, memorial::memorial_format::MemorialFormat,
+};
+
+// where the committed copy of the document lives, clients are generated from it
+pub const SPEC_FILE: &str = "openapi.json";
+
+// body of the routes sending or receiving files, such as PDFs, images and
+// uploads, only described in the document
+#[allow(dead_code)]
+#[derive(ToSchema)]
+#[schema(value_type = String, format = Binary)]
+pub struct BinaryFile(Vec<u8>);
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Real Estate API",
+        description = "Subdivisions, lots and sales of real estate developers"
+    ),
+    paths(
+        handlers::auth::login_handler,
+        handlers::auth::subscribe_handler,
+        handlers::auth::token_refresh_handler,
+        handlers::auth::logout_handler,
+        handlers::auth::user_sessions_revocation_handler,
+        handlers::auth::user_role_update_handler,
+        handlers::organization::organization_creation_handler,
+        handlers::organization::organization_listing_handler,
+        handlers::organization::organization_member_addition_handler,
+        handlers::organization::organization_member_removal_handler,
+        handlers::api_key::api_key_creation_handler,
+        handlers::api_key::api_key_listing_handler,
+        handlers::api_key::api_key_revocation_handler,
+        handlers::subdivision::subdivision_creation_handler,
+        handlers::subdivision::subdivision_listing_handler,
+        handlers::subdivision::subdivision_searching_handler,
+        handlers::subdivision::lot_creation_handler,
+        handlers::subdivision::lots_creation_handler,
+        handlers::subdivision::subdivision_lots_retrieval_handler,
+        handlers::lot_grid::lot_grid_preview_handler,
+        handlers::lot_grid::lot_grid_creation_handler,
+        handlers::memorial::lot_memorial_handler,
+        handlers::block::block_creation_handler,
+        handlers::block::block_listing_handler,
+        handlers::block::block_lots_retrieval_handler,
+        handlers::site_feature::site_feature_creation_handler,
+        handlers::site_feature::site_features_retrieval_handler,
+        handlers::site_feature::site_feature_removal_handler,
+        handlers::site_feature::land_use_breakdown_handler,
+        handlers::import::geojson_import_handler,
+        handlers::import::shapefile_import_handler,
+        handlers::import::dxf_import_handler,
+        handlers::import::spreadsheet_import_handler,
+        handlers::export::subdivision_export_handler,
+        handlers::sales_table::sales_table_handler,
+        handlers::plat::plat_svg_handler,
+        handlers::plat::plat_png_handler,
+        handlers::tile::tile_handler,
+        handlers::job::job_status_handler,
+        handlers::job::job_result_handler,
+        handlers::job::job_cancellation_handler,
+        handlers::contract::contract_template_retrieval_handler,
+        handlers::contract::contract_template_update_handler,
+        handlers::sale::sale_creation_handler,
+        handlers::sale::sale_contract_retrieval_handler,
+        handlers::analytics::analytics_handler,
+    ),
+    // the query parameter enums are only referenced, utoipa leaves them out
+    components(schemas(ProblemDetails, ExportFormat, Hemisphere, MemorialFormat)),
+    modifiers(&Unlicensed, &SecuritySchemes, &ProblemResponses, &IdempotencyKeys),
+    security(("bearer" = []), ("api_key" = [])),
+    tags(
+        (name = "auth", description = "Logins and sessions"),
+        (name = "users"),
+        (name = "organizations"),
+        (name = "api-keys", description = "Credentials of the integrations of an organization"),
+        (name = "subdivisions"),
+        (name = "lots"),
+        (name = "blocks"),
+        (name = "site-features", description = "Streets, green areas and the other non-lot areas"),
+        (name = "imports"),
+        (name = "exports"),
+        (name = "maps", description = "Plats and vector tiles"),
+        (name = "jobs", description = "Imports and exports running in the background"),
+        (name = "contracts"),
+        (name = "sales"),
+        (name = "analytics"),
+    )
+)]
+pub struct ApiDoc;
+
+pub fn spec_json() -> String {
+    ApiDoc::openapi()
+        .to_pretty_json()
+        .expect("the OpenAPI document should serialize")
+}
+
+// utoipa fills the license from Cargo.toml, which has none
+struct Unlicensed;
+
+impl Modify for Unlicensed {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi.info.license = None;
+    }
+}
+
+// people send "Authorization: Bearer <jwt>", integrations "Authorization: ApiKey <key>"
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "Authorization",
+                "ApiKey <key>",
+            ))),
+        );
+    }
+}
+
+// every route answers its errors with a problem details body
+struct ProblemResponses;
+
+impl Modify for ProblemResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let problem = ResponseBuilder::new()
+            .description("The request failed, see the status and `code`")
+            .content(
+                "application/problem+json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("ProblemDetails")))
+                    .build(),
+            )
+            .build();
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .responses
+            .insert(String::from("Problem"), RefOr::T(problem));
+
+        for path_item in openapi.paths.paths.values_mut() {
+            let operations = [
+                path_item.get.as_mut(),
+                path_item.post.as_mut(),
+                path_item.put.as_mut(),
+                path_item.delete.as_mut(),
+            ];
+            for operation in operations.into_iter().flatten() {
+                operation.responses.responses.insert(
+                    String::from("default"),
+                    RefOr::Ref(Ref::from_response_name("Problem")),
+                );
+            }
+        }
+    }
+}
+
+// authenticated POST routes replay their first response when the key repeats
+struct IdempotencyKeys;
+
+impl Modify for IdempotencyKeys {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for path_item in openapi.paths.paths.values_mut() {
+            if let Some(operation) = path_item.post.as_mut() {
+                // public routes opt out of the global security requirement
+                if operation.security.is_none() {
+                    add_idempotency_key(operation);
+                }
+            }
+        }
+    }
+}
+
+fn add_idempotency_key(operation: &mut Operation) {
+    let parameter = ParameterBuilder::new()
+        .name("Idempotency-Key")
+        .parameter_in(ParameterIn::Header)
+        .description(Some(
+            "Retries with the same key get the first response back instead of repeating the request",
+        ))
+        .schema(Some(utoipa::openapi::ObjectBuilder::new().schema_type(Type::String)))
+        .build();
+    operation
+        .parameters
+        .get_or_insert_with(Vec::new)
+        .push(parameter);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn committed_spec_is_up_to_date() {
+        let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), SPEC_FILE);
+        let committed = std::fs::read_to_string(&path).unwrap_or_default();
+
+        assert!(
+            committed.trim_end() == spec_json(),
+            "{} is out of date, regenerate it with `cargo run -- openapi`",
+            path
+        );
+    }
+}
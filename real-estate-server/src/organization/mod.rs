@@ -0,0 +1,4 @@
+pub mod organization;
+mod repo;
+pub mod service;
+pub mod tenant_scope;
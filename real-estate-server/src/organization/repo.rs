@@ -0,0 +1,86 @@
+use crate::{database::storage::Storage, error::app_error::DynAppError};
+
+use super::organization::Organization;
+
+#[derive(Clone)]
+pub struct OrganizationRepo {
+    storage: Storage,
+}
+
+impl OrganizationRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn create(&self, organization: Organization) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                organization
+                    (id, o_name)
+                VALUES
+                    ($1, $2);",
+        );
+
+        self.storage
+            .exec(cmd, &[&organization.id, &organization.name])
+            .await
+    }
+
+    pub async fn add_member(
+        &self,
+        organization_id: String,
+        user_id: String,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "INSERT INTO
+                organization_member
+                    (organization_id, user_id)
+                VALUES
+                    ($1, $2)
+                on conflict do nothing;",
+        );
+
+        self.storage.exec(cmd, &[&organization_id, &user_id]).await
+    }
+
+    pub async fn remove_member(
+        &self,
+        organization_id: String,
+        user_id: String,
+    ) -> Result<u64, DynAppError> {
+        let cmd = String::from(
+            "DELETE FROM
+                organization_member
+            WHERE organization_id = $1 and user_id = $2",
+        );
+
+        self.storage.exec(cmd, &[&organization_id, &user_id]).await
+    }
+
+    // None lists every organization
+    pub async fn get_all(
+        &self,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Organization>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT *
+            FROM
+                organization
+            WHERE
+                $1::varchar[] is null or id = ANY($1)
+            ORDER BY
+                o_name;",
+        );
+
+        let rows = self.storage.query(cmd, &[&organization_ids]).await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| Organization {
+                id: row.get("id"),
+                name: row.get("o_name"),
+            })
+            .collect())
+    }
+}
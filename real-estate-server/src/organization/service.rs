@@ -0,0 +1,63 @@
+use uuid::Uuid;
+
+use crate::{
+    api_contracts::organization_dto::OrganizationDto,
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+};
+
+use super::{organization::Organization, repo::OrganizationRepo, tenant_scope::TenantScope};
+
+#[derive(Clone)]
+pub struct OrganizationService {
+    repo: OrganizationRepo,
+}
+
+impl OrganizationService {
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            repo: OrganizationRepo::new(storage),
+        }
+    }
+
+    pub async fn create(&self, organization_dto: OrganizationDto) -> Result<String, DynAppError> {
+        if organization_dto.name.trim().is_empty() {
+            return Err(Box::new(ApiError::validation(
+                "name",
+                String::from("The organization name is required"),
+            )));
+        }
+
+        let organization = Organization {
+            id: organization_dto
+                .id
+                .unwrap_or_else(|| Uuid::new_v4().to_string()),
+            name: organization_dto.name,
+        };
+
+        self.repo.create(organization.clone()).await?;
+        Ok(organization.id)
+    }
+
+    pub async fn add_member(
+        &self,
+        organization_id: String,
+        user_id: String,
+    ) -> Result<(), DynAppError> {
+        self.repo.add_member(organization_id, user_id).await?;
+        Ok(())
+    }
+
+    pub async fn remove_member(
+        &self,
+        organization_id: String,
+        user_id: String,
+    ) -> Result<(), DynAppError> {
+        self.repo.remove_member(organization_id, user_id).await?;
+        Ok(())
+    }
+
+    pub async fn get_all(&self, scope: &TenantScope) -> Result<Vec<Organization>, DynAppError> {
+        self.repo.get_all(scope.organization_ids.clone()).await
+    }
+}
@@ -0,0 +1,37 @@
+use crate::auth::{role::Role, user::User};
+
+// the organizations whose data a request may read or change. Admins operate the
+// server and are not restricted, everyone else only reaches the organizations
+// they are a member of.
+#[derive(Clone)]
+pub struct TenantScope {
+    // None means every organization
+    pub organization_ids: Option<Vec<String>>,
+}
+
+impl TenantScope {
+    // for work the server does on its own behalf, after the request that
+    // triggered it was already checked against its own scope
+    pub fn unrestricted() -> Self {
+        Self {
+            organization_ids: None,
+        }
+    }
+
+    pub fn for_user(user: &User) -> Self {
+        if user.role == Role::Admin {
+            return Self::unrestricted();
+        }
+
+        Self {
+            organization_ids: Some(user.organization_ids.clone()),
+        }
+    }
+
+    pub fn includes(&self, organization_id: &str) -> bool {
+        match &self.organization_ids {
+            Some(ids) => ids.iter().any(|id| id == organization_id),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+// A minimal PDF writer. It only knows the two standard Helvetica fonts, text and
+// straight lines, which is all the server's reports and contracts need, and it
+// avoids pulling a full PDF toolkit into the build.
+
+pub const A4_WIDTH: f64 = 595.0;
+pub const A4_HEIGHT: f64 = 842.0;
+
+#[derive(Clone, Copy)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(&self) -> &'static str {
+        match *self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Page {
+    content: String,
+}
+
+impl Page {
+    // x and y are in points, measured from the bottom left corner of the page
+    pub fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        self.content += format!(
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font.resource_name(),
+            size,
+            x,
+            y,
+            escape_text(text)
+        )
+        .as_str();
+    }
+
+    pub fn line(&mut self, from: (f64, f64), to: (f64, f64), width: f64) {
+        self.content += format!(
+            "{:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n",
+            width, from.0, from.1, to.0, to.1
+        )
+        .as_str();
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct PdfDocument {
+    pages: Vec<Page>,
+}
+
+impl PdfDocument {
+    pub fn new() -> Self {
+        Self { pages: vec![] }
+    }
+
+    pub fn add_page(&mut self) -> &mut Page {
+        self.pages.push(Page::default());
+        self.pages.last_mut().unwrap()
+    }
+
+    pub fn last_page(&mut self) -> &mut Page {
+        if self.pages.is_empty() {
+            return self.add_page();
+        }
+        self.pages.last_mut().unwrap()
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // object ids: 1 catalog, 2 page tree, 3 and 4 fonts, then a page and its
+        // content stream for every page
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 5 + i * 2).collect();
+
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids
+                    .iter()
+                    .map(|id| format!("{} 0 R", id))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                self.pages.len()
+            )
+            .into_bytes(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        ];
+
+        for (page, page_id) in self.pages.iter().zip(page_ids.iter()) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                    /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    A4_WIDTH,
+                    A4_HEIGHT,
+                    page_id + 1
+                )
+                .into_bytes(),
+            );
+
+            let stream = encode_win_ansi(&page.content);
+            let mut content_object = format!("<< /Length {} >>\nstream\n", stream.len()).into_bytes();
+            content_object.extend(stream);
+            content_object.extend(b"\nendstream");
+            objects.push(content_object);
+        }
+
+        let mut output: Vec<u8> = b"%PDF-1.4\n".to_vec();
+        let mut offsets: Vec<usize> = vec![];
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output.extend(format!("{} 0 obj\n", index + 1).into_bytes());
+            output.extend(object);
+            output.extend(b"\nendobj\n");
+        }
+
+        let xref_offset = output.len();
+        output.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+        for offset in offsets {
+            output.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        output.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .into_bytes(),
+        );
+
+        output
+    }
+}
+
+// Helvetica's average glyph is roughly half an em wide, good enough to wrap text
+// and right align numbers without shipping the font metrics.
+pub fn approximate_text_width(text: &str, size: f64) -> f64 {
+    text.chars().count() as f64 * size * 0.5
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' | '\r' | '\t' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Latin-1 maps one to one onto WinAnsiEncoding, which covers Portuguese. The few
+// typographic characters outside of it are mapped by hand.
+fn encode_win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            c if (c as u32) < 0x100 => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    fn two_page_document() -> Vec<u8> {
+        let mut document = PdfDocument::new();
+        document
+            .add_page()
+            .text(56.0, 786.0, 14.0, Font::Bold, "Tabela (resumo)");
+        document
+            .last_page()
+            .line((56.0, 770.0), (539.0, 770.0), 0.5);
+        document
+            .add_page()
+            .text(56.0, 786.0, 11.0, Font::Regular, "Área: 360,00 m²");
+        document.to_bytes()
+    }
+
+    #[test]
+    fn writes_the_header_trailer_and_page_tree() {
+        let pdf = two_page_document();
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        // catalog, page tree, two fonts and a page and a content per page
+        assert!(text.contains("trailer\n<< /Size 9 /Root 1 0 R >>"));
+        assert!(text.contains("<< /Type /Pages /Kids [5 0 R 7 0 R] /Count 2 >>"));
+    }
+
+    #[test]
+    fn xref_points_to_every_object() {
+        let pdf = two_page_document();
+        let text = String::from_utf8_lossy(&pdf);
+
+        let startxref: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|rest| rest.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .unwrap();
+        assert!(pdf[startxref..].starts_with(b"xref\n0 9\n0000000000 65535 f \n"));
+
+        let entries: Vec<usize> = String::from_utf8_lossy(&pdf[startxref..])
+            .lines()
+            .skip(3)
+            .take(8)
+            .map(|entry| {
+                // 20 bytes with the line break
+                assert_eq!(entry.len(), 19);
+                assert!(entry.ends_with(" 00000 n "));
+                entry[..10].parse().unwrap()
+            })
+            .collect();
+        for (index, offset) in entries.iter().enumerate() {
+            let header = format!("{} 0 obj\n", index + 1);
+            assert!(pdf[*offset..].starts_with(header.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn content_streams_have_their_length() {
+        let pdf = two_page_document();
+
+        let mut rest = &pdf[..];
+        let mut streams = 0;
+        while let Some(start) = find(rest, b"<< /Length ") {
+            let after = &rest[start + 11..];
+            let digits = find(after, b" >>").unwrap();
+            let length: usize = String::from_utf8_lossy(&after[..digits]).parse().unwrap();
+            let content = &after[digits + b" >>\nstream\n".len()..];
+            assert!(content[length..].starts_with(b"\nendstream"));
+            rest = &content[length..];
+            streams += 1;
+        }
+        assert_eq!(streams, 2);
+    }
+
+    #[test]
+    fn escapes_text_and_encodes_it_as_win_ansi() {
+        let pdf = two_page_document();
+
+        assert!(find(&pdf, b"(Tabela \\(resumo\\)) Tj").is_some());
+        // Á is 0xC1 and ² is 0xB2 in WinAnsiEncoding
+        assert!(find(&pdf, b"(\xC1rea: 360,00 m\xB2) Tj").is_some());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod document;
+pub mod number_format;
+pub mod text_layout;
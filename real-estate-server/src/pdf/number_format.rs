@@ -0,0 +1,52 @@
+// Brazilian notation, 7.394.647,52
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (integer, fraction) = match formatted.split_once('.') {
+        Some((integer, fraction)) => (integer.to_string(), Some(fraction.to_string())),
+        None => (formatted.clone(), None),
+    };
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push('.');
+        }
+        grouped.push(digit);
+    }
+
+    let sign = if value < 0.0 && formatted.chars().any(|c| c != '0' && c != '.') {
+        "-"
+    } else {
+        ""
+    };
+    match fraction {
+        Some(fraction) => format!("{}{},{}", sign, grouped, fraction),
+        None => format!("{}{}", sign, grouped),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_thousands_with_dots_and_decimals_with_a_comma() {
+        assert_eq!(format_decimal(7_394_647.52, 2), "7.394.647,52");
+        assert_eq!(format_decimal(1_000.0, 2), "1.000,00");
+        assert_eq!(format_decimal(999.5, 2), "999,50");
+        assert_eq!(format_decimal(0.0, 2), "0,00");
+    }
+
+    #[test]
+    fn rounds_to_the_requested_decimals() {
+        assert_eq!(format_decimal(1_234_567.891, 0), "1.234.568");
+        assert_eq!(format_decimal(12.3456, 3), "12,346");
+        assert_eq!(format_decimal(999.999, 2), "1.000,00");
+    }
+
+    #[test]
+    fn keeps_the_sign_only_of_non_zero_values() {
+        assert_eq!(format_decimal(-1_234.5, 2), "-1.234,50");
+        assert_eq!(format_decimal(-0.001, 2), "0,00");
+    }
+}
@@ -0,0 +1,70 @@
+use super::document::{approximate_text_width, Font, Page, PdfDocument, A4_HEIGHT, A4_WIDTH};
+
+const MARGIN: f64 = 56.0;
+const TITLE_SIZE: f64 = 14.0;
+const BODY_SIZE: f64 = 11.0;
+const LINE_HEIGHT: f64 = 15.0;
+
+// Lays a title and a plain text body out on as many A4 pages as needed. Blank
+// lines in the body are kept as paragraph breaks.
+pub fn render_text_document(title: &str, body: &str) -> Vec<u8> {
+    let mut document = PdfDocument::new();
+    let max_width = A4_WIDTH - 2.0 * MARGIN;
+
+    let mut lines: Vec<String> = vec![];
+    for paragraph in body.lines() {
+        if paragraph.trim().is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        lines.extend(wrap_line(paragraph, max_width, BODY_SIZE));
+    }
+
+    let mut y = start_page(document.add_page(), title);
+    for line in lines {
+        if y < MARGIN {
+            document.add_page();
+            y = A4_HEIGHT - MARGIN;
+        }
+
+        document
+            .last_page()
+            .text(MARGIN, y, BODY_SIZE, Font::Regular, &line);
+        y -= LINE_HEIGHT;
+    }
+
+    document.to_bytes()
+}
+
+pub fn wrap_line(text: &str, max_width: f64, size: f64) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+
+        if approximate_text_width(&candidate, size) > max_width && !current.is_empty() {
+            lines.push(current);
+            current = word.to_string();
+        } else {
+            current = candidate;
+        }
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+fn start_page(page: &mut Page, title: &str) -> f64 {
+    let title_width = approximate_text_width(title, TITLE_SIZE);
+    let x = ((A4_WIDTH - title_width) / 2.0).max(MARGIN);
+    page.text(x, A4_HEIGHT - MARGIN, TITLE_SIZE, Font::Bold, title);
+    A4_HEIGHT - MARGIN - 2.0 * LINE_HEIGHT
+}
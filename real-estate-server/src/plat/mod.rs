@@ -0,0 +1,3 @@
+pub mod plat;
+pub mod raster;
+pub mod service;
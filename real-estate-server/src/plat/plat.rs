@@ -0,0 +1,370 @@
+use std::fmt::Write;
+
+use crate::{
+    export::xml::escape,
+    geometry::crs::Crs,
+    site_feature::site_feature::{GeometryKind, LandUse},
+    subdivision::lot::LotStatus,
+};
+
+pub const DEFAULT_WIDTH: u32 = 1200;
+pub const MAX_WIDTH: u32 = 4000;
+
+const MARGIN: f64 = 40.0;
+const HEADER: f64 = 72.0;
+const FOOTER: f64 = 80.0;
+const FONT: &str = "DejaVu Sans, Arial, sans-serif";
+
+pub struct PlatLot {
+    pub name: String,
+    pub area: Vec<(f64, f64)>,
+    pub status: LotStatus,
+}
+
+pub struct PlatBlock {
+    pub area: Vec<(f64, f64)>,
+}
+
+pub struct PlatFeature {
+    pub land_use: LandUse,
+    pub geometry_kind: GeometryKind,
+    pub coordinates: Vec<(f64, f64)>,
+    pub width: Option<f64>,
+}
+
+// everything drawn on the plat map, coordinates as (lat, long) in WGS84
+pub struct PlatMap {
+    pub subdivision_name: String,
+    pub boundary: Vec<(f64, f64)>,
+    pub blocks: Vec<PlatBlock>,
+    pub lots: Vec<PlatLot>,
+    pub features: Vec<PlatFeature>,
+    pub selected_lot: Option<String>,
+}
+
+// maps UTM meters to pixels, north up
+struct Viewport {
+    crs: Crs,
+    min_e: f64,
+    max_n: f64,
+    scale: f64,
+}
+
+impl Viewport {
+    fn to_px(&self, point: (f64, f64)) -> (f64, f64) {
+        let (e, n) = self.crs.from_wgs84(point);
+        (
+            MARGIN + (e - self.min_e) * self.scale,
+            HEADER + (self.max_n - n) * self.scale,
+        )
+    }
+
+    fn points(&self, area: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        area.iter().map(|point| self.to_px(*point)).collect()
+    }
+}
+
+impl PlatMap {
+    // the map is drawn in the UTM zone of the subdivision, so the scale bar
+    // holds everywhere on it and north is straight up
+    pub fn to_svg(&self, width: u32) -> String {
+        let width = width.clamp(400, MAX_WIDTH) as f64;
+        let (lat, long) = centroid(&self.boundary);
+        let crs = Crs::utm_containing(lat, long);
+
+        let projected: Vec<(f64, f64)> = self
+            .boundary
+            .iter()
+            .chain(self.lots.iter().flat_map(|lot| lot.area.iter()))
+            .map(|point| crs.from_wgs84(*point))
+            .collect();
+        let min_e = projected.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+        let max_e = projected
+            .iter()
+            .map(|p| p.0)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let min_n = projected.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+        let max_n = projected
+            .iter()
+            .map(|p| p.1)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let span_e = (max_e - min_e).max(1.0);
+        let span_n = (max_n - min_n).max(1.0);
+        let scale = (width - 2.0 * MARGIN) / span_e;
+        let height = (HEADER + span_n * scale + FOOTER).round();
+        let viewport = Viewport {
+            crs,
+            min_e,
+            max_n,
+            scale,
+        };
+
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"{font}\">",
+            w = width,
+            h = height,
+            font = FONT,
+        );
+        let _ = write!(
+            svg,
+            "<rect width=\"{}\" height=\"{}\" fill=\"#ffffff\"/>",
+            width, height
+        );
+        let _ = write!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" font-size=\"22\" font-weight=\"bold\" fill=\"#212121\">{}</text>",
+            MARGIN,
+            HEADER / 2.0 + 8.0,
+            escape(&self.subdivision_name)
+        );
+
+        for feature in self.features.iter() {
+            draw_feature(&mut svg, &viewport, feature);
+        }
+
+        for lot in self.lots.iter() {
+            if self.selected_lot.as_ref() != Some(&lot.name) {
+                draw_lot(&mut svg, &viewport, lot, false);
+            }
+        }
+
+        for block in self.blocks.iter().filter(|block| block.area.len() >= 3) {
+            let _ = write!(
+                svg,
+                "<polygon points=\"{}\" fill=\"none\" stroke=\"#616161\" stroke-width=\"1.5\" stroke-dasharray=\"6 4\"/>",
+                points_attr(&viewport.points(&block.area))
+            );
+        }
+
+        // drawn last so its outline is not covered by the neighbours
+        if let Some(lot) = self
+            .lots
+            .iter()
+            .find(|lot| self.selected_lot.as_ref() == Some(&lot.name))
+        {
+            draw_lot(&mut svg, &viewport, lot, true);
+        }
+
+        let _ = write!(
+            svg,
+            "<polygon points=\"{}\" fill=\"none\" stroke=\"#212121\" stroke-width=\"2.5\"/>",
+            points_attr(&viewport.points(&self.boundary))
+        );
+
+        draw_north_arrow(&mut svg, width - MARGIN - 15.0, HEADER / 2.0 - 18.0);
+        draw_scale_bar(&mut svg, scale, MARGIN, height - FOOTER / 2.0);
+        draw_legend(
+            &mut svg,
+            width - MARGIN - 330.0,
+            height - FOOTER / 2.0,
+            self.selected_lot.is_some(),
+        );
+
+        svg.push_str("</svg>");
+        svg
+    }
+}
+
+fn draw_lot(svg: &mut String, viewport: &Viewport, lot: &PlatLot, selected: bool) {
+    let points = viewport.points(&lot.area);
+    let (fill, stroke, stroke_width) = if selected {
+        ("#ffd54f", "#d84315", 3.0)
+    } else {
+        (status_color(lot.status), "#424242", 0.8)
+    };
+    let _ = write!(
+        svg,
+        "<polygon points=\"{}\" fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\"/>",
+        points_attr(&points),
+        fill,
+        stroke,
+        stroke_width
+    );
+
+    // the label has to fit inside the lot, tiny lots go without one
+    let min_x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let max_x = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    let min_y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let max_y = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+    let by_width = (max_x - min_x) * 0.9 / (lot.name.chars().count().max(1) as f64 * 0.6);
+    let font_size = by_width.min((max_y - min_y) * 0.5).min(14.0);
+    if font_size < 5.0 {
+        return;
+    }
+
+    let (x, y) = centroid(&points);
+    let _ = write!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#212121\">{}</text>",
+        x,
+        y,
+        font_size,
+        escape(&lot.name)
+    );
+}
+
+fn draw_feature(svg: &mut String, viewport: &Viewport, feature: &PlatFeature) {
+    let points = viewport.points(&feature.coordinates);
+    let color = land_use_color(feature.land_use);
+    match feature.geometry_kind {
+        GeometryKind::Polygon => {
+            let _ = write!(
+                svg,
+                "<polygon points=\"{}\" fill=\"{}\" stroke=\"#9e9e9e\" stroke-width=\"0.8\"/>",
+                points_attr(&points),
+                color
+            );
+        }
+        GeometryKind::LineString => {
+            let stroke_width = (feature.width.unwrap_or(1.0) * viewport.scale).max(1.0);
+            let _ = write!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{:.1}\" stroke-linecap=\"butt\"/>",
+                points_attr(&points),
+                color,
+                stroke_width
+            );
+        }
+    }
+}
+
+fn draw_north_arrow(svg: &mut String, x: f64, y: f64) {
+    let _ = write!(
+        svg,
+        "<polygon points=\"{x:.1},{top:.1} {right:.1},{bottom:.1} {x:.1},{middle:.1} {left:.1},{bottom:.1}\" fill=\"#212121\"/>",
+        x = x,
+        top = y,
+        right = x + 10.0,
+        left = x - 10.0,
+        bottom = y + 30.0,
+        middle = y + 22.0
+    );
+    let _ = write!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"14\" font-weight=\"bold\" text-anchor=\"middle\" fill=\"#212121\">N</text>",
+        x,
+        y + 46.0
+    );
+}
+
+// a round length taking about a fifth of the map width
+fn draw_scale_bar(svg: &mut String, scale: f64, x: f64, y: f64) {
+    let target = 200.0 / scale;
+    let magnitude = 10f64.powf(target.log10().floor());
+    let length = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|step| step * magnitude)
+        .take_while(|length| *length <= target)
+        .last()
+        .unwrap_or(magnitude);
+    let bar_width = length * scale;
+
+    for segment in 0..4 {
+        let fill = if segment % 2 == 0 {
+            "#212121"
+        } else {
+            "#ffffff"
+        };
+        let _ = write!(
+            svg,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"6\" fill=\"{}\" stroke=\"#212121\" stroke-width=\"1\"/>",
+            x + bar_width / 4.0 * segment as f64,
+            y,
+            bar_width / 4.0,
+            fill
+        );
+    }
+    let _ = write!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\" text-anchor=\"middle\" fill=\"#212121\">0</text>",
+        x,
+        y + 22.0
+    );
+    let _ = write!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\" text-anchor=\"middle\" fill=\"#212121\">{} m</text>",
+        x + bar_width,
+        y + 22.0,
+        length
+    );
+}
+
+fn draw_legend(svg: &mut String, x: f64, y: f64, with_selected: bool) {
+    let mut entries = vec![
+        (status_color(LotStatus::Available), "Disponível"),
+        (status_color(LotStatus::Sold), "Vendido"),
+    ];
+    if with_selected {
+        entries.push(("#ffd54f", "Selecionado"));
+    }
+
+    for (pos, (color, label)) in entries.iter().enumerate() {
+        let entry_x = x + pos as f64 * 110.0;
+        let _ = write!(
+            svg,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"14\" height=\"14\" fill=\"{}\" stroke=\"#424242\" stroke-width=\"0.8\"/>",
+            entry_x,
+            y - 4.0,
+            color
+        );
+        let _ = write!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"12\" fill=\"#212121\">{}</text>",
+            entry_x + 20.0,
+            y + 8.0,
+            label
+        );
+    }
+}
+
+fn status_color(status: LotStatus) -> &'static str {
+    match status {
+        LotStatus::Available => "#a5d6a7",
+        LotStatus::Sold => "#ef9a9a",
+    }
+}
+
+fn land_use_color(land_use: LandUse) -> &'static str {
+    match land_use {
+        LandUse::Street => "#e0e0e0",
+        LandUse::GreenArea => "#c5e1a5",
+        LandUse::Institutional => "#b3e5fc",
+        LandUse::Preservation => "#81c784",
+    }
+}
+
+fn points_attr(points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{:.1},{:.1}", x, y))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// area weighted, falls back to the mean of the vertices for degenerate rings
+fn centroid(points: &[(f64, f64)]) -> (f64, f64) {
+    let mut area = 0.0;
+    let mut cx = 0.0;
+    let mut cy = 0.0;
+    for i in 0..points.len() {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % points.len()];
+        let cross = x1 * y2 - x2 * y1;
+        area += cross;
+        cx += (x1 + x2) * cross;
+        cy += (y1 + y2) * cross;
+    }
+
+    if area.abs() < 1e-12 {
+        let count = points.len().max(1) as f64;
+        return (
+            points.iter().map(|p| p.0).sum::<f64>() / count,
+            points.iter().map(|p| p.1).sum::<f64>() / count,
+        );
+    }
+
+    (cx / (3.0 * area), cy / (3.0 * area))
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use resvg::{tiny_skia, usvg};
+
+use crate::error::{api_error::ApiError, app_error::DynAppError};
+
+// the fonts installed on the server, loaded once since scanning them is slow
+pub fn load_fonts() -> Arc<usvg::fontdb::Database> {
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_system_fonts();
+    Arc::new(fontdb)
+}
+
+pub fn render_png(svg: &str, fontdb: Arc<usvg::fontdb::Database>) -> Result<Vec<u8>, DynAppError> {
+    let options = usvg::Options {
+        fontdb,
+        ..usvg::Options::default()
+    };
+
+    let tree = match usvg::Tree::from_str(svg, &options) {
+        Ok(tree) => tree,
+        Err(err) => return Err(render_error(err.to_string())),
+    };
+
+    let size = tree.size().to_int_size();
+    let mut pixmap = match tiny_skia::Pixmap::new(size.width(), size.height()) {
+        Some(pixmap) => pixmap,
+        None => return Err(render_error(String::from("empty image"))),
+    };
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    match pixmap.encode_png() {
+        Ok(png) => Ok(png),
+        Err(err) => Err(render_error(err.to_string())),
+    }
+}
+
+fn render_error(reason: String) -> DynAppError {
+    Box::new(ApiError::Internal(format!(
+        "Could not render the plat map: {}",
+        reason
+    )))
+}
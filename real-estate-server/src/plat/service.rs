@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use resvg::usvg::fontdb;
+
+use crate::{
+    block::service::BlockService,
+    error::{api_error::ApiError, app_error::DynAppError},
+    organization::tenant_scope::TenantScope,
+    site_feature::service::SiteFeatureService,
+    subdivision::{lot::LotStatus, service::SubdivisionService},
+};
+
+use super::{
+    plat::{PlatBlock, PlatFeature, PlatLot, PlatMap, DEFAULT_WIDTH},
+    raster::{load_fonts, render_png},
+};
+
+#[derive(Clone)]
+pub struct PlatService {
+    subdivision_service: SubdivisionService,
+    block_service: BlockService,
+    site_feature_service: SiteFeatureService,
+    fontdb: Arc<fontdb::Database>,
+}
+
+impl PlatService {
+    pub fn new(
+        subdivision_service: SubdivisionService,
+        block_service: BlockService,
+        site_feature_service: SiteFeatureService,
+    ) -> Self {
+        Self {
+            subdivision_service,
+            block_service,
+            site_feature_service,
+            fontdb: load_fonts(),
+        }
+    }
+
+    pub async fn render_svg(
+        &self,
+        subdivision_id: String,
+        selected_lot: Option<String>,
+        width: Option<u32>,
+        scope: &TenantScope,
+    ) -> Result<String, DynAppError> {
+        let plat = self.build(subdivision_id, selected_lot, scope).await?;
+        Ok(plat.to_svg(width.unwrap_or(DEFAULT_WIDTH)))
+    }
+
+    pub async fn render_png(
+        &self,
+        subdivision_id: String,
+        selected_lot: Option<String>,
+        width: Option<u32>,
+        scope: &TenantScope,
+    ) -> Result<Vec<u8>, DynAppError> {
+        let svg = self
+            .render_svg(subdivision_id, selected_lot, width, scope)
+            .await?;
+        render_png(&svg, self.fontdb.clone())
+    }
+
+    async fn build(
+        &self,
+        subdivision_id: String,
+        selected_lot: Option<String>,
+        scope: &TenantScope,
+    ) -> Result<PlatMap, DynAppError> {
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+        let lots = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id.clone(), scope)
+            .await?;
+        let blocks = self
+            .block_service
+            .get_blocks(subdivision_id.clone(), scope)
+            .await?;
+        let features = self
+            .site_feature_service
+            .get_features(subdivision_id.clone(), scope)
+            .await?;
+
+        if let Some(lot_name) = &selected_lot {
+            if !lots.iter().any(|lot| &lot.name == lot_name) {
+                return Err(Box::new(ApiError::NotFound(format!(
+                    "Lot {} not found in subdivision {}",
+                    lot_name, subdivision_id
+                ))));
+            }
+        }
+
+        Ok(PlatMap {
+            subdivision_name: subdivision.name,
+            boundary: *subdivision.area,
+            blocks: blocks
+                .into_iter()
+                .map(|block| PlatBlock { area: *block.area })
+                .collect(),
+            lots: lots
+                .into_iter()
+                .map(|lot| PlatLot {
+                    name: lot.name,
+                    area: *lot.area,
+                    status: lot.status.unwrap_or(LotStatus::Available),
+                })
+                .collect(),
+            features: features
+                .into_iter()
+                .map(|feature| PlatFeature {
+                    land_use: feature.land_use,
+                    geometry_kind: feature.geometry_kind,
+                    coordinates: *feature.coordinates,
+                    width: feature.width,
+                })
+                .collect(),
+            selected_lot,
+        })
+    }
+}
@@ -0,0 +1,3 @@
+mod repo;
+pub mod sale;
+pub mod service;
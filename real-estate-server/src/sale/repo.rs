@@ -0,0 +1,111 @@
+use crate::{
+    database::storage::{SqlParam, Storage},
+    error::{api_error::ApiError, app_error::DynAppError},
+};
+
+use super::sale::{Installment, Sale};
+
+#[derive(Clone)]
+pub struct SaleRepo {
+    storage: Storage,
+}
+
+impl SaleRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn create(&self, sale: Sale) -> Result<u64, DynAppError> {
+        let mut statements: Vec<(String, Vec<SqlParam>)> = vec![(
+            String::from(
+                "INSERT INTO
+                    sale
+                        (id, subdivision_id, l_name, buyer_name, buyer_document, price, sold_at)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7);",
+            ),
+            vec![
+                Box::new(sale.id.clone()),
+                Box::new(sale.subdivision_id),
+                Box::new(sale.lot_name),
+                Box::new(sale.buyer_name),
+                Box::new(sale.buyer_document),
+                Box::new(sale.price),
+                Box::new(sale.sold_at),
+            ],
+        )];
+
+        for installment in sale.installments.into_iter() {
+            statements.push((
+                String::from(
+                    "INSERT INTO
+                        sale_installment
+                            (sale_id, i_number, due_date, amount)
+                    VALUES
+                        ($1, $2, $3, $4);",
+                ),
+                vec![
+                    Box::new(sale.id.clone()),
+                    Box::new(installment.number),
+                    Box::new(installment.due_date),
+                    Box::new(installment.amount),
+                ],
+            ));
+        }
+
+        self.storage.transaction(statements).await
+    }
+
+    pub async fn get(&self, id: String) -> Result<Sale, DynAppError> {
+        let sale_cmd = String::from(
+            "
+            SELECT *
+            FROM
+                sale
+            WHERE
+                id = $1;",
+        );
+
+        let rows = self.storage.query(sale_cmd, &[&id]).await?;
+
+        if rows.len() != 1 {
+            return Err(Box::new(ApiError::NotFound(format!(
+                "Sale {} not found",
+                id
+            ))));
+        }
+
+        let installments_cmd = String::from(
+            "
+            SELECT *
+            FROM
+                sale_installment
+            WHERE
+                sale_id = $1
+            ORDER BY
+                i_number;",
+        );
+
+        let installment_rows = self.storage.query(installments_cmd, &[&id]).await?;
+
+        let mut installments: Vec<Installment> = vec![];
+        for row in installment_rows.iter() {
+            installments.push(Installment {
+                number: row.get("i_number"),
+                due_date: row.get("due_date"),
+                amount: row.get("amount"),
+            });
+        }
+
+        Ok(Sale {
+            id: rows[0].get("id"),
+            subdivision_id: rows[0].get("subdivision_id"),
+            lot_name: rows[0].get("l_name"),
+            buyer_name: rows[0].get("buyer_name"),
+            buyer_document: rows[0].get("buyer_document"),
+            price: rows[0].get("price"),
+            installments,
+            sold_at: rows[0].get("sold_at"),
+        })
+    }
+}
@@ -0,0 +1,20 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+#[derive(Clone)]
+pub struct Installment {
+    pub number: i32,
+    pub due_date: NaiveDate,
+    pub amount: f64,
+}
+
+#[derive(Clone)]
+pub struct Sale {
+    pub id: String,
+    pub subdivision_id: String,
+    pub lot_name: String,
+    pub buyer_name: String,
+    pub buyer_document: String,
+    pub price: f64,
+    pub installments: Vec<Installment>,
+    pub sold_at: NaiveDateTime,
+}
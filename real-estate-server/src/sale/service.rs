@@ -0,0 +1,166 @@
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    api_contracts::sale_dto::{InstallmentDto, SaleDto},
+    contract::service::ContractService,
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+    organization::tenant_scope::TenantScope,
+    subdivision::service::SubdivisionService,
+};
+
+use super::{
+    repo::SaleRepo,
+    sale::{Installment, Sale},
+};
+
+#[derive(Clone)]
+pub struct SaleService {
+    repo: SaleRepo,
+    subdivision_service: SubdivisionService,
+    contract_service: ContractService,
+}
+
+impl SaleService {
+    pub fn new(
+        storage: Storage,
+        subdivision_service: SubdivisionService,
+        contract_service: ContractService,
+    ) -> Self {
+        Self {
+            repo: SaleRepo::new(storage),
+            subdivision_service,
+            contract_service,
+        }
+    }
+
+    // records the sale of a lot. The contract is generated on its first
+    // download, so a template that fails to render does not keep the sale from
+    // being recorded
+    pub async fn create(
+        &self,
+        sale_dto: SaleDto,
+        scope: &TenantScope,
+    ) -> Result<String, DynAppError> {
+        if sale_dto.price <= 0.0 {
+            return Err(Box::new(ApiError::validation(
+                "price",
+                String::from("The sale price must be greater than zero"),
+            )));
+        }
+
+        check_installments(sale_dto.price, &sale_dto.installments)?;
+
+        // fails with 404 when the lot does not exist
+        self.subdivision_service
+            .get_lot(
+                sale_dto.subdivision_id.clone(),
+                sale_dto.lot_name.clone(),
+                scope,
+            )
+            .await?;
+
+        let sale = Sale {
+            id: sale_dto.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            subdivision_id: sale_dto.subdivision_id,
+            lot_name: sale_dto.lot_name,
+            buyer_name: sale_dto.buyer_name,
+            buyer_document: sale_dto.buyer_document,
+            price: sale_dto.price,
+            installments: sale_dto
+                .installments
+                .into_iter()
+                .enumerate()
+                .map(|(pos, installment)| Installment {
+                    number: pos as i32 + 1,
+                    due_date: installment.due_date,
+                    amount: installment.amount,
+                })
+                .collect(),
+            sold_at: Utc::now().naive_utc(),
+        };
+
+        self.repo.create(sale.clone()).await?;
+        self.subdivision_service.invalidate_tiles(&sale.subdivision_id);
+
+        Ok(sale.id)
+    }
+
+    pub async fn get_contract(
+        &self,
+        sale_id: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<u8>, DynAppError> {
+        let sale = self.repo.get(sale_id.clone()).await?;
+        if self
+            .subdivision_service
+            .check_access(sale.subdivision_id.clone(), scope)
+            .await
+            .is_err()
+        {
+            return Err(Box::new(ApiError::NotFound(format!("Sale {} not found", sale_id))));
+        }
+
+        if let Some(contract) = self.contract_service.get_contract(sale_id).await? {
+            return Ok(contract);
+        }
+
+        // generated once, later downloads get the stored one
+        self.contract_service.generate(&sale).await
+    }
+}
+
+// a sale without installments is paid at once, otherwise they have to add up to
+// the price, to the cent
+fn check_installments(price: f64, installments: &[InstallmentDto]) -> Result<(), DynAppError> {
+    if installments.is_empty() {
+        return Ok(());
+    }
+
+    let total: f64 = installments
+        .iter()
+        .map(|installment| installment.amount)
+        .sum();
+    if (total - price).abs() >= 0.005 {
+        return Err(Box::new(ApiError::validation(
+            "installments",
+            format!(
+                "The installments add up to {:.2}, the sale price is {:.2}",
+                total, price
+            ),
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn installments(amounts: &[f64]) -> Vec<InstallmentDto> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(pos, amount)| InstallmentDto {
+                due_date: NaiveDate::from_ymd_opt(2025, pos as u32 + 1, 10).unwrap(),
+                amount: *amount,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn installments_must_add_up_to_the_price() {
+        assert!(check_installments(90_000.0, &[]).is_ok());
+        assert!(check_installments(90_000.0, &installments(&[30_000.0; 3])).is_ok());
+        // a third of the price does not round to the cent
+        assert!(check_installments(100.0, &installments(&[33.33, 33.33, 33.34])).is_ok());
+
+        let err = check_installments(90_000.0, &installments(&[30_000.0; 2])).unwrap_err();
+        assert_eq!(err.status_code(), 422);
+        assert_eq!(err.field_errors()[0].field, "installments");
+        assert!(check_installments(100.0, &installments(&[33.33; 3])).is_err());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod sales_table;
+pub mod service;
@@ -0,0 +1,311 @@
+use std::cmp::Ordering;
+
+use chrono::NaiveDate;
+
+use crate::{
+    api_contracts::lot_dto::LotDto,
+    geometry::polygon::area_m2,
+    pdf::{
+        document::{approximate_text_width, Font, PdfDocument, A4_HEIGHT, A4_WIDTH},
+        number_format::format_decimal,
+    },
+    subdivision::lot::LotStatus,
+};
+
+const MARGIN: f64 = 48.0;
+const TITLE_SIZE: f64 = 14.0;
+const BODY_SIZE: f64 = 10.0;
+const LINE_HEIGHT: f64 = 15.0;
+
+// right edges of the numeric columns and left edge of the status column
+const AREA_RIGHT: f64 = 320.0;
+const PRICE_RIGHT: f64 = 440.0;
+const STATUS_LEFT: f64 = 470.0;
+
+pub struct SalesTableRow {
+    pub name: String,
+    pub area: f64,
+    pub price: Option<f64>,
+    pub status: LotStatus,
+}
+
+pub struct SalesTableBlock {
+    // None for the lots outside of any block
+    pub name: Option<String>,
+    pub rows: Vec<SalesTableRow>,
+}
+
+// the "tabela de vendas" brokers hand out, lots grouped by block with the
+// totals of each block and of the whole subdivision
+// name the PDF is saved or downloaded with
+pub fn file_name(subdivision_id: &str) -> String {
+    format!("tabela-de-vendas-{}.pdf", subdivision_id)
+}
+
+pub struct SalesTable {
+    pub subdivision_name: String,
+    pub generated_on: NaiveDate,
+    pub available_only: bool,
+    pub blocks: Vec<SalesTableBlock>,
+}
+
+impl SalesTable {
+    pub fn new(
+        subdivision_name: String,
+        lots: Vec<LotDto>,
+        available_only: bool,
+        generated_on: NaiveDate,
+    ) -> Self {
+        let mut blocks: Vec<SalesTableBlock> = vec![];
+        for lot in lots.into_iter() {
+            let status = lot.status.unwrap_or(LotStatus::Available);
+            if available_only && status != LotStatus::Available {
+                continue;
+            }
+
+            let row = SalesTableRow {
+                area: area_m2(&lot.area),
+                name: lot.name,
+                price: lot.price,
+                status,
+            };
+            match blocks.iter_mut().find(|block| block.name == lot.block_name) {
+                Some(block) => block.rows.push(row),
+                None => blocks.push(SalesTableBlock {
+                    name: lot.block_name,
+                    rows: vec![row],
+                }),
+            }
+        }
+
+        // lots without a block go last
+        blocks.sort_by(|a, b| match (&a.name, &b.name) {
+            (Some(a), Some(b)) => natural_cmp(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        for block in blocks.iter_mut() {
+            block.rows.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+        }
+
+        Self {
+            subdivision_name,
+            generated_on,
+            available_only,
+            blocks,
+        }
+    }
+
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut document = PdfDocument::new();
+        let mut y = self.start_page(&mut document);
+
+        for block in self.blocks.iter() {
+            // a block header never ends a page on its own
+            if y < MARGIN + 4.0 * LINE_HEIGHT {
+                y = self.start_page(&mut document);
+            }
+
+            let title = match &block.name {
+                Some(name) => format!("Quadra {}", name),
+                None => String::from("Lotes sem quadra"),
+            };
+            document
+                .last_page()
+                .text(MARGIN, y, BODY_SIZE + 1.0, Font::Bold, &title);
+            y -= LINE_HEIGHT;
+            y = column_headers(&mut document, y);
+
+            for row in block.rows.iter() {
+                if y < MARGIN + LINE_HEIGHT {
+                    y = self.start_page(&mut document);
+                    y = column_headers(&mut document, y);
+                }
+                table_row(
+                    &mut document,
+                    y,
+                    Font::Regular,
+                    [
+                        row.name.clone(),
+                        format_decimal(row.area, 2),
+                        row.price
+                            .map(|price| format_decimal(price, 2))
+                            .unwrap_or_else(|| String::from("-")),
+                        status_label(row.status).to_string(),
+                    ],
+                );
+                y -= LINE_HEIGHT;
+            }
+
+            y = totals_row(
+                &mut document,
+                y,
+                &format!("Total da quadra: {}", lots_label(block.rows.len())),
+                block.rows.iter(),
+            );
+            y -= LINE_HEIGHT;
+        }
+
+        if y < MARGIN + 2.0 * LINE_HEIGHT {
+            y = self.start_page(&mut document);
+        }
+        let lots_amount: usize = self.blocks.iter().map(|block| block.rows.len()).sum();
+        totals_row(
+            &mut document,
+            y,
+            &format!("Total geral: {}", lots_label(lots_amount)),
+            self.blocks.iter().flat_map(|block| block.rows.iter()),
+        );
+
+        document.to_bytes()
+    }
+
+    fn start_page(&self, document: &mut PdfDocument) -> f64 {
+        let page = document.add_page();
+        let title = format!("Tabela de vendas - {}", self.subdivision_name);
+        let title_width = approximate_text_width(&title, TITLE_SIZE);
+        let x = ((A4_WIDTH - title_width) / 2.0).max(MARGIN);
+        page.text(x, A4_HEIGHT - MARGIN, TITLE_SIZE, Font::Bold, &title);
+
+        let mut subtitle = format!("Gerada em {}", self.generated_on.format("%d/%m/%Y"));
+        if self.available_only {
+            subtitle += " - somente lotes disponíveis";
+        }
+        page.text(
+            MARGIN,
+            A4_HEIGHT - MARGIN - 1.5 * LINE_HEIGHT,
+            BODY_SIZE - 1.0,
+            Font::Regular,
+            &subtitle,
+        );
+
+        A4_HEIGHT - MARGIN - 3.5 * LINE_HEIGHT
+    }
+}
+
+fn column_headers(document: &mut PdfDocument, y: f64) -> f64 {
+    table_row(
+        document,
+        y,
+        Font::Bold,
+        [
+            String::from("Lote"),
+            String::from("Área (m²)"),
+            String::from("Preço (R$)"),
+            String::from("Situação"),
+        ],
+    );
+    document
+        .last_page()
+        .line((MARGIN, y - 4.0), (A4_WIDTH - MARGIN, y - 4.0), 0.5);
+    y - LINE_HEIGHT
+}
+
+// the lot count goes in the name column, unpriced lots add nothing to the price
+fn totals_row<'a>(
+    document: &mut PdfDocument,
+    y: f64,
+    label: &str,
+    rows: impl Iterator<Item = &'a SalesTableRow>,
+) -> f64 {
+    let (area, price) = rows.fold((0.0, 0.0), |(area, price), row| {
+        (area + row.area, price + row.price.unwrap_or(0.0))
+    });
+
+    document.last_page().line(
+        (MARGIN, y + LINE_HEIGHT - 4.0),
+        (A4_WIDTH - MARGIN, y + LINE_HEIGHT - 4.0),
+        0.5,
+    );
+    table_row(
+        document,
+        y,
+        Font::Bold,
+        [
+            label.to_string(),
+            format_decimal(area, 2),
+            format_decimal(price, 2),
+            String::new(),
+        ],
+    );
+    y - LINE_HEIGHT
+}
+
+fn table_row(document: &mut PdfDocument, y: f64, font: Font, cells: [String; 4]) {
+    let page = document.last_page();
+    page.text(MARGIN, y, BODY_SIZE, font, &cells[0]);
+    page.text(
+        AREA_RIGHT - approximate_text_width(&cells[1], BODY_SIZE),
+        y,
+        BODY_SIZE,
+        font,
+        &cells[1],
+    );
+    page.text(
+        PRICE_RIGHT - approximate_text_width(&cells[2], BODY_SIZE),
+        y,
+        BODY_SIZE,
+        font,
+        &cells[2],
+    );
+    if !cells[3].is_empty() {
+        page.text(STATUS_LEFT, y, BODY_SIZE, font, &cells[3]);
+    }
+}
+
+fn lots_label(amount: usize) -> String {
+    match amount {
+        1 => String::from("1 lote"),
+        _ => format!("{} lotes", amount),
+    }
+}
+
+fn status_label(status: LotStatus) -> &'static str {
+    match status {
+        LotStatus::Available => "Disponível",
+        LotStatus::Sold => "Vendido",
+    }
+}
+
+// Q2-L10 after Q2-L9, comparing runs of digits by their value
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let mut x_digits = String::new();
+                while let Some(c) = a_chars.next_if(|c| c.is_ascii_digit()) {
+                    x_digits.push(c);
+                }
+                let mut y_digits = String::new();
+                while let Some(c) = b_chars.next_if(|c| c.is_ascii_digit()) {
+                    y_digits.push(c);
+                }
+
+                let x_value = x_digits.trim_start_matches('0');
+                let y_value = y_digits.trim_start_matches('0');
+                let ordering = x_value
+                    .len()
+                    .cmp(&y_value.len())
+                    .then_with(|| x_value.cmp(y_value));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_ascii_lowercase().cmp(&y.to_ascii_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::Utc;
+
+use crate::{
+    error::app_error::DynAppError, organization::tenant_scope::TenantScope,
+    subdivision::service::SubdivisionService,
+};
+
+use super::sales_table::SalesTable;
+
+#[derive(Clone)]
+pub struct SalesTableService {
+    subdivision_service: SubdivisionService,
+}
+
+impl SalesTableService {
+    pub fn new(subdivision_service: SubdivisionService) -> Self {
+        Self {
+            subdivision_service,
+        }
+    }
+
+    pub async fn generate(
+        &self,
+        subdivision_id: String,
+        available_only: bool,
+        scope: &TenantScope,
+    ) -> Result<SalesTable, DynAppError> {
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+        let lots = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id, scope)
+            .await?;
+
+        Ok(SalesTable::new(
+            subdivision.name,
+            lots,
+            available_only,
+            Utc::now().date_naive(),
+        ))
+    }
+}
@@ -0,0 +1,274 @@
+use chrono::{Datelike, Utc};
+
+// Reads and writes the dBase III attribute tables of shapefiles.
+
+const HEADER_TERMINATOR: u8 = 0x0D;
+const FILE_TERMINATOR: u8 = 0x1A;
+const DELETED_RECORD: u8 = b'*';
+
+#[derive(Clone)]
+pub struct DbfField {
+    // up to 10 characters
+    pub name: String,
+    // C for text, N for numbers
+    pub kind: char,
+    pub length: u8,
+    pub decimals: u8,
+}
+
+#[derive(Clone, Default)]
+pub struct DbfTable {
+    pub fields: Vec<DbfField>,
+    // values trimmed of their padding, in the order of the fields
+    pub records: Vec<Vec<String>>,
+}
+
+impl DbfTable {
+    // field names are matched ignoring case, blank values count as missing
+    pub fn value(&self, record: usize, field_name: &str) -> Option<&str> {
+        let position = self
+            .fields
+            .iter()
+            .position(|field| field.name.eq_ignore_ascii_case(field_name))?;
+        self.records
+            .get(record)
+            .and_then(|values| values.get(position))
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    pub fn has_field(&self, field_name: &str) -> bool {
+        self.fields
+            .iter()
+            .any(|field| field.name.eq_ignore_ascii_case(field_name))
+    }
+}
+
+pub fn read_dbf(bytes: &[u8]) -> Result<DbfTable, String> {
+    if bytes.len() < 32 {
+        return Err(String::from("The .dbf file is truncated"));
+    }
+
+    let records_amount = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+    let header_length = u16::from_le_bytes(bytes[8..10].try_into().unwrap()) as usize;
+    let record_length = u16::from_le_bytes(bytes[10..12].try_into().unwrap()) as usize;
+    // every record starts with its deletion flag
+    if records_amount > 0 && record_length == 0 {
+        return Err(String::from("The .dbf file has records without length"));
+    }
+
+    let mut table = DbfTable::default();
+    let mut offset = 32;
+    while offset + 32 <= header_length && bytes.get(offset) != Some(&HEADER_TERMINATOR) {
+        let descriptor = match bytes.get(offset..offset + 32) {
+            Some(descriptor) => descriptor,
+            None => return Err(String::from("The .dbf file is truncated")),
+        };
+        let name_end = descriptor[..11].iter().position(|b| *b == 0).unwrap_or(11);
+        table.fields.push(DbfField {
+            name: decode(&descriptor[..name_end]),
+            kind: descriptor[11] as char,
+            length: descriptor[16],
+            decimals: descriptor[17],
+        });
+        offset += 32;
+    }
+
+    for record in 0..records_amount {
+        let values = match record
+            .checked_mul(record_length)
+            .and_then(|start| start.checked_add(header_length))
+            .and_then(|start| bytes.get(start..start.checked_add(record_length)?))
+        {
+            Some(values) => values,
+            None => return Err(String::from("The .dbf file is truncated")),
+        };
+
+        // deleted records still take their place so records keep matching shapes
+        let mut record_values: Vec<String> = vec![];
+        let mut position = 1;
+        for field in table.fields.iter() {
+            let end = (position + field.length as usize).min(values.len());
+            match values.get(position..end) {
+                Some(value) if values.first() != Some(&DELETED_RECORD) => {
+                    record_values.push(decode(value).trim().to_string())
+                }
+                _ => record_values.push(String::new()),
+            }
+            position = end;
+        }
+        table.records.push(record_values);
+    }
+
+    Ok(table)
+}
+
+// written as UTF-8, which the .cpg file next to it declares
+pub fn write_dbf(table: &DbfTable) -> Vec<u8> {
+    let header_length = 32 + table.fields.len() * 32 + 1;
+    let record_length = 1 + table
+        .fields
+        .iter()
+        .map(|field| field.length as usize)
+        .sum::<usize>();
+
+    let today = Utc::now().date_naive();
+    let mut bytes: Vec<u8> = vec![
+        0x03,
+        (today.year() - 1900) as u8,
+        today.month() as u8,
+        today.day() as u8,
+    ];
+    bytes.extend((table.records.len() as u32).to_le_bytes());
+    bytes.extend((header_length as u16).to_le_bytes());
+    bytes.extend((record_length as u16).to_le_bytes());
+    bytes.extend([0; 20]);
+
+    for field in table.fields.iter() {
+        let mut name = [0_u8; 11];
+        for (position, byte) in field.name.bytes().take(10).enumerate() {
+            name[position] = byte;
+        }
+        bytes.extend(name);
+        bytes.push(field.kind as u8);
+        bytes.extend([0; 4]);
+        bytes.push(field.length);
+        bytes.push(field.decimals);
+        bytes.extend([0; 14]);
+    }
+    bytes.push(HEADER_TERMINATOR);
+
+    for record in table.records.iter() {
+        bytes.push(b' ');
+        for (field, value) in table.fields.iter().zip(record.iter()) {
+            bytes.extend(pad(value, field));
+        }
+    }
+    bytes.push(FILE_TERMINATOR);
+
+    bytes
+}
+
+// text is left aligned and numbers right aligned, both padded with spaces
+fn pad(value: &str, field: &DbfField) -> Vec<u8> {
+    let length = field.length as usize;
+
+    let mut truncated = String::new();
+    for c in value.chars() {
+        if truncated.len() + c.len_utf8() > length {
+            break;
+        }
+        truncated.push(c);
+    }
+
+    let padding = vec![b' '; length - truncated.len()];
+    match field.kind {
+        'N' | 'F' => [padding, truncated.into_bytes()].concat(),
+        _ => [truncated.into_bytes(), padding].concat(),
+    }
+}
+
+// most files are UTF-8 or Latin-1, the latter maps byte by byte to chars
+pub fn decode(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|b| *b as char).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lots_table() -> DbfTable {
+        DbfTable {
+            fields: vec![
+                DbfField {
+                    name: String::from("NAME"),
+                    kind: 'C',
+                    length: 20,
+                    decimals: 0,
+                },
+                DbfField {
+                    name: String::from("PRICE"),
+                    kind: 'N',
+                    length: 12,
+                    decimals: 2,
+                },
+            ],
+            records: vec![
+                vec![String::from("Quadra D'Ávila L1"), String::from("150000.00")],
+                vec![String::from("L2"), String::new()],
+            ],
+        }
+    }
+
+    #[test]
+    fn written_tables_are_read_back() {
+        let table = read_dbf(&write_dbf(&lots_table())).unwrap();
+
+        assert_eq!(table.fields.len(), 2);
+        assert_eq!(table.fields[1].name, "PRICE");
+        assert_eq!(table.fields[1].kind, 'N');
+        assert_eq!(table.fields[1].length, 12);
+        assert_eq!(table.fields[1].decimals, 2);
+        assert_eq!(table.records, lots_table().records);
+        assert_eq!(table.value(0, "name"), Some("Quadra D'Ávila L1"));
+        assert_eq!(table.value(1, "price"), None);
+        assert!(table.has_field("Price"));
+    }
+
+    #[test]
+    fn deleted_records_keep_their_place() {
+        let mut bytes = write_dbf(&lots_table());
+        let header_length = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        bytes[header_length] = DELETED_RECORD;
+
+        let table = read_dbf(&bytes).unwrap();
+        assert_eq!(table.records.len(), 2);
+        assert_eq!(table.value(0, "NAME"), None);
+        assert_eq!(table.value(1, "NAME"), Some("L2"));
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let bytes = write_dbf(&lots_table());
+
+        // inside the header, the field descriptors and the records
+        for length in [20, 40, bytes.len() - 10] {
+            assert_eq!(
+                read_dbf(&bytes[..length]).err(),
+                Some(String::from("The .dbf file is truncated")),
+                "cut at {}",
+                length
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_corrupted_lengths() {
+        let bytes = write_dbf(&lots_table());
+
+        let mut no_record_length = bytes.clone();
+        no_record_length[10..12].copy_from_slice(&0_u16.to_le_bytes());
+        assert!(read_dbf(&no_record_length).is_err());
+
+        let mut many_records = bytes.clone();
+        many_records[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(read_dbf(&many_records).is_err());
+
+        let mut long_header = bytes.clone();
+        long_header[8..10].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert!(read_dbf(&long_header).is_err());
+    }
+
+    #[test]
+    fn short_records_leave_the_missing_values_blank() {
+        let mut bytes = write_dbf(&lots_table());
+        // records of the deletion flag and 5 characters of the name
+        bytes[10..12].copy_from_slice(&6_u16.to_le_bytes());
+
+        let table = read_dbf(&bytes).unwrap();
+        assert_eq!(table.records[0], vec![String::from("Quadr"), String::new()]);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod dbf;
+pub mod prj;
+pub mod shp;
@@ -0,0 +1,63 @@
+use crate::geometry::utm::{Hemisphere, UtmZone};
+
+pub const WGS84_PRJ: &str = "GEOGCS[\"GCS_WGS_1984\",DATUM[\"D_WGS_1984\",SPHEROID[\"WGS_1984\",6378137.0,298.257223563]],PRIMEM[\"Greenwich\",0.0],UNIT[\"Degree\",0.0174532925199433]]";
+
+pub enum ShapefileCrs {
+    // degrees, x is the longitude
+    Geographic,
+    Utm(UtmZone),
+}
+
+// understands geographic and UTM coordinates over WGS84 or SIRGAS 2000, which
+// is what the planning departments we deal with use
+pub fn read_prj(wkt: &str) -> Result<ShapefileCrs, String> {
+    let wkt = wkt.trim();
+    let upper = wkt.to_uppercase();
+
+    if !(upper.contains("WGS_1984")
+        || upper.contains("WGS 84")
+        || upper.contains("WGS84")
+        || upper.contains("SIRGAS"))
+    {
+        return Err(String::from(
+            "Only WGS84 and SIRGAS 2000 coordinates are supported",
+        ));
+    }
+
+    if upper.starts_with("GEOGCS") {
+        return Ok(ShapefileCrs::Geographic);
+    }
+
+    if !upper.starts_with("PROJCS") || !upper.contains("TRANSVERSE_MERCATOR") {
+        return Err(String::from(
+            "Only geographic and UTM projections are supported",
+        ));
+    }
+
+    let central_meridian = parameter(&upper, "CENTRAL_MERIDIAN")
+        .ok_or(String::from("The projection has no central meridian"))?;
+    let false_northing = parameter(&upper, "FALSE_NORTHING").unwrap_or(0.0);
+
+    let number = (central_meridian + 183.0) / 6.0;
+    let hemisphere = if false_northing > 0.0 {
+        Hemisphere::South
+    } else {
+        Hemisphere::North
+    };
+
+    match UtmZone::new(number.round() as u8, hemisphere) {
+        Some(zone) if (number - number.round()).abs() < 1e-9 => Ok(ShapefileCrs::Utm(zone)),
+        _ => Err(format!(
+            "The central meridian {} is not the one of a UTM zone",
+            central_meridian
+        )),
+    }
+}
+
+// PARAMETER["name",value]
+fn parameter(upper_wkt: &str, name: &str) -> Option<f64> {
+    let key = format!("PARAMETER[\"{}\",", name);
+    let start = upper_wkt.find(&key)? + key.len();
+    let end = start + upper_wkt[start..].find(']')?;
+    upper_wkt[start..end].trim().parse::<f64>().ok()
+}
@@ -0,0 +1,348 @@
+// Reads and writes the .shp geometry and .shx index files of an ESRI shapefile.
+// Only polygons are supported, Z and M values are ignored on read.
+
+const FILE_CODE: i32 = 9994;
+const VERSION: i32 = 1000;
+const HEADER_LENGTH: usize = 100;
+
+pub const SHAPE_NULL: i32 = 0;
+pub const SHAPE_POLYGON: i32 = 5;
+pub const SHAPE_POLYGON_Z: i32 = 15;
+pub const SHAPE_POLYGON_M: i32 = 25;
+
+pub enum Shape {
+    Null,
+    // (x, y) rings as stored, closed
+    Polygon(Vec<Vec<(f64, f64)>>),
+    Unsupported(i32),
+}
+
+pub fn read_shp(bytes: &[u8]) -> Result<Vec<Shape>, String> {
+    if bytes.len() < HEADER_LENGTH || be_i32(bytes, 0)? != FILE_CODE {
+        return Err(String::from("The .shp file is not a shapefile"));
+    }
+
+    let mut shapes: Vec<Shape> = vec![];
+    let mut offset = HEADER_LENGTH;
+    while offset + 8 <= bytes.len() {
+        // lengths are counted in 16 bit words
+        let content_length = to_usize(be_i32(bytes, offset + 4)?)?
+            .checked_mul(2)
+            .ok_or_else(corrupted)?;
+        let content = offset + 8;
+        let end = content.checked_add(content_length).ok_or_else(corrupted)?;
+        // a record is only read inside its own bounds
+        let record = match bytes.get(content..end) {
+            Some(record) => record,
+            None => return Err(String::from("The .shp file is truncated")),
+        };
+
+        let shape_type = le_i32(record, 0)?;
+        shapes.push(match shape_type {
+            SHAPE_NULL => Shape::Null,
+            SHAPE_POLYGON | SHAPE_POLYGON_Z | SHAPE_POLYGON_M => {
+                Shape::Polygon(read_rings(record)?)
+            }
+            other => Shape::Unsupported(other),
+        });
+
+        offset = end;
+    }
+
+    Ok(shapes)
+}
+
+fn read_rings(record: &[u8]) -> Result<Vec<Vec<(f64, f64)>>, String> {
+    // shape type and bounding box come first
+    let parts_amount = to_usize(le_i32(record, 36)?)?;
+    let points_amount = to_usize(le_i32(record, 40)?)?;
+    let parts_start = 44;
+    let points_start = parts_amount
+        .checked_mul(4)
+        .and_then(|parts_length| parts_length.checked_add(parts_start))
+        .ok_or_else(corrupted)?;
+
+    let mut starts: Vec<usize> = vec![];
+    for part in 0..parts_amount {
+        starts.push(to_usize(le_i32(record, parts_start + part * 4)?)?);
+    }
+
+    let mut rings: Vec<Vec<(f64, f64)>> = vec![];
+    for (part, start) in starts.iter().enumerate() {
+        let end = starts.get(part + 1).copied().unwrap_or(points_amount);
+        if *start > end || end > points_amount {
+            return Err(String::from("The .shp file has invalid polygon parts"));
+        }
+
+        let mut ring: Vec<(f64, f64)> = vec![];
+        for point in *start..end {
+            let point_offset = point
+                .checked_mul(16)
+                .and_then(|point_offset| point_offset.checked_add(points_start))
+                .ok_or_else(corrupted)?;
+            ring.push((
+                le_f64(record, point_offset)?,
+                le_f64(record, point_offset + 8)?,
+            ));
+        }
+        rings.push(ring);
+    }
+
+    Ok(rings)
+}
+
+// positive for counterclockwise rings. Shapefiles store outer rings clockwise
+// and holes counterclockwise
+pub fn signed_area(ring: &[(f64, f64)]) -> f64 {
+    let mut sum = 0.0;
+    for i in 0..ring.len() {
+        let (x1, y1) = ring[i];
+        let (x2, y2) = ring[(i + 1) % ring.len()];
+        sum += x1 * y2 - x2 * y1;
+    }
+    sum / 2.0
+}
+
+// writes single ring polygons, returning the .shp and .shx contents
+pub fn write_polygons(polygons: &[Vec<(f64, f64)>]) -> (Vec<u8>, Vec<u8>) {
+    let mut records: Vec<Vec<u8>> = vec![];
+    let mut bounds = Bounds::default();
+
+    for polygon in polygons.iter() {
+        let mut ring = polygon.clone();
+        if signed_area(&ring) > 0.0 {
+            ring.reverse();
+        }
+        if let Some(first) = ring.first().copied() {
+            ring.push(first);
+        }
+
+        let ring_bounds = Bounds::of(&ring);
+        bounds.extend(&ring_bounds);
+
+        let mut content: Vec<u8> = vec![];
+        content.extend(SHAPE_POLYGON.to_le_bytes());
+        ring_bounds.write(&mut content);
+        content.extend(1_i32.to_le_bytes());
+        content.extend((ring.len() as i32).to_le_bytes());
+        content.extend(0_i32.to_le_bytes());
+        for (x, y) in ring.iter() {
+            content.extend(x.to_le_bytes());
+            content.extend(y.to_le_bytes());
+        }
+        records.push(content);
+    }
+
+    let shp_length = HEADER_LENGTH + records.iter().map(|record| record.len() + 8).sum::<usize>();
+    let shx_length = HEADER_LENGTH + records.len() * 8;
+
+    let mut shp = header(shp_length, &bounds);
+    let mut shx = header(shx_length, &bounds);
+    for (index, record) in records.iter().enumerate() {
+        shx.extend(((shp.len() / 2) as i32).to_be_bytes());
+        shx.extend(((record.len() / 2) as i32).to_be_bytes());
+
+        shp.extend((index as i32 + 1).to_be_bytes());
+        shp.extend(((record.len() / 2) as i32).to_be_bytes());
+        shp.extend(record);
+    }
+
+    (shp, shx)
+}
+
+fn header(file_length: usize, bounds: &Bounds) -> Vec<u8> {
+    let mut header: Vec<u8> = vec![];
+    header.extend(FILE_CODE.to_be_bytes());
+    header.extend([0; 20]);
+    header.extend(((file_length / 2) as i32).to_be_bytes());
+    header.extend(VERSION.to_le_bytes());
+    header.extend(SHAPE_POLYGON.to_le_bytes());
+    bounds.write(&mut header);
+    // z and m ranges
+    header.extend([0; 32]);
+    header
+}
+
+struct Bounds {
+    min_x: f64,
+    min_y: f64,
+    max_x: f64,
+    max_y: f64,
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Self {
+            min_x: f64::MAX,
+            min_y: f64::MAX,
+            max_x: f64::MIN,
+            max_y: f64::MIN,
+        }
+    }
+}
+
+impl Bounds {
+    fn of(points: &[(f64, f64)]) -> Bounds {
+        let mut bounds = Bounds::default();
+        for (x, y) in points.iter() {
+            bounds.min_x = bounds.min_x.min(*x);
+            bounds.min_y = bounds.min_y.min(*y);
+            bounds.max_x = bounds.max_x.max(*x);
+            bounds.max_y = bounds.max_y.max(*y);
+        }
+        bounds
+    }
+
+    fn extend(&mut self, other: &Bounds) {
+        self.min_x = self.min_x.min(other.min_x);
+        self.min_y = self.min_y.min(other.min_y);
+        self.max_x = self.max_x.max(other.max_x);
+        self.max_y = self.max_y.max(other.max_y);
+    }
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        // an empty file has no bounds, zeros are written instead
+        let empty = self.min_x > self.max_x;
+        for value in [self.min_x, self.min_y, self.max_x, self.max_y] {
+            bytes.extend(if empty { 0.0 } else { value }.to_le_bytes());
+        }
+    }
+}
+
+fn slice<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], String> {
+    match offset.checked_add(N).and_then(|end| bytes.get(offset..end)) {
+        Some(slice) => Ok(slice.try_into().unwrap()),
+        None => Err(String::from("The .shp file is truncated")),
+    }
+}
+
+// lengths, amounts and indexes are never negative in a valid file
+fn to_usize(value: i32) -> Result<usize, String> {
+    usize::try_from(value).map_err(|_| corrupted())
+}
+
+fn corrupted() -> String {
+    String::from("The .shp file is corrupted")
+}
+
+fn be_i32(bytes: &[u8], offset: usize) -> Result<i32, String> {
+    Ok(i32::from_be_bytes(slice(bytes, offset)?))
+}
+
+fn le_i32(bytes: &[u8], offset: usize) -> Result<i32, String> {
+    Ok(i32::from_le_bytes(slice(bytes, offset)?))
+}
+
+fn le_f64(bytes: &[u8], offset: usize) -> Result<f64, String> {
+    Ok(f64::from_le_bytes(slice(bytes, offset)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Vec<(f64, f64)> {
+        vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]
+    }
+
+    fn polygon(shape: &Shape) -> &Vec<Vec<(f64, f64)>> {
+        match shape {
+            Shape::Polygon(rings) => rings,
+            _ => panic!("expected a polygon"),
+        }
+    }
+
+    #[test]
+    fn written_polygons_are_read_back() {
+        let triangle = vec![(-46.5, -23.5), (-46.4, -23.5), (-46.45, -23.4)];
+        let (shp, shx) = write_polygons(&[square(), triangle.clone()]);
+
+        let shapes = read_shp(&shp).unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(shx.len(), HEADER_LENGTH + 2 * 8);
+
+        // rings come back closed and clockwise
+        let rings = polygon(&shapes[0]);
+        assert_eq!(rings.len(), 1);
+        assert_eq!(rings[0].len(), 5);
+        assert_eq!(rings[0].first(), rings[0].last());
+        assert!(signed_area(&rings[0]) < 0.0);
+        for point in square() {
+            assert!(rings[0].contains(&point));
+        }
+
+        let rings = polygon(&shapes[1]);
+        for point in triangle {
+            assert!(rings[0].contains(&point));
+        }
+    }
+
+    #[test]
+    fn empty_file_has_no_shapes() {
+        let (shp, _) = write_polygons(&[]);
+
+        assert!(read_shp(&shp).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_shapefiles() {
+        let (mut shp, _) = write_polygons(&[square()]);
+        shp[3] = 0;
+
+        assert!(read_shp(&shp).is_err());
+        assert!(read_shp(&shp[..50]).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let (shp, _) = write_polygons(&[square()]);
+
+        for length in [HEADER_LENGTH + 8, HEADER_LENGTH + 20, shp.len() - 1] {
+            assert_eq!(
+                read_shp(&shp[..length]).err(),
+                Some(String::from("The .shp file is truncated")),
+                "cut at {}",
+                length
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_negative_and_oversized_record_lengths() {
+        let (shp, _) = write_polygons(&[square()]);
+
+        for content_length in [-1, i32::MAX] {
+            let mut corrupted = shp.clone();
+            corrupted[HEADER_LENGTH + 4..HEADER_LENGTH + 8]
+                .copy_from_slice(&content_length.to_be_bytes());
+
+            assert!(read_shp(&corrupted).is_err(), "length {}", content_length);
+        }
+    }
+
+    #[test]
+    fn rejects_corrupted_parts_and_points() {
+        let (shp, _) = write_polygons(&[square()]);
+        let content = HEADER_LENGTH + 8;
+
+        // parts amount, points amount and the start of the only part
+        for (offset, value) in [
+            (36, -1),
+            (36, i32::MAX),
+            (40, -1),
+            (40, i32::MAX),
+            (44, -1),
+            (44, 6),
+        ] {
+            let mut corrupted = shp.clone();
+            corrupted[content + offset..content + offset + 4].copy_from_slice(&value.to_le_bytes());
+
+            assert!(
+                read_shp(&corrupted).is_err(),
+                "{} at offset {}",
+                value,
+                offset
+            );
+        }
+    }
+}
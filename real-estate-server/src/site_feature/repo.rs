@@ -0,0 +1,144 @@
+use postgres::Row;
+
+use crate::{
+    database::storage::{SqlParam, Storage},
+    error::app_error::DynAppError,
+    location::location::Location,
+};
+
+use super::site_feature::SiteFeature;
+
+#[derive(Clone)]
+pub struct SiteFeatureRepo {
+    storage: Storage,
+}
+
+impl SiteFeatureRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    pub async fn create(
+        &self,
+        feature: SiteFeature,
+        locations: Vec<Location>,
+    ) -> Result<u64, DynAppError> {
+        let mut statements: Vec<(String, Vec<SqlParam>)> = vec![];
+
+        for location in locations.into_iter() {
+            statements.push((
+                String::from(
+                    "INSERT INTO
+                        app_location
+                            (id, lat, long)
+                    VALUES
+                        ($1, $2, $3)
+                    on conflict do nothing;",
+                ),
+                vec![
+                    Box::new(location.id),
+                    Box::new(location.lat),
+                    Box::new(location.long),
+                ],
+            ));
+        }
+
+        statements.push((
+            String::from(
+                "INSERT INTO
+                    site_feature
+                        (id, subdivision_id, f_name, land_use, geometry_kind, width)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6);",
+            ),
+            vec![
+                Box::new(feature.id.clone()),
+                Box::new(feature.subdivision_id),
+                Box::new(feature.name),
+                Box::new(feature.land_use.value_str().to_string()),
+                Box::new(feature.geometry_kind.value_str().to_string()),
+                Box::new(feature.width),
+            ],
+        ));
+
+        for (position, location_id) in feature.area.into_iter().enumerate() {
+            statements.push((
+                String::from(
+                    "INSERT INTO
+                        site_feature_location
+                            (feature_id, location_id, position)
+                    VALUES
+                        ($1, $2, $3);",
+                ),
+                vec![
+                    Box::new(feature.id.clone()),
+                    Box::new(location_id),
+                    Box::new(position as i32),
+                ],
+            ));
+        }
+
+        self.storage.transaction(statements).await
+    }
+
+    pub async fn get_subdivision_features(
+        &self,
+        subdivision_id: String,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                f.id, f.f_name, f.land_use, f.geometry_kind, f.width,
+                array_agg(al.lat ORDER BY fl.position) as lats, array_agg(al.long ORDER BY fl.position) as longs
+            FROM 
+                site_feature f join subdivision s on s.id = f.subdivision_id
+                join site_feature_location fl on fl.feature_id = f.id
+                join app_location al on al.id = fl.location_id
+            WHERE 
+                f.subdivision_id = $1
+                and ($2::varchar[] is null or s.organization_id = ANY($2))
+            GROUP BY 
+                f.id, f.f_name, f.land_use, f.geometry_kind, f.width
+            ORDER BY 
+                f.land_use, f.f_name;
+            ",
+        );
+
+        self.storage
+            .query(cmd, &[&subdivision_id, &organization_ids])
+            .await
+    }
+
+    pub async fn delete(
+        &self,
+        subdivision_id: String,
+        feature_id: String,
+    ) -> Result<u64, DynAppError> {
+        let statements: Vec<(String, Vec<SqlParam>)> = vec![
+            (
+                String::from(
+                    "DELETE FROM
+                        site_feature_location
+                    WHERE feature_id = (
+                        SELECT id FROM site_feature WHERE id = $1 and subdivision_id = $2
+                    );",
+                ),
+                vec![
+                    Box::new(feature_id.clone()),
+                    Box::new(subdivision_id.clone()),
+                ],
+            ),
+            (
+                String::from(
+                    "DELETE FROM
+                        site_feature
+                    WHERE id = $1 and subdivision_id = $2;",
+                ),
+                vec![Box::new(feature_id), Box::new(subdivision_id)],
+            ),
+        ];
+
+        self.storage.transaction(statements).await
+    }
+}
@@ -0,0 +1,254 @@
+use uuid::Uuid;
+
+use crate::{
+    api_contracts::{
+        land_use_breakdown::{LandUseBreakdown, LandUseShare},
+        site_feature_dto::SiteFeatureDto,
+    },
+    database::storage::Storage,
+    error::{
+        api_error::{ApiError, FieldError},
+        app_error::DynAppError,
+    },
+    geometry::polygon::{area_m2, length_m, validate_polygon},
+    location::location::Location,
+    organization::tenant_scope::TenantScope,
+    subdivision::service::{assemble_area, resolve_crs, to_locations, SubdivisionService},
+};
+
+use super::{
+    repo::SiteFeatureRepo,
+    site_feature::{GeometryKind, LandUse, SiteFeature},
+};
+
+const LAND_USES: [LandUse; 4] = [
+    LandUse::Street,
+    LandUse::GreenArea,
+    LandUse::Institutional,
+    LandUse::Preservation,
+];
+
+#[derive(Clone)]
+pub struct SiteFeatureService {
+    repo: SiteFeatureRepo,
+    subdivision_service: SubdivisionService,
+}
+
+impl SiteFeatureService {
+    pub fn new(storage: Storage, subdivision_service: SubdivisionService) -> Self {
+        Self {
+            repo: SiteFeatureRepo::new(storage),
+            subdivision_service,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        subdivision_id: String,
+        feature_dto: SiteFeatureDto,
+        scope: &TenantScope,
+    ) -> Result<String, DynAppError> {
+        self.subdivision_service
+            .check_access(subdivision_id.clone(), scope)
+            .await?;
+
+        let coordinates =
+            resolve_crs(feature_dto.crs.clone())?.area_to_wgs84(&feature_dto.coordinates);
+        let errors = validate_feature(&feature_dto, &coordinates);
+        if !errors.is_empty() {
+            let messages: Vec<String> = errors.iter().map(|error| error.message.clone()).collect();
+            return Err(Box::new(ApiError::Validation {
+                message: format!("Invalid feature: {}", messages.join(", ")),
+                errors,
+            }));
+        }
+
+        let mut locations: Vec<Location> = vec![];
+        let feature = SiteFeature {
+            id: Uuid::new_v4().to_string(),
+            subdivision_id,
+            name: feature_dto.name,
+            land_use: feature_dto.land_use,
+            geometry_kind: feature_dto.geometry_kind,
+            width: feature_dto.width,
+            area: Box::new(to_locations(&coordinates, &mut locations)),
+        };
+        let id = feature.id.clone();
+
+        self.repo.create(feature, locations).await?;
+        Ok(id)
+    }
+
+    pub async fn get_features(
+        &self,
+        subdivision_id: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<SiteFeatureDto>, DynAppError> {
+        self.subdivision_service
+            .check_access(subdivision_id.clone(), scope)
+            .await?;
+
+        let rows = self
+            .repo
+            .get_subdivision_features(subdivision_id, scope.organization_ids.clone())
+            .await?;
+
+        let mut features: Vec<SiteFeatureDto> = vec![];
+        for row in rows.iter() {
+            let land_use: String = row.get("land_use");
+            let geometry_kind: String = row.get("geometry_kind");
+            let (land_use, geometry_kind) = match (
+                LandUse::from_value_str(&land_use),
+                GeometryKind::from_value_str(&geometry_kind),
+            ) {
+                (Some(land_use), Some(geometry_kind)) => (land_use, geometry_kind),
+                _ => {
+                    return Err(Box::new(ApiError::Internal(format!(
+                        "Unknown land use {} or geometry {}",
+                        land_use, geometry_kind
+                    ))))
+                }
+            };
+
+            let coordinates = assemble_area(row.get("lats"), row.get("longs"));
+            let width: Option<f64> = row.get("width");
+            features.push(SiteFeatureDto {
+                id: Some(row.get("id")),
+                name: row.get("f_name"),
+                land_use,
+                geometry_kind,
+                area_m2: Some(feature_area(geometry_kind, &coordinates, width)),
+                coordinates: Box::new(coordinates),
+                width,
+                crs: None,
+            });
+        }
+
+        Ok(features)
+    }
+
+    pub async fn delete(
+        &self,
+        subdivision_id: String,
+        feature_id: String,
+        scope: &TenantScope,
+    ) -> Result<(), DynAppError> {
+        let features = self.get_features(subdivision_id.clone(), scope).await?;
+        if !features
+            .iter()
+            .any(|feature| feature.id.as_ref() == Some(&feature_id))
+        {
+            return Err(Box::new(ApiError::NotFound(format!(
+                "Feature {} not found in subdivision {}",
+                feature_id, subdivision_id
+            ))));
+        }
+
+        self.repo.delete(subdivision_id, feature_id).await?;
+        Ok(())
+    }
+
+    // share of the subdivision area taken by the lots and by each land use.
+    // Whatever the recorded geometries do not cover is reported as unassigned
+    pub async fn get_breakdown(
+        &self,
+        subdivision_id: String,
+        scope: &TenantScope,
+    ) -> Result<LandUseBreakdown, DynAppError> {
+        let subdivision = self
+            .subdivision_service
+            .get(subdivision_id.clone(), scope)
+            .await?;
+        let features = self.get_features(subdivision_id.clone(), scope).await?;
+        let lots = self
+            .subdivision_service
+            .get_subdivision_lots(subdivision_id.clone(), scope)
+            .await?;
+
+        let total_area = area_m2(&subdivision.area);
+        let mut areas: Vec<(String, f64)> = vec![(
+            String::from("lots"),
+            lots.iter().map(|lot| area_m2(&lot.area)).sum(),
+        )];
+        for land_use in LAND_USES.iter() {
+            areas.push((
+                land_use.value_str().to_string(),
+                features
+                    .iter()
+                    .filter(|feature| feature.land_use == *land_use)
+                    .filter_map(|feature| feature.area_m2)
+                    .sum(),
+            ));
+        }
+
+        let assigned: f64 = areas.iter().map(|(_, area)| area).sum();
+        areas.push((String::from("unassigned"), (total_area - assigned).max(0.0)));
+
+        Ok(LandUseBreakdown {
+            subdivision_id,
+            total_area_m2: total_area,
+            uses: areas
+                .into_iter()
+                .map(|(land_use, area)| LandUseShare {
+                    land_use,
+                    area_m2: area,
+                    percentage: if total_area > 0.0 {
+                        area / total_area * 100.0
+                    } else {
+                        0.0
+                    },
+                })
+                .collect(),
+        })
+    }
+}
+
+// line strings only have an area when they come with a width
+fn feature_area(
+    geometry_kind: GeometryKind,
+    coordinates: &[(f64, f64)],
+    width: Option<f64>,
+) -> f64 {
+    match geometry_kind {
+        GeometryKind::Polygon => area_m2(coordinates),
+        GeometryKind::LineString => length_m(coordinates) * width.unwrap_or(0.0),
+    }
+}
+
+fn validate_feature(feature_dto: &SiteFeatureDto, coordinates: &[(f64, f64)]) -> Vec<FieldError> {
+    let mut errors: Vec<FieldError> = vec![];
+
+    if feature_dto.name.trim().is_empty() {
+        errors.push(FieldError::new(
+            "name",
+            String::from("the name is required"),
+        ));
+    }
+
+    match feature_dto.geometry_kind {
+        GeometryKind::Polygon => errors.extend(
+            validate_polygon(coordinates)
+                .into_iter()
+                .map(|error| FieldError::new("coordinates", error)),
+        ),
+        GeometryKind::LineString => {
+            if coordinates.len() < 2 {
+                errors.push(FieldError::new(
+                    "coordinates",
+                    String::from("a line string needs at least 2 points"),
+                ));
+            }
+        }
+    }
+
+    if let Some(width) = feature_dto.width {
+        if width <= 0.0 {
+            errors.push(FieldError::new(
+                "width",
+                String::from("the width must be greater than zero"),
+            ));
+        }
+    }
+
+    errors
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// what a non-sale piece of the subdivision is used for. Municipal approval
+// asks for the share of the total area taken by each of them
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LandUse {
+    Street,
+    GreenArea,
+    Institutional,
+    // área de preservação permanente
+    Preservation,
+}
+
+impl LandUse {
+    pub fn value_str(&self) -> &'static str {
+        match *self {
+            LandUse::Street => "street",
+            LandUse::GreenArea => "green_area",
+            LandUse::Institutional => "institutional",
+            LandUse::Preservation => "preservation",
+        }
+    }
+
+    pub fn from_value_str(value: &str) -> Option<LandUse> {
+        match value {
+            "street" => Some(LandUse::Street),
+            "green_area" => Some(LandUse::GreenArea),
+            "institutional" => Some(LandUse::Institutional),
+            "preservation" => Some(LandUse::Preservation),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GeometryKind {
+    Polygon,
+    // streets are often drawn as their center line plus a width
+    LineString,
+}
+
+impl GeometryKind {
+    pub fn value_str(&self) -> &'static str {
+        match *self {
+            GeometryKind::Polygon => "polygon",
+            GeometryKind::LineString => "line_string",
+        }
+    }
+
+    pub fn from_value_str(value: &str) -> Option<GeometryKind> {
+        match value {
+            "polygon" => Some(GeometryKind::Polygon),
+            "line_string" => Some(GeometryKind::LineString),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SiteFeature {
+    pub id: String,
+    pub subdivision_id: String,
+    pub name: String,
+    pub land_use: LandUse,
+    pub geometry_kind: GeometryKind,
+    // in meters, only meaningful for line strings
+    pub width: Option<f64>,
+    pub area: Box<Vec<String>>,
+}
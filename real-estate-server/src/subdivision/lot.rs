@@ -1,6 +1,30 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
 #[derive(Clone)]
 pub struct Lot {
     pub area: Box<Vec<String>>,
     pub name: String,
     pub subdivision_id: String,
+    // asking price, unset until the developer prices the lot
+    pub price: Option<f64>,
+    // the quadra the lot belongs to, if the subdivision is organized in them
+    pub block_name: Option<String>,
+}
+
+// derived from the sales, a lot is sold once a sale references it
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LotStatus {
+    Available,
+    Sold,
+}
+
+impl LotStatus {
+    pub fn value_str(&self) -> &'static str {
+        match *self {
+            LotStatus::Available => "available",
+            LotStatus::Sold => "sold",
+        }
+    }
 }
@@ -1,4 +1,4 @@
 pub mod lot;
-mod repo;
+pub mod repo;
 pub mod service;
 pub mod subdivision;
@@ -1,9 +1,13 @@
 use std::vec;
 
 use postgres::Row;
-use postgres_types::ToSql;
 
-use crate::{database::storage::Storage, error::app_error::DynAppError};
+use crate::{
+    block::{block::Block, repo::block_statements},
+    database::storage::{SqlParam, Storage},
+    error::app_error::DynAppError,
+    location::location::Location,
+};
 
 use super::{lot::Lot, subdivision::Subdivision};
 
@@ -14,90 +18,93 @@ pub struct SubdivisonRepo {
 
 impl SubdivisonRepo {
     pub fn new(storage: Storage) -> Self {
-        Self { storage: storage }
+        Self { storage }
     }
 
-    pub async fn create(&self, subdivision: Subdivision) -> Result<(), DynAppError> {
-        let mut subdivision_locations_values = String::from("VALUES\n   ");
-        for location_id in subdivision.area.into_iter() {
-            subdivision_locations_values +=
-                format!("('{}', '{}'),\n", subdivision.id, location_id).as_str();
+    pub async fn create(&self, subdivision: Subdivision) -> Result<u64, DynAppError> {
+        let mut statements: Vec<(String, Vec<SqlParam>)> = vec![(
+            String::from(
+                "INSERT INTO
+                    subdivision
+                        (id, s_name, organization_id)
+                VALUES
+                    ($1, $2, $3);",
+            ),
+            vec![
+                Box::new(subdivision.id.clone()),
+                Box::new(subdivision.name),
+                Box::new(subdivision.organization_id),
+            ],
+        )];
+
+        for (position, location_id) in subdivision.area.into_iter().enumerate() {
+            statements.push((
+                String::from(
+                    "INSERT INTO
+                        subdivision_location
+                            (subdivision_id, location_id, position)
+                    VALUES
+                        ($1, $2, $3);",
+                ),
+                vec![
+                    Box::new(subdivision.id.clone()),
+                    Box::new(location_id),
+                    Box::new(position as i32),
+                ],
+            ));
         }
 
-        subdivision_locations_values = subdivision_locations_values.trim_end().to_string();
-        subdivision_locations_values.pop();
-
-        let cmd = format!(
-            "INSERT INTO
-                subdivision 
-                    (id, s_name)
-            VALUES
-                ('{}', '{}');
-                
-            INSERT INTO
-                subdivision_location
-                    (subdivision_id, location_id)
-            {};",
-            subdivision.id, subdivision.name, subdivision_locations_values
-        );
-
-        self.storage.batch_exec(cmd).await
-    }
-
-    pub async fn delete(&self, id: String) -> Result<u64, DynAppError> {
-        let cmd = String::from(
-            "DELETE FROM
-                subdivision
-            WHERE id = $1",
-        );
-
-        self.storage.exec(cmd, &[&id]).await
+        self.storage.transaction(statements).await
     }
 
-    pub async fn update(&self, id: String, new_name: String) -> Result<u64, DynAppError> {
-        let cmd = String::from(
-            "UPDATE subdivision
-            SET s_name = $1
-            WHERE id = $2",
-        );
-
-        self.storage.exec(cmd, &[&new_name, &id]).await
-    }
-
-    pub async fn search_by_name(&self, name: String) -> Result<Vec<Row>, DynAppError> {
+    // organization_ids restricts the results to those organizations, None means all of them
+    pub async fn search_by_name(
+        &self,
+        name: String,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
         let cmd = String::from(
             "
             SELECT 
-                s.s_name, s.id, array_agg(lat) as lats, array_agg(long) as longs 
+                s.s_name, s.id, array_agg(lat ORDER BY sl.position) as lats, array_agg(long ORDER BY sl.position) as longs 
             FROM
                 subdivision s 
                 join subdivision_location sl on s.id = sl.subdivision_id
                 join app_location al on sl.location_id = al.id 
             WHERE
-                POSITION(LOWER($1) in LOWER(s.s_name)) > 0;",
+                POSITION(LOWER($1) in LOWER(s.s_name)) > 0
+                and ($2::varchar[] is null or s.organization_id = ANY($2))
+            GROUP BY
+                s.s_name, s.id;",
         );
 
-         self.storage.query(cmd, &[&name]).await
+        self.storage.query(cmd, &[&name, &organization_ids]).await
     }
 
     pub async fn search_by_location(
         &self,
         coords: (f64, f64),
         radius: f64,
+        organization_ids: Option<Vec<String>>,
     ) -> Result<Vec<Row>, DynAppError> {
         let cmd = String::from(
             "
             SELECT 
-                s.s_name, s.id, array_agg(lat) lats, array_agg(long) longs 
+                s.s_name, s.id, array_agg(lat ORDER BY sl.position) lats, array_agg(long ORDER BY sl.position) longs 
             FROM
                 subdivision s 
                 join subdivision_location sl on s.id = sl.subdivision_id
                 join app_location al on sl.location_id = al.id
+            WHERE
+                $4::varchar[] is null or s.organization_id = ANY($4)
             GROUP BY
                 s.s_name, s.id
             HAVING 
                 (ST_DistanceSphere(
-                    ST_MakePoint((array_agg(al.long))[1], (array_agg(al.lat))[1]),
+                    ST_MakePoint(
+                        (array_agg(al.long ORDER BY sl.position))[1],
+                        (array_agg(al.lat ORDER BY sl.position))[1]
+                    ),
                     ST_MakePoint($1, $2)
                 )) <= $3;
             "
@@ -105,86 +112,91 @@ impl SubdivisonRepo {
 
         self
             .storage
-            .query(cmd, &[&coords.1, &coords.0, &radius])
+            .query(cmd, &[&coords.1, &coords.0, &radius, &organization_ids])
             .await
     }
 
-    pub async fn get_all_preview(&self) -> Result<Vec<Row>, DynAppError> {
+    pub async fn get(
+        &self,
+        id: String,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
         let cmd = String::from(
             "
             SELECT 
-                s.s_name, s.id, count(l.l_name) as lots
+                s.s_name, s.id, s.organization_id, array_agg(lat ORDER BY sl.position) as lats, array_agg(long ORDER BY sl.position) as longs 
+            FROM
+                subdivision s 
+                join subdivision_location sl on s.id = sl.subdivision_id
+                join app_location al on sl.location_id = al.id 
+            WHERE
+                s.id = $1
+                and ($2::varchar[] is null or s.organization_id = ANY($2))
+            GROUP BY
+                s.s_name, s.id, s.organization_id;",
+        );
+
+        self.storage.query(cmd, &[&id, &organization_ids]).await
+    }
+
+    pub async fn get_all_preview(
+        &self,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                s.s_name, s.id, s.organization_id, count(l.l_name) as lots
             FROM 
                 subdivision s 
                 left join lot l on l.subdivision_id = s.id 
+            WHERE
+                $1::varchar[] is null or s.organization_id = ANY($1)
             GROUP BY 
-                s.s_name, s.id;"
+                s.s_name, s.id, s.organization_id;"
         );
 
-        self.storage.query(cmd, &[]).await
+        self.storage.query(cmd, &[&organization_ids]).await
     }
 
-    // create a batch of lots assuming that the locations already exists
-    pub async fn create_lots(&self, lots: Box<[Lot]>) -> Result<(), DynAppError> {
-        let mut lot_values = String::from("VALUES\n   ");
-        let mut lot_locations_values = String::from("VALUES\n   ");
-        for lot in lots.into_iter() {
-            lot_values += format!("('{}', '{}'),\n", lot.name, lot.subdivision_id).as_str();
-            for location_id in lot.clone().area.into_iter() {
-                lot_locations_values += format!(
-                    "('{}', '{}', '{}'),\n",
-                    lot.name, lot.subdivision_id, location_id
-                )
-                .as_str();
-            }
-        }
-
-        lot_values = lot_values.trim_end().to_string();
-        lot_values.pop();
-        lot_locations_values = lot_locations_values.trim_end().to_string();
-        lot_locations_values.pop();
-
-        let cmd = format!(
-            "INSERT INTO
-                lot 
-                    (l_name, subdivision_id)
-                {};
-            
-            INSERT INTO
-                lot_location
-                    (l_name, subdivision_id, location_id)
-                {};",
-            lot_values, lot_locations_values
+    pub async fn get_preview(
+        &self,
+        id: String,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                s.s_name, s.id, s.organization_id, count(l.l_name) as lots
+            FROM 
+                subdivision s 
+                left join lot l on l.subdivision_id = s.id 
+            WHERE
+                s.id = $1
+                and ($2::varchar[] is null or s.organization_id = ANY($2))
+            GROUP BY 
+                s.s_name, s.id, s.organization_id;"
         );
 
-        self.storage.batch_exec(cmd).await
+        self.storage.query(cmd, &[&id, &organization_ids]).await
     }
 
-    pub async fn create_lot(&self, lot: Lot) -> Result<(), DynAppError> {
-        let mut lot_locations_values = String::from("VALUES\n   ");
-        for location_id in lot.area.into_iter() {
-            lot_locations_values +=
-                format!("('{}', '{}', '{}'),\n", lot.name, lot.subdivision_id, location_id).as_str();
+    // the lots, their vertices and, when the lots go to a block that does not
+    // exist yet, the block, all or nothing. Vertices shared with lots and
+    // blocks already saved are kept as they are
+    pub async fn insert_lots(
+        &self,
+        lots: Vec<Lot>,
+        locations: Vec<Location>,
+        block: Option<Block>,
+    ) -> Result<(), DynAppError> {
+        let mut statements = location_statements(locations);
+        if let Some(block) = block {
+            statements.extend(block_statements(block));
         }
+        statements.extend(lots.into_iter().flat_map(lot_statements));
 
-        lot_locations_values = lot_locations_values.trim_end().to_string();
-        lot_locations_values.pop();
-
-        let cmd = format!(
-            "INSERT INTO
-                lot 
-                    (l_name, subdivision_id)
-            VALUES
-                ('{}', '{}');
-                
-            INSERT INTO
-                lot_location
-                    (l_name, subdivision_id, location_id)
-            {};",
-            lot.name, lot.subdivision_id, lot_locations_values
-        );
-
-        self.storage.batch_exec(cmd).await
+        self.storage.transaction(statements).await.map(|_| ())
     }
 
     pub async fn get_lots_by_subdivision(
@@ -208,7 +220,8 @@ impl SubdivisonRepo {
             "
             SELECT *
             FROM lot_location
-            WHERE l_name = $1 and subdivision_id = $2;
+            WHERE l_name = $1 and subdivision_id = $2
+            ORDER BY position;
         ",
         );
 
@@ -232,27 +245,191 @@ impl SubdivisonRepo {
                 area: Box::new(ids),
                 name: row.get("l_name"),
                 subdivision_id: row.get("subdivision_id"),
+                price: row.get("l_price"),
+                block_name: row.get("block_name"),
             });
         }
 
         Ok(lots)
     }
     
-    pub async fn get_subdivision_lots(&self, subdivision_id : String) -> Result<Vec<Row>, DynAppError> {
+    pub async fn get_subdivision_lots(
+        &self,
+        subdivision_id: String,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
         let cmd = String::from(
             "
             SELECT 
-                l.l_name, array_agg(lat) as lats, array_agg(long) as longs
+                l.l_name, l.l_price, l.block_name, sa.id as sale_id, array_agg(lat ORDER BY ll.position) as lats, array_agg(long ORDER BY ll.position) as longs
             FROM 
                 lot l join lot_location ll on l.l_name = ll.l_name and l.subdivision_id = ll.subdivision_id 
                 join app_location al on al.id = ll.location_id
+                join subdivision s on s.id = l.subdivision_id
+                left join sale sa on sa.l_name = l.l_name and sa.subdivision_id = l.subdivision_id
             WHERE 
                 l.subdivision_id = $1
+                and ($2::varchar[] is null or s.organization_id = ANY($2))
             GROUP by 
-                l.l_name;
+                l.l_name, l.l_price, l.block_name, sa.id;
+            "
+        );
+
+        self.storage.query(cmd, &[&subdivision_id, &organization_ids]).await
+    }
+
+    pub async fn get_lot(
+        &self,
+        subdivision_id: String,
+        lot_name: String,
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                l.l_name, l.l_price, l.block_name, sa.id as sale_id, array_agg(lat ORDER BY ll.position) as lats, array_agg(long ORDER BY ll.position) as longs
+            FROM 
+                lot l join lot_location ll on l.l_name = ll.l_name and l.subdivision_id = ll.subdivision_id 
+                join app_location al on al.id = ll.location_id
+                join subdivision s on s.id = l.subdivision_id
+                left join sale sa on sa.l_name = l.l_name and sa.subdivision_id = l.subdivision_id
+            WHERE 
+                l.subdivision_id = $1 and l.l_name = $2
+                and ($3::varchar[] is null or s.organization_id = ANY($3))
+            GROUP by 
+                l.l_name, l.l_price, l.block_name, sa.id;
+            "
+        );
+
+        self.storage
+            .query(cmd, &[&subdivision_id, &lot_name, &organization_ids])
+            .await
+    }
+
+    pub async fn get_block_names(&self, subdivision_id: String) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                b_name
+            FROM 
+                block
+            WHERE 
+                subdivision_id = $1;
             "
         );
 
         self.storage.query(cmd, &[&subdivision_id]).await
     }
+
+    pub async fn create_with_lots(
+        &self,
+        subdivision: Subdivision,
+        lots: Vec<Lot>,
+        locations: Vec<Location>,
+    ) -> Result<u64, DynAppError> {
+        let mut statements = location_statements(locations);
+
+        statements.push((
+            String::from(
+                "INSERT INTO
+                    subdivision
+                        (id, s_name, organization_id)
+                VALUES
+                    ($1, $2, $3);",
+            ),
+            vec![
+                Box::new(subdivision.id.clone()),
+                Box::new(subdivision.name),
+                Box::new(subdivision.organization_id),
+            ],
+        ));
+
+        for (position, location_id) in subdivision.area.into_iter().enumerate() {
+            statements.push((
+                String::from(
+                    "INSERT INTO
+                        subdivision_location
+                            (subdivision_id, location_id, position)
+                    VALUES
+                        ($1, $2, $3);",
+                ),
+                vec![
+                    Box::new(subdivision.id.clone()),
+                    Box::new(location_id),
+                    Box::new(position as i32),
+                ],
+            ));
+        }
+
+        for lot in lots.into_iter() {
+            statements.extend(lot_statements(lot));
+        }
+
+        self.storage.transaction(statements).await
+    }
+}
+
+// vertices are shared between neighbouring polygons, the ones already saved are
+// left untouched
+pub fn location_statements(locations: Vec<Location>) -> Vec<(String, Vec<SqlParam>)> {
+    locations
+        .into_iter()
+        .map(|location| {
+            let params: Vec<SqlParam> = vec![
+                Box::new(location.id),
+                Box::new(location.lat),
+                Box::new(location.long),
+            ];
+            (
+                String::from(
+                    "INSERT INTO
+                        app_location
+                            (id, lat, long)
+                    VALUES
+                        ($1, $2, $3)
+                    on conflict do nothing;",
+                ),
+                params,
+            )
+        })
+        .collect()
+}
+
+// the lot and its vertices, assuming that the locations already exists
+fn lot_statements(lot: Lot) -> Vec<(String, Vec<SqlParam>)> {
+    let mut statements: Vec<(String, Vec<SqlParam>)> = vec![(
+        String::from(
+            "INSERT INTO
+                lot
+                    (l_name, subdivision_id, l_price, block_name)
+            VALUES
+                ($1, $2, $3, $4);",
+        ),
+        vec![
+            Box::new(lot.name.clone()),
+            Box::new(lot.subdivision_id.clone()),
+            Box::new(lot.price),
+            Box::new(lot.block_name.clone()),
+        ],
+    )];
+
+    for (position, location_id) in lot.area.into_iter().enumerate() {
+        statements.push((
+            String::from(
+                "INSERT INTO
+                    lot_location
+                        (l_name, subdivision_id, location_id, position)
+                VALUES
+                    ($1, $2, $3, $4);",
+            ),
+            vec![
+                Box::new(lot.name.clone()),
+                Box::new(lot.subdivision_id.clone()),
+                Box::new(location_id),
+                Box::new(position as i32),
+            ],
+        ));
+    }
+
+    statements
 }
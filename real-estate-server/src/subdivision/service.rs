@@ -1,21 +1,24 @@
-use std::{
-    borrow::Borrow,
-    future::{self, IntoFuture},
-    vec,
-};
+use std::vec;
 
-use tokio::try_join;
+use postgres::Row;
 
 use crate::{
-    api_contracts::{lot_dto::LotDto, subdivision_dto::SubdivisionDto, subdivision_preview::SubdivisionPreview},
-    database::storage::{self, Storage},
-    error::app_error::{AppError, DynAppError},
+    api_contracts::{
+        lot_batch_report::{LotBatchReport, LotBatchResult, LotBatchStatus},
+        lot_dto::LotDto, subdivision_dto::SubdivisionDto, subdivision_preview::SubdivisionPreview,
+    },
+    block::block::Block,
+    database::storage::Storage,
+    error::{api_error::ApiError, app_error::DynAppError},
+    geometry::{crs::Crs, polygon::{open_ring, validate_polygon}},
     location::{location::Location, service::LocationService},
+    organization::tenant_scope::TenantScope,
+    tile::tile_cache::TileCache,
 };
 
 use super::{
-    lot::Lot,
-    repo::{self, SubdivisonRepo},
+    lot::{Lot, LotStatus},
+    repo::SubdivisonRepo,
     subdivision::Subdivision,
 };
 
@@ -23,20 +26,31 @@ use super::{
 pub struct SubdivisionService {
     repo: SubdivisonRepo,
     location_service: LocationService,
+    // the map tiles drawing the subdivisions and lots written here
+    tile_cache: TileCache,
 }
 
 impl SubdivisionService {
-    pub fn new(storage: Storage, location_service: LocationService) -> Self {
+    pub fn new(storage: Storage, location_service: LocationService, tile_cache: TileCache) -> Self {
         Self {
             repo: SubdivisonRepo::new(storage),
-            location_service: location_service,
+            location_service,
+            tile_cache,
         }
     }
 
-    pub async fn create(&self, subdivision_dto: SubdivisionDto) -> Result<String, DynAppError> {
+    pub async fn create(
+        &self,
+        subdivision_dto: SubdivisionDto,
+        scope: &TenantScope,
+    ) -> Result<String, DynAppError> {
+        let organization_id =
+            resolve_organization(subdivision_dto.organization_id.clone(), scope)?;
+        let area = resolve_crs(subdivision_dto.crs.clone())?.area_to_wgs84(&subdivision_dto.area);
+
         let mut location_ids: Vec<String> = vec![];
 
-        for coords in subdivision_dto.area.into_iter() {
+        for coords in area.into_iter() {
             location_ids.push(
                 self.location_service
                     .get_or_create_location(coords)
@@ -49,85 +63,293 @@ impl SubdivisionService {
             id: subdivision_dto.id,
             area: Box::new(location_ids),
             name: subdivision_dto.name,
+            organization_id,
         };
 
         self.repo.create(subdivision.clone()).await?;
+        self.tile_cache.clear();
         Ok(subdivision.id)
     }
 
-    pub async fn create_lot(&self, lot: LotDto) -> Result<String, DynAppError> {
-        let mut location_ids: Vec<String> = vec![];
+    // creates the subdivision together with all of its lots and their locations
+    // in a single transaction, so a failure leaves nothing behind
+    pub async fn create_with_lots(
+        &self,
+        subdivision_dto: SubdivisionDto,
+        lots: Vec<LotDto>,
+        scope: &TenantScope,
+    ) -> Result<String, DynAppError> {
+        let organization_id =
+            resolve_organization(subdivision_dto.organization_id.clone(), scope)?;
 
-        for coordinates in lot.area.iter() {
-            let location = Location {
-                id: format!(
-                    "{}-{}",
-                    coordinates.0.to_string(),
-                    coordinates.1.to_string()
-                ),
-                lat: coordinates.0,
-                long: coordinates.1,
-            };
+        let mut locations: Vec<Location> = vec![];
+        let subdivision_area =
+            resolve_crs(subdivision_dto.crs.clone())?.area_to_wgs84(&subdivision_dto.area);
+        let subdivision = Subdivision {
+            id: subdivision_dto.id.clone(),
+            name: subdivision_dto.name,
+            area: Box::new(to_locations(&subdivision_area, &mut locations)),
+            organization_id,
+        };
 
-            self.location_service
-                .create_location(location.clone())
-                .await?;
-            location_ids.push(location.id)
+        let mut lot_entities: Vec<Lot> = vec![];
+        for lot in lots.iter() {
+            let lot_area = resolve_crs(lot.crs.clone())?.area_to_wgs84(&lot.area);
+            lot_entities.push(Lot {
+                area: Box::new(to_locations(&lot_area, &mut locations)),
+                name: lot.name.clone(),
+                subdivision_id: subdivision_dto.id.clone(),
+                price: lot.price,
+                block_name: lot.block_name.clone(),
+            });
         }
 
+        self.repo
+            .create_with_lots(subdivision, lot_entities, locations)
+            .await?;
+        self.tile_cache.clear();
+        Ok(subdivision_dto.id)
+    }
+
+    // the lot always goes to subdivision_id, whatever its dto says, so a tenant
+    // cannot write to a subdivision outside of its scope
+    pub async fn create_lot(
+        &self,
+        subdivision_id: String,
+        lot: LotDto,
+        scope: &TenantScope,
+    ) -> Result<String, DynAppError> {
+        self.check_access(subdivision_id.clone(), scope).await?;
+        self.check_blocks(subdivision_id.clone(), std::slice::from_ref(&lot))
+            .await?;
+        let area = resolve_crs(lot.crs.clone())?.area_to_wgs84(&lot.area);
+        let mut locations: Vec<Location> = vec![];
+
         let lot_entity = Lot {
-            area: Box::new(location_ids),
+            area: Box::new(to_locations(&area, &mut locations)),
             name: lot.name,
-            subdivision_id: lot.subdivision_id,
+            subdivision_id: subdivision_id.clone(),
+            price: lot.price,
+            block_name: lot.block_name.clone(),
         };
 
-        self.repo.create_lot(lot_entity).await?;
+        self.repo
+            .insert_lots(vec![lot_entity], locations, None)
+            .await?;
+        self.invalidate_tiles(&subdivision_id);
         Ok(lot.id)
     }
 
-    pub async fn create_lots(
+    // all the lots or none of them. new_block is created along with the lots,
+    // for lots drawn in a block the subdivision does not have yet
+    pub async fn insert_lots(
         &self,
-        lots_dtos: Box<[LotDto]>,
-    ) -> Result<Box<Vec<Lot>>, DynAppError> {
-        let mut lots: Box<Vec<Lot>> = Box::new(vec![]);
+        subdivision_id: String,
+        lots_dtos: &[LotDto],
+        new_block: Option<(Block, Vec<Location>)>,
+        scope: &TenantScope,
+    ) -> Result<(), DynAppError> {
+        self.check_access(subdivision_id.clone(), scope).await?;
+        let new_block_name = new_block.as_ref().map(|(block, _)| block.name.clone());
+        let in_existing_blocks: Vec<LotDto> = lots_dtos
+            .iter()
+            .filter(|lot| new_block_name.is_none() || lot.block_name != new_block_name)
+            .cloned()
+            .collect();
+        self.check_blocks(subdivision_id.clone(), &in_existing_blocks)
+            .await?;
 
+        let (block, mut locations) = match new_block {
+            Some((block, locations)) => (Some(block), locations),
+            None => (None, vec![]),
+        };
+        let mut lots: Vec<Lot> = vec![];
         for lot in lots_dtos.iter() {
-            let mut location_ids: Vec<String> = vec![];
-            for coordinates in lot.area.iter() {
-                let location = Location {
-                    id: format!(
-                        "{}-{}",
-                        coordinates.0.to_string(),
-                        coordinates.1.to_string()
-                    ),
-                    lat: coordinates.0,
-                    long: coordinates.1,
-                };
+            let area = resolve_crs(lot.crs.clone())?.area_to_wgs84(&lot.area);
+            lots.push(Lot {
+                area: Box::new(to_locations(&area, &mut locations)),
+                name: lot.name.clone(),
+                subdivision_id: subdivision_id.clone(),
+                price: lot.price,
+                block_name: lot.block_name.clone(),
+            });
+        }
 
-                self.location_service
-                    .create_location(location.clone())
-                    .await?;
-                location_ids.push(location.id);
+        self.repo.insert_lots(lots, locations, block).await?;
+        self.invalidate_tiles(&subdivision_id);
+        Ok(())
+    }
+
+    // saves every valid lot of the batch and reports what happened to each one.
+    // Lots whose name is already taken are skipped, so a batch that failed
+    // halfway can be sent again as it is
+    pub async fn create_lots_batch(
+        &self,
+        subdivision_id: String,
+        lots_dtos: Box<[LotDto]>,
+        scope: &TenantScope,
+    ) -> Result<LotBatchReport, DynAppError> {
+        let existing_names: Vec<String> = self
+            .get_subdivision_lots(subdivision_id.clone(), scope)
+            .await?
+            .into_iter()
+            .map(|lot| lot.name)
+            .collect();
+        let block_names = self.get_block_names(subdivision_id.clone()).await?;
+
+        let mut results: Vec<LotBatchResult> = vec![];
+        // the index of each result and its lot, with the area opened and in WGS84
+        let mut accepted: Vec<(usize, LotDto)> = vec![];
+
+        for (index, lot) in lots_dtos.iter().enumerate() {
+            let mut result = LotBatchResult {
+                index,
+                name: lot.name.clone(),
+                status: LotBatchStatus::Created,
+                reasons: vec![],
+            };
+
+            if existing_names.contains(&lot.name) {
+                result.status = LotBatchStatus::Skipped;
+                result.reasons.push(format!(
+                    "A lot named {} already exists in the subdivision",
+                    lot.name
+                ));
+                results.push(result);
+                continue;
             }
 
-            let cloned_lot = lot.clone();
-            lots.push(Lot {
-                area: Box::new(location_ids),
-                name: cloned_lot.name,
-                subdivision_id: cloned_lot.subdivision_id,
-            });
+            if lot.name.trim().is_empty() {
+                result.reasons.push(String::from("The lot has no name"));
+            } else if accepted.iter().any(|(_, other)| other.name == lot.name) {
+                result
+                    .reasons
+                    .push(format!("The name {} is repeated in the batch", lot.name));
+            }
+
+            if let Some(block_name) = &lot.block_name {
+                if !block_names.contains(block_name) {
+                    result.reasons.push(format!(
+                        "Block {} not found in subdivision {}",
+                        block_name, subdivision_id
+                    ));
+                }
+            }
+
+            let area = match resolve_crs(lot.crs.clone()) {
+                Ok(crs) => {
+                    let area = open_ring(&crs.area_to_wgs84(&lot.area));
+                    result.reasons.extend(validate_polygon(&area));
+                    area
+                }
+                Err(err) => {
+                    result.reasons.push(err.message());
+                    vec![]
+                }
+            };
+
+            if result.reasons.is_empty() {
+                accepted.push((
+                    index,
+                    LotDto {
+                        area: Box::new(area),
+                        crs: None,
+                        ..lot.clone()
+                    },
+                ));
+            } else {
+                result.status = LotBatchStatus::Failed;
+            }
+            results.push(result);
         }
 
-        self.repo.create_lots(lots.as_slice().into()).await?;
-        Ok(lots)
+        // each lot goes in on its own, so a lot the database refuses fails
+        // alone instead of taking the rest of the batch with it. Vertices
+        // shared with lots already saved are reused
+        let mut created_any = false;
+        for (index, lot) in accepted.into_iter() {
+            let mut locations: Vec<Location> = vec![];
+            let lot_entity = Lot {
+                area: Box::new(to_locations(&lot.area, &mut locations)),
+                name: lot.name,
+                subdivision_id: subdivision_id.clone(),
+                price: lot.price,
+                block_name: lot.block_name,
+            };
+
+            match self.repo.insert_lots(vec![lot_entity], locations, None).await {
+                Ok(_) => created_any = true,
+                Err(err) => {
+                    results[index].status = LotBatchStatus::Failed;
+                    results[index].reasons.push(batch_failure_reason(err));
+                }
+            }
+        }
+        if created_any {
+            self.invalidate_tiles(&subdivision_id);
+        }
+
+        let count = |status: LotBatchStatus| {
+            results.iter().filter(|result| result.status == status).count()
+        };
+        Ok(LotBatchReport {
+            subdivision_id,
+            created: count(LotBatchStatus::Created),
+            skipped: count(LotBatchStatus::Skipped),
+            failed: count(LotBatchStatus::Failed),
+            lots: results,
+        })
+    }
+
+    // lots can only reference blocks the subdivision already has
+    // callers check the access to the subdivision first
+    pub async fn get_block_names(
+        &self,
+        subdivision_id: String,
+    ) -> Result<Vec<String>, DynAppError> {
+        Ok(self
+            .repo
+            .get_block_names(subdivision_id)
+            .await?
+            .iter()
+            .map(|row| row.get("b_name"))
+            .collect())
+    }
+
+    async fn check_blocks(&self, subdivision_id: String, lots: &[LotDto]) -> Result<(), DynAppError> {
+        if lots.iter().all(|lot| lot.block_name.is_none()) {
+            return Ok(());
+        }
+
+        let block_names = self.get_block_names(subdivision_id.clone()).await?;
+
+        for lot in lots.iter() {
+            if let Some(block_name) = &lot.block_name {
+                if !block_names.contains(block_name) {
+                    return Err(Box::new(ApiError::NotFound(format!(
+                        "Block {} not found in subdivision {}",
+                        block_name, subdivision_id
+                    ))));
+                }
+            }
+        }
+
+        Ok(())
     }
 
     // pub async fn delete(&self, id: String) -> Result<String, DynAppError> {}
 
     // pub async fn update(&self, id: String) -> Result<String, DynAppError> {}
 
-    pub async fn search_by_name(&self, name: String) -> Result<Vec<SubdivisionDto>, DynAppError> {
-        let rows = self.repo.search_by_name(name).await?;
+    pub async fn search_by_name(
+        &self,
+        name: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<SubdivisionDto>, DynAppError> {
+        let rows = self
+            .repo
+            .search_by_name(name, scope.organization_ids.clone())
+            .await?;
 
         let mut dtos: Vec<SubdivisionDto> = vec![];
         for row in rows.iter() {
@@ -136,7 +358,9 @@ impl SubdivisionService {
                     area: Box::new(assemble_area(row.get("lats"), row.get("longs"))),
                     id: row.get("id"),
                     name: row.get("s_name"),
-                    lots: None
+                    lots: None,
+                    organization_id: None,
+                    crs: None,
                 }
             )
         }
@@ -147,8 +371,12 @@ impl SubdivisionService {
     pub async fn search_by_location(
         &self,
         coords: (f64, f64),
+        scope: &TenantScope,
     ) -> Result<Vec<SubdivisionDto>, DynAppError> {
-        let rows = self.repo.search_by_location(coords, 5000.0).await?;
+        let rows = self
+            .repo
+            .search_by_location(coords, 5000.0, scope.organization_ids.clone())
+            .await?;
 
         let mut dtos: Vec<SubdivisionDto> = vec![];
         for row in rows.iter() {
@@ -157,7 +385,9 @@ impl SubdivisionService {
                     area: Box::new(assemble_area(row.get("lats"), row.get("longs"))),
                     id: row.get("id"),
                     name: row.get("s_name"),
-                    lots: None
+                    lots: None,
+                    organization_id: None,
+                    crs: None,
                 }
             )
         }
@@ -188,6 +418,10 @@ impl SubdivisionService {
                 name: lot.name,
                 subdivision_id: lot.subdivision_id,
                 area: Box::new(locations),
+                price: lot.price,
+                block_name: lot.block_name,
+                status: None,
+                crs: None,
             })
         }
 
@@ -206,12 +440,34 @@ impl SubdivisionService {
             ),
             lots: Some(Box::new(lot_dtos)),
             name: subdivision.clone().name,
+            organization_id: Some(subdivision.organization_id),
+            crs: None,
         })
     }
 
+    // the subdivision and its boundary, without the lots
+    pub async fn get(&self, id: String, scope: &TenantScope) -> Result<SubdivisionDto, DynAppError> {
+        let rows = self.repo.get(id.clone(), scope.organization_ids.clone()).await?;
+
+        match rows.first() {
+            Some(row) => Ok(SubdivisionDto {
+                area: Box::new(assemble_area(row.get("lats"), row.get("longs"))),
+                id: row.get("id"),
+                name: row.get("s_name"),
+                lots: None,
+                organization_id: Some(row.get("organization_id")),
+                crs: None,
+            }),
+            None => Err(Box::new(ApiError::NotFound(format!("Subdivision {} not found", id)))),
+        }
+    }
+
     // TODO: implement pagination
-    pub async fn get_all(&self) -> Result<Vec<SubdivisionPreview>, DynAppError> {
-        let rows = self.repo.get_all_preview().await?;
+    pub async fn get_all(&self, scope: &TenantScope) -> Result<Vec<SubdivisionPreview>, DynAppError> {
+        let rows = self
+            .repo
+            .get_all_preview(scope.organization_ids.clone())
+            .await?;
 
         let mut previews: Vec<SubdivisionPreview> = vec![];
         for row in rows.iter() {
@@ -219,7 +475,8 @@ impl SubdivisionService {
                 SubdivisionPreview {
                     id: row.get("id"),
                     name: row.get("s_name"),
-                    lots_amount: row.get("lots")
+                    lots_amount: row.get("lots"),
+                    organization_id: row.get("organization_id"),
                 }
             )
         }
@@ -227,8 +484,83 @@ impl SubdivisionService {
         Ok(previews)
     }
 
-    pub async fn get_subdivision_lots(&self, subdivision_id : String) -> Result<Vec<LotDto>, DynAppError> {
-        let lot_rows = self.repo.get_subdivision_lots(subdivision_id.clone()).await?;
+    // subdivisions outside of the scope are reported as missing, so their
+    // existence does not leak to other tenants
+    pub async fn get_preview(
+        &self,
+        id: String,
+        scope: &TenantScope,
+    ) -> Result<SubdivisionPreview, DynAppError> {
+        let rows = self
+            .repo
+            .get_preview(id.clone(), scope.organization_ids.clone())
+            .await?;
+
+        match rows.first() {
+            Some(row) => Ok(SubdivisionPreview {
+                id: row.get("id"),
+                name: row.get("s_name"),
+                lots_amount: row.get("lots"),
+                organization_id: row.get("organization_id"),
+            }),
+            None => Err(Box::new(ApiError::NotFound(format!("Subdivision {} not found", id)))),
+        }
+    }
+
+    // for changes to the lots made elsewhere, such as a sale changing their status
+    pub fn invalidate_tiles(&self, subdivision_id: &str) {
+        self.tile_cache.invalidate_subdivision(subdivision_id);
+    }
+
+    pub async fn check_access(&self, id: String, scope: &TenantScope) -> Result<(), DynAppError> {
+        self.get_preview(id, scope).await?;
+        Ok(())
+    }
+
+    pub async fn get_lot(
+        &self,
+        subdivision_id: String,
+        lot_name: String,
+        scope: &TenantScope,
+    ) -> Result<LotDto, DynAppError> {
+        let rows = self
+            .repo
+            .get_lot(
+                subdivision_id.clone(),
+                lot_name.clone(),
+                scope.organization_ids.clone(),
+            )
+            .await?;
+
+        match rows.first() {
+            Some(row) => Ok(LotDto {
+                id: format!("{}-{}", lot_name, subdivision_id),
+                area: Box::new(assemble_area(row.get("lats"), row.get("longs"))),
+                name: lot_name,
+                subdivision_id,
+                price: row.get("l_price"),
+                block_name: row.get("block_name"),
+                status: Some(lot_status(row)),
+                crs: None,
+            }),
+            None => Err(Box::new(ApiError::NotFound(format!(
+                "Lot {} not found in subdivision {}",
+                lot_name, subdivision_id
+            )))),
+        }
+    }
+
+    pub async fn get_subdivision_lots(
+        &self,
+        subdivision_id: String,
+        scope: &TenantScope,
+    ) -> Result<Vec<LotDto>, DynAppError> {
+        self.check_access(subdivision_id.clone(), scope).await?;
+
+        let lot_rows = self
+            .repo
+            .get_subdivision_lots(subdivision_id.clone(), scope.organization_ids.clone())
+            .await?;
         
         let mut lots: Vec<LotDto> = vec![];
         for row in lot_rows.into_iter() {
@@ -238,7 +570,11 @@ impl SubdivisionService {
                     id: format!("{}-{}", lot_name, subdivision_id.clone()),
                     area: Box::new(assemble_area(row.get("lats"), row.get("longs"))),
                     name: lot_name,
-                    subdivision_id: subdivision_id.clone()
+                    subdivision_id: subdivision_id.clone(),
+                    price: row.get("l_price"),
+                    block_name: row.get("block_name"),
+                    status: Some(lot_status(&row)),
+                    crs: None,
                 }
             )
         }
@@ -247,10 +583,191 @@ impl SubdivisionService {
     }
 }
 
-fn assemble_area(lats: Vec<f64>, longs: Vec<f64>) -> Vec<(f64, f64)> {
+pub fn lot_status(row: &Row) -> LotStatus {
+    match row.get::<&str, Option<String>>("sale_id") {
+        Some(_) => LotStatus::Sold,
+        None => LotStatus::Available,
+    }
+}
+
+pub fn assemble_area(lats: Vec<f64>, longs: Vec<f64>) -> Vec<(f64, f64)> {
     let mut area: Vec<(f64, f64)> = vec![];
     for (pos, value) in lats.into_iter().enumerate() {
         area.push((value, longs[pos]));
     }
     area
-}
\ No newline at end of file
+}
+
+// the subdivision goes to the informed organization or, when the creator only
+// belongs to one, to that one
+pub fn resolve_organization(
+    organization_id: Option<String>,
+    scope: &TenantScope,
+) -> Result<String, DynAppError> {
+    let organization_id = match (organization_id, &scope.organization_ids) {
+        (Some(organization_id), _) => organization_id,
+        (None, Some(ids)) if ids.len() == 1 => ids[0].clone(),
+        _ => {
+            return Err(Box::new(ApiError::validation(
+                "organization_id",
+                String::from("The organization owning the subdivision must be informed"),
+            )))
+        }
+    };
+
+    if !scope.includes(&organization_id) {
+        return Err(Box::new(ApiError::NotFound(format!(
+            "Organization {} not found",
+            organization_id
+        ))));
+    }
+
+    Ok(organization_id)
+}
+
+// collects the locations of an area into `locations`, returning their ids
+// what a lot of a batch reports when the database refuses it. Server errors
+// only reach the logs, like in the error responses
+fn batch_failure_reason(err: DynAppError) -> String {
+    if err.status_code() >= 500 {
+        eprintln!("{} {}: {}", err.status_code(), err.code(), err.message());
+        return String::from("The lot could not be saved");
+    }
+    err.message()
+}
+
+pub fn to_locations(area: &[(f64, f64)], locations: &mut Vec<Location>) -> Vec<String> {
+    area.iter()
+        .map(|coordinates| {
+            let location = Location {
+                id: format!("{}-{}", coordinates.0, coordinates.1),
+                lat: coordinates.0,
+                long: coordinates.1,
+            };
+            locations.push(location.clone());
+            location.id
+        })
+        .collect()
+}
+
+// areas without a CRS are taken as WGS84
+pub fn resolve_crs(code: Option<String>) -> Result<Crs, DynAppError> {
+    match code {
+        None => Ok(Crs::Wgs84),
+        Some(code) => match Crs::from_code(&code) {
+            Some(crs) => Ok(crs),
+            None => Err(Box::new(ApiError::validation(
+                "crs",
+                format!("Unsupported coordinate reference system {}", code),
+            ))),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok<T>(result: Result<T, DynAppError>) -> T {
+        result.unwrap_or_else(|err| panic!("{}", err.message()))
+    }
+
+    fn lot(name: &str, area: Vec<(f64, f64)>) -> LotDto {
+        LotDto {
+            area: Box::new(area),
+            id: String::from(name),
+            name: String::from(name),
+            subdivision_id: String::new(),
+            price: None,
+            block_name: None,
+            status: None,
+            crs: None,
+        }
+    }
+
+    #[test]
+    fn batch_failures_hide_server_errors() {
+        let internal: DynAppError = Box::new(ApiError::Internal(String::from(
+            "Unexpected number of rows created: 0",
+        )));
+        assert_eq!(batch_failure_reason(internal), "The lot could not be saved");
+
+        let conflict: DynAppError = Box::new(ApiError::Conflict(String::from(
+            "The lot L1 already exists",
+        )));
+        assert_eq!(batch_failure_reason(conflict), "The lot L1 already exists");
+    }
+
+    #[tokio::test]
+    async fn batch_lots_touching_saved_lots_are_created() {
+        let storage = match Storage::for_tests() {
+            Some(storage) => storage,
+            None => {
+                eprintln!("REAL_ESTATE_TEST_DB_HOST is not set, skipping");
+                return;
+            }
+        };
+        let scope = TenantScope::unrestricted();
+        let service = SubdivisionService::new(
+            storage.clone(),
+            LocationService::new(storage.clone()),
+            TileCache::new(),
+        );
+
+        let suffix = uuid::Uuid::new_v4().to_string();
+        let organization_id = format!("batch-test-{}", suffix);
+        ok(storage
+            .exec(
+                String::from("INSERT INTO organization (id, o_name) VALUES ($1, $2);"),
+                &[&organization_id, &organization_id],
+            )
+            .await);
+        // each run gets its own corner of the map, so its vertices are new
+        let base = -22.5 + (suffix.as_bytes()[0] as f64) * 0.001;
+        let subdivision_id = ok(service
+            .create(
+                SubdivisionDto {
+                    id: format!("batch-test-{}", suffix),
+                    name: String::from("Batch test"),
+                    area: Box::new(vec![
+                        (base - 0.01, -45.01),
+                        (base - 0.01, -44.99),
+                        (base + 0.01, -44.99),
+                        (base + 0.01, -45.01),
+                    ]),
+                    lots: None,
+                    organization_id: Some(organization_id),
+                    crs: None,
+                },
+                &scope,
+            )
+            .await);
+
+        // three lots in a row, each one sharing a side with the next
+        let side = |k: u32| -45.0 + k as f64 * 0.0001;
+        let square = |k: u32| {
+            vec![
+                (base, side(k)),
+                (base, side(k + 1)),
+                (base + 0.0001, side(k + 1)),
+                (base + 0.0001, side(k)),
+            ]
+        };
+        ok(service
+            .create_lot(subdivision_id.clone(), lot("L1", square(0)), &scope)
+            .await);
+        let report = ok(service
+            .create_lots_batch(
+                subdivision_id.clone(),
+                vec![lot("L2", square(1)), lot("L3", square(2))]
+                    .into_boxed_slice(),
+                &scope,
+            )
+            .await);
+
+        assert_eq!(report.created, 2);
+        assert_eq!(report.failed, 0);
+        let saved = ok(service.get_subdivision_lots(subdivision_id, &scope).await);
+        assert_eq!(saved.len(), 3);
+    }
+}
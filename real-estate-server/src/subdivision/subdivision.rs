@@ -1,10 +1,9 @@
 use serde::Serialize;
 
-use crate::api_contracts::subdivision_dto::SubdivisionDto;
-
 #[derive(Clone, Serialize)]
 pub struct Subdivision {
     pub id: String,
     pub name: String,
+    pub organization_id: String,
     pub area: Box<Vec<String>>,
 }
@@ -0,0 +1,5 @@
+pub mod mvt;
+mod repo;
+pub mod service;
+pub mod tile;
+pub mod tile_cache;
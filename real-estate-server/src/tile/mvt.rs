@@ -0,0 +1,339 @@
+// A minimal Mapbox Vector Tile 2.1 encoder, enough for polygon layers. The
+// protobuf messages are written by hand:
+//
+//   Tile    { repeated Layer layers = 3; }
+//   Layer   { required uint32 version = 15; required string name = 1;
+//             repeated Feature features = 2; repeated string keys = 3;
+//             repeated Value values = 4; optional uint32 extent = 5; }
+//   Feature { repeated uint32 tags = 2 [packed]; optional GeomType type = 3;
+//             repeated uint32 geometry = 4 [packed]; }
+//   Value   { string string_value = 1; double double_value = 3; }
+
+const POLYGON: u64 = 3;
+
+const MOVE_TO: u32 = 1;
+const LINE_TO: u32 = 2;
+const CLOSE_PATH: u32 = 7;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Text(String),
+    Number(f64),
+}
+
+struct EncodedFeature {
+    tags: Vec<u32>,
+    geometry: Vec<u32>,
+}
+
+pub struct Layer {
+    name: String,
+    extent: u32,
+    keys: Vec<String>,
+    values: Vec<PropertyValue>,
+    features: Vec<EncodedFeature>,
+}
+
+impl Layer {
+    pub fn new(name: &str, extent: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            extent,
+            keys: vec![],
+            values: vec![],
+            features: vec![],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    // ring in tile coordinates, open or closed. Rings that collapse to less
+    // than three distinct points at this zoom are left out
+    pub fn add_polygon(&mut self, ring: &[(i32, i32)], properties: Vec<(&str, PropertyValue)>) {
+        let mut points: Vec<(i32, i32)> = vec![];
+        for point in ring.iter() {
+            if points.last() != Some(point) {
+                points.push(*point);
+            }
+        }
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        if points.len() < 3 {
+            return;
+        }
+
+        // exterior rings go clockwise on screen, where y points down
+        if signed_area(&points) < 0 {
+            points.reverse();
+        }
+
+        let mut geometry: Vec<u32> = vec![command(MOVE_TO, 1)];
+        let mut cursor = (0, 0);
+        for (pos, point) in points.iter().enumerate() {
+            if pos == 1 {
+                geometry.push(command(LINE_TO, points.len() as u32 - 1));
+            }
+            geometry.push(zigzag(point.0 - cursor.0));
+            geometry.push(zigzag(point.1 - cursor.1));
+            cursor = *point;
+        }
+        geometry.push(command(CLOSE_PATH, 1));
+
+        let mut tags: Vec<u32> = vec![];
+        for (key, value) in properties.into_iter() {
+            tags.push(self.key_index(key));
+            tags.push(self.value_index(value));
+        }
+
+        self.features.push(EncodedFeature { tags, geometry });
+    }
+
+    fn key_index(&mut self, key: &str) -> u32 {
+        match self.keys.iter().position(|existing| existing == key) {
+            Some(pos) => pos as u32,
+            None => {
+                self.keys.push(key.to_string());
+                self.keys.len() as u32 - 1
+            }
+        }
+    }
+
+    fn value_index(&mut self, value: PropertyValue) -> u32 {
+        match self.values.iter().position(|existing| *existing == value) {
+            Some(pos) => pos as u32,
+            None => {
+                self.values.push(value);
+                self.values.len() as u32 - 1
+            }
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut out: Vec<u8> = vec![];
+        write_tag(&mut out, 15, 0);
+        write_varint(&mut out, 2);
+        write_bytes(&mut out, 1, self.name.as_bytes());
+
+        for feature in self.features.iter() {
+            let mut encoded: Vec<u8> = vec![];
+            write_packed(&mut encoded, 2, &feature.tags);
+            write_tag(&mut encoded, 3, 0);
+            write_varint(&mut encoded, POLYGON);
+            write_packed(&mut encoded, 4, &feature.geometry);
+            write_bytes(&mut out, 2, &encoded);
+        }
+
+        for key in self.keys.iter() {
+            write_bytes(&mut out, 3, key.as_bytes());
+        }
+
+        for value in self.values.iter() {
+            let mut encoded: Vec<u8> = vec![];
+            match value {
+                PropertyValue::Text(text) => write_bytes(&mut encoded, 1, text.as_bytes()),
+                PropertyValue::Number(number) => {
+                    write_tag(&mut encoded, 3, 1);
+                    encoded.extend_from_slice(&number.to_le_bytes());
+                }
+            }
+            write_bytes(&mut out, 4, &encoded);
+        }
+
+        write_tag(&mut out, 5, 0);
+        write_varint(&mut out, self.extent as u64);
+        out
+    }
+}
+
+// empty layers are left out, a tile without any layer is a valid empty tile
+pub fn encode_tile(layers: &[Layer]) -> Vec<u8> {
+    let mut out: Vec<u8> = vec![];
+    for layer in layers.iter().filter(|layer| !layer.is_empty()) {
+        write_bytes(&mut out, 3, &layer.encode());
+    }
+    out
+}
+
+fn command(id: u32, count: u32) -> u32 {
+    (id & 0x7) | (count << 3)
+}
+
+fn zigzag(value: i32) -> u32 {
+    ((value << 1) ^ (value >> 31)) as u32
+}
+
+// twice the area, in screen coordinates
+fn signed_area(points: &[(i32, i32)]) -> i64 {
+    let mut sum: i64 = 0;
+    for i in 0..points.len() {
+        let (x1, y1) = points[i];
+        let (x2, y2) = points[(i + 1) % points.len()];
+        sum += x1 as i64 * y2 as i64 - x2 as i64 * y1 as i64;
+    }
+    sum
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_tag(out: &mut Vec<u8>, field: u32, wire_type: u8) {
+    write_varint(out, ((field as u64) << 3) | wire_type as u64);
+}
+
+fn write_bytes(out: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    write_tag(out, field, 2);
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn write_packed(out: &mut Vec<u8>, field: u32, values: &[u32]) {
+    let mut packed: Vec<u8> = vec![];
+    for value in values.iter() {
+        write_varint(&mut packed, *value as u64);
+    }
+    write_bytes(out, field, &packed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::tile::TileCoord;
+
+    fn varint(value: u64) -> Vec<u8> {
+        let mut out: Vec<u8> = vec![];
+        write_varint(&mut out, value);
+        out
+    }
+
+    // back to absolute points, checking the commands on the way
+    fn decode_ring(geometry: &[u32]) -> Vec<(i32, i32)> {
+        let unzigzag = |value: u32| ((value >> 1) as i32) ^ -((value & 1) as i32);
+        assert_eq!(geometry[0], command(MOVE_TO, 1));
+        assert_eq!(
+            geometry[3],
+            command(LINE_TO, (geometry.len() as u32 - 5) / 2)
+        );
+        assert_eq!(geometry[geometry.len() - 1], command(CLOSE_PATH, 1));
+
+        let deltas = [&geometry[1..3], &geometry[4..geometry.len() - 1]].concat();
+        let mut cursor = (0, 0);
+        deltas
+            .chunks(2)
+            .map(|delta| {
+                cursor = (cursor.0 + unzigzag(delta[0]), cursor.1 + unzigzag(delta[1]));
+                cursor
+            })
+            .collect()
+    }
+
+    #[test]
+    fn zigzag_interleaves_negative_and_positive_values() {
+        assert_eq!(zigzag(0), 0);
+        assert_eq!(zigzag(-1), 1);
+        assert_eq!(zigzag(1), 2);
+        assert_eq!(zigzag(-2), 3);
+        assert_eq!(zigzag(2), 4);
+        assert_eq!(zigzag(i32::MAX), u32::MAX - 1);
+        assert_eq!(zigzag(i32::MIN), u32::MAX);
+    }
+
+    #[test]
+    fn varints_take_seven_bits_per_byte() {
+        assert_eq!(varint(0), vec![0x00]);
+        assert_eq!(varint(127), vec![0x7f]);
+        assert_eq!(varint(128), vec![0x80, 0x01]);
+        assert_eq!(varint(300), vec![0xac, 0x02]);
+        assert_eq!(varint(u64::MAX).len(), 10);
+    }
+
+    #[test]
+    fn commands_pack_the_id_and_the_count() {
+        assert_eq!(command(MOVE_TO, 1), 9);
+        assert_eq!(command(LINE_TO, 3), 26);
+        assert_eq!(command(CLOSE_PATH, 1), 15);
+    }
+
+    #[test]
+    fn encodes_a_square_as_move_line_and_close() {
+        let mut layer = Layer::new("lots", 4096);
+        layer.add_polygon(&[(0, 0), (10, 0), (10, 10), (0, 10), (0, 0)], vec![]);
+
+        // the closing point is left to ClosePath
+        assert_eq!(
+            layer.features[0].geometry,
+            vec![9, 0, 0, 26, 20, 0, 0, 20, 19, 0, 15]
+        );
+    }
+
+    #[test]
+    fn exterior_rings_are_clockwise_after_the_y_flip() {
+        // counter-clockwise on a north up map, which stays counter-clockwise on
+        // screen once y grows southwards and has to be reversed
+        let tile = TileCoord::new(0, 0, 0).unwrap();
+        let ring: Vec<(i32, i32)> = [(-10.0, -10.0), (-10.0, 10.0), (10.0, 10.0), (10.0, -10.0)]
+            .into_iter()
+            .map(|point| tile.project(point))
+            .collect();
+        let reversed: Vec<(i32, i32)> = ring.iter().rev().cloned().collect();
+
+        let mut layer = Layer::new("lots", 4096);
+        layer.add_polygon(&ring, vec![]);
+        layer.add_polygon(&reversed, vec![]);
+
+        for feature in layer.features.iter() {
+            let points = decode_ring(&feature.geometry);
+            assert_eq!(points.len(), 4);
+            // the surveyor's formula in tile coordinates, positive for exterior rings
+            assert!(signed_area(&points) > 0);
+        }
+    }
+
+    #[test]
+    fn drops_rings_that_collapse_at_the_zoom() {
+        let mut layer = Layer::new("lots", 4096);
+        layer.add_polygon(&[(5, 5), (5, 5), (6, 5), (5, 5)], vec![]);
+
+        assert!(layer.is_empty());
+        assert!(encode_tile(&[layer]).is_empty());
+    }
+
+    #[test]
+    fn layers_share_repeated_keys_and_values() {
+        let square = [(0, 0), (10, 0), (10, 10), (0, 10)];
+        let mut layer = Layer::new("lots", 4096);
+        layer.add_polygon(
+            &square,
+            vec![
+                ("status", PropertyValue::Text(String::from("sold"))),
+                ("price", PropertyValue::Number(1.0)),
+            ],
+        );
+        layer.add_polygon(
+            &square,
+            vec![
+                ("status", PropertyValue::Text(String::from("sold"))),
+                ("price", PropertyValue::Text(String::from("1"))),
+            ],
+        );
+
+        assert_eq!(layer.keys, vec!["status", "price"]);
+        assert_eq!(
+            layer.values,
+            vec![
+                PropertyValue::Text(String::from("sold")),
+                PropertyValue::Number(1.0),
+                PropertyValue::Text(String::from("1")),
+            ]
+        );
+        assert_eq!(layer.features[0].tags, vec![0, 0, 1, 1]);
+        assert_eq!(layer.features[1].tags, vec![0, 0, 1, 2]);
+    }
+}
@@ -0,0 +1,84 @@
+use postgres::Row;
+
+use crate::{database::storage::Storage, error::app_error::DynAppError};
+
+#[derive(Clone)]
+pub struct TileRepo {
+    storage: Storage,
+}
+
+impl TileRepo {
+    pub fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+
+    // subdivisions whose bounding box touches (south, west, north, east)
+    pub async fn get_subdivisions_in_bounds(
+        &self,
+        bounds: (f64, f64, f64, f64),
+        organization_ids: Option<Vec<String>>,
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                s.id, s.s_name, array_agg(lat ORDER BY sl.position) as lats, array_agg(long ORDER BY sl.position) as longs 
+            FROM
+                subdivision s 
+                join subdivision_location sl on s.id = sl.subdivision_id
+                join app_location al on al.id = sl.location_id
+            WHERE 
+                ($5::varchar[] is null or s.organization_id = ANY($5))
+            GROUP BY 
+                s.id, s.s_name
+            HAVING 
+                min(al.lat) <= $3 and max(al.lat) >= $1
+                and min(al.long) <= $4 and max(al.long) >= $2;
+            ",
+        );
+
+        self.storage
+            .query(
+                cmd,
+                &[
+                    &bounds.0,
+                    &bounds.1,
+                    &bounds.2,
+                    &bounds.3,
+                    &organization_ids,
+                ],
+            )
+            .await
+    }
+
+    // lots of the given subdivisions whose bounding box touches the bounds
+    pub async fn get_lots_in_bounds(
+        &self,
+        subdivision_ids: Vec<String>,
+        bounds: (f64, f64, f64, f64),
+    ) -> Result<Vec<Row>, DynAppError> {
+        let cmd = String::from(
+            "
+            SELECT 
+                l.subdivision_id, l.l_name, l.l_price, sa.id as sale_id, array_agg(lat ORDER BY ll.position) as lats, array_agg(long ORDER BY ll.position) as longs
+            FROM 
+                lot l join lot_location ll on l.l_name = ll.l_name and l.subdivision_id = ll.subdivision_id 
+                join app_location al on al.id = ll.location_id
+                left join sale sa on sa.l_name = l.l_name and sa.subdivision_id = l.subdivision_id
+            WHERE 
+                l.subdivision_id = ANY($1)
+            GROUP BY 
+                l.subdivision_id, l.l_name, l.l_price, sa.id
+            HAVING 
+                min(al.lat) <= $4 and max(al.lat) >= $2
+                and min(al.long) <= $5 and max(al.long) >= $3;
+            ",
+        );
+
+        self.storage
+            .query(
+                cmd,
+                &[&subdivision_ids, &bounds.0, &bounds.1, &bounds.2, &bounds.3],
+            )
+            .await
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    database::storage::Storage,
+    error::app_error::DynAppError,
+    organization::tenant_scope::TenantScope,
+    subdivision::service::{assemble_area, lot_status},
+};
+
+use super::{
+    mvt::{encode_tile, Layer, PropertyValue},
+    repo::TileRepo,
+    tile::{TileCoord, EXTENT},
+    tile_cache::TileCache,
+};
+
+// below this zoom lots are a few pixels wide and only the boundaries are drawn
+const MIN_LOT_ZOOM: u8 = 14;
+
+// share of a tile fetched around it, keeps edges from showing seams
+const BUFFER: f64 = 1.0 / 16.0;
+
+#[derive(Clone)]
+pub struct TileService {
+    repo: TileRepo,
+    cache: TileCache,
+}
+
+impl TileService {
+    pub fn new(storage: Storage, cache: TileCache) -> Self {
+        Self {
+            repo: TileRepo::new(storage),
+            cache,
+        }
+    }
+
+    // the tile as a Mapbox Vector Tile with a subdivisions and a lots layer
+    pub async fn get_tile(
+        &self,
+        coord: TileCoord,
+        scope: &TenantScope,
+    ) -> Result<Vec<u8>, DynAppError> {
+        let key = cache_key(coord, scope);
+        if let Some(content) = self.cache.get(&key) {
+            return Ok(content);
+        }
+
+        let bounds = coord.bounds(BUFFER);
+        let subdivision_rows = self
+            .repo
+            .get_subdivisions_in_bounds(bounds, scope.organization_ids.clone())
+            .await?;
+
+        let mut subdivision_ids: Vec<String> = vec![];
+        let mut subdivisions = Layer::new("subdivisions", EXTENT);
+        for row in subdivision_rows.iter() {
+            let id: String = row.get("id");
+            let name: String = row.get("s_name");
+            let ring = project(coord, &assemble_area(row.get("lats"), row.get("longs")));
+            subdivisions.add_polygon(
+                &ring,
+                vec![
+                    ("id", PropertyValue::Text(id.clone())),
+                    ("name", PropertyValue::Text(name)),
+                ],
+            );
+            subdivision_ids.push(id);
+        }
+
+        let mut lots = Layer::new("lots", EXTENT);
+        if coord.z >= MIN_LOT_ZOOM && !subdivision_ids.is_empty() {
+            let lot_rows = self
+                .repo
+                .get_lots_in_bounds(subdivision_ids.clone(), bounds)
+                .await?;
+
+            for row in lot_rows.iter() {
+                let ring = project(coord, &assemble_area(row.get("lats"), row.get("longs")));
+                let mut properties = vec![
+                    ("name", PropertyValue::Text(row.get("l_name"))),
+                    (
+                        "subdivision_id",
+                        PropertyValue::Text(row.get("subdivision_id")),
+                    ),
+                    (
+                        "status",
+                        PropertyValue::Text(lot_status(row).value_str().to_string()),
+                    ),
+                ];
+                if let Some(price) = row.get::<&str, Option<f64>>("l_price") {
+                    properties.push(("price", PropertyValue::Number(price)));
+                }
+                lots.add_polygon(&ring, properties);
+            }
+        }
+
+        let content = encode_tile(&[subdivisions, lots]);
+        self.cache.insert(key, content.clone(), subdivision_ids);
+        Ok(content)
+    }
+}
+
+fn project(coord: TileCoord, area: &[(f64, f64)]) -> Vec<(i32, i32)> {
+    area.iter().map(|point| coord.project(*point)).collect()
+}
+
+// users of different organizations see different subdivisions on the same tile
+fn cache_key(coord: TileCoord, scope: &TenantScope) -> String {
+    let organizations = match &scope.organization_ids {
+        Some(ids) => {
+            let mut ids = ids.clone();
+            ids.sort();
+            ids.join(",")
+        }
+        None => String::from("*"),
+    };
+
+    format!("{}/{}/{}:{}", coord.z, coord.x, coord.y, organizations)
+}
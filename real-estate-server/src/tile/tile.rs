@@ -0,0 +1,59 @@
+use std::f64::consts::PI;
+
+// the resolution of the tile grid, the one every Mapbox Vector Tile client expects
+pub const EXTENT: u32 = 4096;
+
+const MAX_ZOOM: u8 = 22;
+
+// an XYZ tile in web mercator, y grows southwards
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TileCoord {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl TileCoord {
+    pub fn new(z: u8, x: u32, y: u32) -> Option<TileCoord> {
+        if z > MAX_ZOOM {
+            return None;
+        }
+
+        let n = 1u32 << z;
+        if x >= n || y >= n {
+            return None;
+        }
+
+        Some(TileCoord { z, x, y })
+    }
+
+    // (south, west, north, east) in degrees, grown by buffer tiles on every
+    // side so features crossing the edge make it to both tiles
+    pub fn bounds(&self, buffer: f64) -> (f64, f64, f64, f64) {
+        let n = (1u64 << self.z) as f64;
+        let west = (self.x as f64 - buffer) / n * 360.0 - 180.0;
+        let east = (self.x as f64 + 1.0 + buffer) / n * 360.0 - 180.0;
+        let north = tile_y_to_lat(self.y as f64 - buffer, n);
+        let south = tile_y_to_lat(self.y as f64 + 1.0 + buffer, n);
+
+        (south, west, north, east)
+    }
+
+    // position of a (lat, long) point inside the tile, in EXTENT units. Points
+    // outside the tile fall out of the 0..EXTENT range
+    pub fn project(&self, point: (f64, f64)) -> (i32, i32) {
+        let n = (1u64 << self.z) as f64;
+        let lat = point.0.clamp(-85.051_128, 85.051_128).to_radians();
+        let world_x = (point.1 + 180.0) / 360.0 * n;
+        let world_y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0 * n;
+
+        (
+            ((world_x - self.x as f64) * EXTENT as f64).round() as i32,
+            ((world_y - self.y as f64) * EXTENT as f64).round() as i32,
+        )
+    }
+}
+
+fn tile_y_to_lat(y: f64, n: f64) -> f64 {
+    (PI * (1.0 - 2.0 * y / n)).sinh().atan().to_degrees()
+}
@@ -0,0 +1,70 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+// past this many tiles the cache starts over, a map session only ever needs a
+// few hundred of them
+const MAX_ENTRIES: usize = 20_000;
+
+struct CachedTile {
+    content: Vec<u8>,
+    // the subdivisions drawn on the tile, a change to any of them drops it
+    subdivision_ids: Vec<String>,
+}
+
+// encoded tiles shared by every request. Cloning shares the same entries
+#[derive(Clone)]
+pub struct TileCache {
+    entries: Arc<RwLock<HashMap<String, CachedTile>>>,
+}
+
+impl TileCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        match self.entries.read() {
+            Ok(entries) => entries.get(key).map(|tile| tile.content.clone()),
+            Err(_) => None,
+        }
+    }
+
+    pub fn insert(&self, key: String, content: Vec<u8>, subdivision_ids: Vec<String>) {
+        if let Ok(mut entries) = self.entries.write() {
+            if entries.len() >= MAX_ENTRIES {
+                entries.clear();
+            }
+            entries.insert(
+                key,
+                CachedTile {
+                    content,
+                    subdivision_ids,
+                },
+            );
+        }
+    }
+
+    // for changes to the lots of a subdivision
+    pub fn invalidate_subdivision(&self, subdivision_id: &str) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.retain(|_, tile| !tile.subdivision_ids.iter().any(|id| id == subdivision_id));
+        }
+    }
+
+    // a new subdivision may land on tiles that had nothing on them
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.clear();
+        }
+    }
+}
+
+impl Default for TileCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,599 @@
+// Generated by `cargo run --bin ts_client` from real-estate-server/openapi.json,
+// regenerate it instead of editing it.
+
+import axios, { Method, ResponseType } from "axios";
+
+export const client = {
+    baseUrl: "http://192.168.0.9:5000",
+    // "Bearer <access_token>" once logged in, or "ApiKey <key>" for integrations
+    authorization: undefined as string | undefined,
+};
+
+// thrown for any status the route does not document, `problem` is the error
+// body the server sent
+export class ApiError extends Error {
+    status: number;
+    problem?: ProblemDetails;
+
+    constructor(status: number, problem?: ProblemDetails) {
+        super(problem?.detail ?? `Request failed with status ${status}`);
+        this.status = status;
+        this.problem = problem;
+    }
+}
+
+interface SendOptions {
+    body?: unknown;
+    contentType?: string;
+    query?: object;
+    headers?: Record<string, string | undefined>;
+    responseType: ResponseType;
+    accepted: number[];
+}
+
+const send = async <T>(method: Method, path: string, options: SendOptions): Promise<T> => {
+    const headers: Record<string, string> = {};
+    const allHeaders = {
+        ...options.headers,
+        "Content-Type": options.contentType,
+        Authorization: client.authorization,
+    };
+    for (const [name, value] of Object.entries(allHeaders)) {
+        if (value !== undefined) {
+            headers[name] = value;
+        }
+    }
+
+    try {
+        const response = await axios.request<T>({
+            method,
+            url: `${client.baseUrl}${path}`,
+            data: options.body,
+            params: options.query,
+            headers,
+            responseType: options.responseType,
+            validateStatus: (status) => options.accepted.includes(status),
+        });
+        return response.data;
+    } catch (error) {
+        if (axios.isAxiosError(error) && error.response) {
+            const data = error.response.data;
+            const problem = typeof data === "object" && data !== null && "code" in data
+                ? data as ProblemDetails
+                : undefined;
+            throw new ApiError(error.response.status, problem);
+        }
+        throw error;
+    }
+};
+
+export interface AnalyticsReport {
+    from: string;
+    organizations: OrganizationAnalytics[];
+    subdivisions: SubdivisionAnalytics[];
+    to: string;
+}
+
+export interface ApiKeyCreationPayload {
+    expires_at?: string | null;
+    name: string;
+    scope: ApiKeyScope;
+}
+
+export interface ApiKeyDto {
+    created_at: string;
+    expires_at?: string | null;
+    id: string;
+    key?: string | null;
+    last_used_at?: string | null;
+    name: string;
+    organization_id: string;
+    revoked_at?: string | null;
+    scope: ApiKeyScope;
+}
+
+export type ApiKeyScope = "read_only" | "read_write";
+
+export interface AuthTokenDto {
+    access_token: string;
+    expires_in: number;
+    refresh_token: string;
+    token_type: string;
+}
+
+export type BinaryFile = ArrayBuffer | Blob;
+
+export interface BlockDto {
+    area?: [number, number][];
+    crs?: string | null;
+    name: string;
+}
+
+export interface BlockSummary {
+    area: [number, number][];
+    available_lots: number;
+    listed_price: number;
+    lots_amount: number;
+    lots_area: number;
+    name: string;
+    sold_lots: number;
+    subdivision_id: string;
+}
+
+export interface ContractTemplateDto {
+    body: string;
+}
+
+export type ExportFormat = "geojson" | "kml" | "gpx" | "shapefile";
+
+export type FeatureKind = "subdivision" | "lot";
+
+export interface FeatureReport {
+    errors: string[];
+    index: number;
+    kind: FeatureKind;
+    name?: string | null;
+    status: string;
+}
+
+export interface FieldError {
+    field: string;
+    message: string;
+}
+
+export interface GeoJsonImportPayload {
+    feature_collection: Record<string, unknown>;
+    kind_property?: string | null;
+    name_property?: string | null;
+    organization_id?: string | null;
+    subdivision_id: string;
+}
+
+export type GeometryKind = "polygon" | "line_string";
+
+export type Hemisphere = "north" | "south";
+
+export interface ImportReport {
+    created: boolean;
+    errors: string[];
+    features: FeatureReport[];
+    subdivision_id: string;
+}
+
+export interface InstallmentDto {
+    amount: number;
+    due_date: string;
+}
+
+export interface JobDto {
+    attempts: number;
+    created_at: string;
+    error?: string | null;
+    finished_at?: string | null;
+    id: string;
+    kind: JobKind;
+    progress: number;
+    result_url?: string | null;
+    started_at?: string | null;
+    status: JobStatus;
+}
+
+export type JobKind = "geojson_import" | "shapefile_import" | "dxf_import" | "spreadsheet_import" | "subdivision_export" | "sales_table";
+
+export type JobStatus = "queued" | "running" | "succeeded" | "failed" | "cancelled";
+
+export type LandUse = "street" | "green_area" | "institutional" | "preservation";
+
+export interface LandUseBreakdown {
+    subdivision_id: string;
+    total_area_m2: number;
+    uses: LandUseShare[];
+}
+
+export interface LandUseShare {
+    area_m2: number;
+    land_use: string;
+    percentage: number;
+}
+
+export interface LoginPayload {
+    password: string;
+    username: string;
+}
+
+export interface LotBatchReport {
+    created: number;
+    failed: number;
+    lots: LotBatchResult[];
+    skipped: number;
+    subdivision_id: string;
+}
+
+export interface LotBatchResult {
+    index: number;
+    name: string;
+    reasons: string[];
+    status: LotBatchStatus;
+}
+
+export type LotBatchStatus = "created" | "skipped" | "failed";
+
+export interface LotDto {
+    area: [number, number][];
+    block_name?: string | null;
+    crs?: string | null;
+    id: string;
+    name: string;
+    price?: number | null;
+    status?: null | LotStatus;
+    subdivision_id: string;
+}
+
+export interface LotGridPayload {
+    area: [number, number][];
+    block_name: string;
+    crs?: string | null;
+    depth_azimuth: number;
+    first_number?: number | null;
+    frontage: number;
+}
+
+export type LotStatus = "available" | "sold";
+
+export type MemorialFormat = "text" | "pdf";
+
+export interface MonthlySales {
+    month: string;
+    revenue: number;
+    sales: number;
+}
+
+export interface Organization {
+    id: string;
+    name: string;
+}
+
+export interface OrganizationAnalytics {
+    name: string;
+    organization_id: string;
+    totals: SalesTotals;
+}
+
+export interface OrganizationDto {
+    id?: string | null;
+    name: string;
+}
+
+export interface OrganizationMemberPayload {
+    user_id: string;
+}
+
+export interface ProblemDetails {
+    code: string;
+    detail: string;
+    errors?: FieldError[];
+    status: number;
+    title: string;
+    type: string;
+}
+
+export interface RefreshTokenPayload {
+    refresh_token: string;
+}
+
+export type Role = "admin" | "developer" | "broker" | "buyer";
+
+export interface RoleUpdatePayload {
+    role: Role;
+}
+
+export interface SaleDto {
+    buyer_document: string;
+    buyer_name: string;
+    id?: string | null;
+    installments: InstallmentDto[];
+    lot_name: string;
+    price: number;
+    subdivision_id: string;
+}
+
+export interface SalesTotals {
+    available_lots: number;
+    average_price_per_m2?: number | null;
+    converted_reservations: number;
+    lots_amount: number;
+    monthly_sales: MonthlySales[];
+    period_sales: number;
+    reservation_conversion_rate?: number | null;
+    reservations: number;
+    revenue: number;
+    sold_area: number;
+    sold_lots: number;
+}
+
+export interface SiteFeatureDto {
+    area_m2?: number | null;
+    coordinates: [number, number][];
+    crs?: string | null;
+    geometry_kind: GeometryKind;
+    id?: string | null;
+    land_use: LandUse;
+    name: string;
+    width?: number | null;
+}
+
+export interface SubdivisionAnalytics {
+    name: string;
+    organization_id: string;
+    subdivision_id: string;
+    totals: SalesTotals;
+}
+
+export interface SubdivisionDto {
+    area: [number, number][];
+    crs?: string | null;
+    id: string;
+    lots?: LotDto[] | null;
+    name: string;
+    organization_id?: string | null;
+}
+
+export interface SubdivisionPreview {
+    id: string;
+    lots_amount: number;
+    name: string;
+    organization_id: string;
+}
+
+export interface SubscriptionPayload {
+    name: string;
+    password: string;
+    username: string;
+}
+
+export interface User {
+    api_key_id?: string | null;
+    id: string;
+    name: string;
+    organization_ids: string[];
+    role: Role;
+    username: string;
+}
+
+// auth
+
+// POST /api/real-estate/login
+export const login = (body: LoginPayload): Promise<AuthTokenDto> =>
+    send<AuthTokenDto>("POST", `/api/real-estate/login`, { body, responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/logout
+export const logout = (body: RefreshTokenPayload): Promise<void> =>
+    send<void>("POST", `/api/real-estate/logout`, { body, responseType: "json", accepted: [204] });
+
+// POST /api/real-estate/subscribe
+export const subscribe = (body: SubscriptionPayload): Promise<User> =>
+    send<User>("POST", `/api/real-estate/subscribe`, { body, responseType: "json", accepted: [201] });
+
+// POST /api/real-estate/token/refresh
+export const tokenRefresh = (body: RefreshTokenPayload): Promise<AuthTokenDto> =>
+    send<AuthTokenDto>("POST", `/api/real-estate/token/refresh`, { body, responseType: "json", accepted: [200] });
+
+// users
+
+// PUT /api/real-estate/users/{user_id}/role
+export const userRoleUpdate = (userId: string, body: RoleUpdatePayload): Promise<void> =>
+    send<void>("PUT", `/api/real-estate/users/${encodeURIComponent(userId)}/role`, { body, responseType: "json", accepted: [204] });
+
+// DELETE /api/real-estate/users/{user_id}/sessions
+export const userSessionsRevocation = (userId: string): Promise<void> =>
+    send<void>("DELETE", `/api/real-estate/users/${encodeURIComponent(userId)}/sessions`, { responseType: "json", accepted: [204] });
+
+// organizations
+
+// GET /api/real-estate/organizations
+export const organizationListing = (): Promise<Organization[]> =>
+    send<Organization[]>("GET", `/api/real-estate/organizations`, { responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/organizations
+export const organizationCreation = (body: OrganizationDto, headers: { "Idempotency-Key"?: string } = {}): Promise<string> =>
+    send<string>("POST", `/api/real-estate/organizations`, { body, headers, responseType: "json", accepted: [201] });
+
+// POST /api/real-estate/organizations/{organization_id}/members
+export const organizationMemberAddition = (organizationId: string, body: OrganizationMemberPayload, headers: { "Idempotency-Key"?: string } = {}): Promise<void> =>
+    send<void>("POST", `/api/real-estate/organizations/${encodeURIComponent(organizationId)}/members`, { body, headers, responseType: "json", accepted: [204] });
+
+// DELETE /api/real-estate/organizations/{organization_id}/members/{user_id}
+export const organizationMemberRemoval = (organizationId: string, userId: string): Promise<void> =>
+    send<void>("DELETE", `/api/real-estate/organizations/${encodeURIComponent(organizationId)}/members/${encodeURIComponent(userId)}`, { responseType: "json", accepted: [204] });
+
+// api-keys
+
+// GET /api/real-estate/organizations/{organization_id}/api-keys
+export const apiKeyListing = (organizationId: string): Promise<ApiKeyDto[]> =>
+    send<ApiKeyDto[]>("GET", `/api/real-estate/organizations/${encodeURIComponent(organizationId)}/api-keys`, { responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/organizations/{organization_id}/api-keys
+export const apiKeyCreation = (organizationId: string, body: ApiKeyCreationPayload, headers: { "Idempotency-Key"?: string } = {}): Promise<ApiKeyDto> =>
+    send<ApiKeyDto>("POST", `/api/real-estate/organizations/${encodeURIComponent(organizationId)}/api-keys`, { body, headers, responseType: "json", accepted: [201] });
+
+// DELETE /api/real-estate/organizations/{organization_id}/api-keys/{api_key_id}
+export const apiKeyRevocation = (organizationId: string, apiKeyId: string): Promise<void> =>
+    send<void>("DELETE", `/api/real-estate/organizations/${encodeURIComponent(organizationId)}/api-keys/${encodeURIComponent(apiKeyId)}`, { responseType: "json", accepted: [204] });
+
+// subdivisions
+
+// GET /api/real-estate/subdivisions
+export const subdivisionListing = (): Promise<SubdivisionPreview[]> =>
+    send<SubdivisionPreview[]>("GET", `/api/real-estate/subdivisions`, { responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/subdivisions
+export const subdivisionCreation = (body: SubdivisionDto, headers: { "Idempotency-Key"?: string } = {}): Promise<string> =>
+    send<string>("POST", `/api/real-estate/subdivisions`, { body, headers, responseType: "json", accepted: [200] });
+
+// GET /api/real-estate/subdivisions/search
+export const subdivisionSearching = (query: { name?: string; lat?: number; long?: number; crs?: string } = {}): Promise<SubdivisionDto[]> =>
+    send<SubdivisionDto[]>("GET", `/api/real-estate/subdivisions/search`, { query, responseType: "json", accepted: [200] });
+
+// lots
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lot-grid
+export const lotGridCreation = (subdivisionId: string, body: LotGridPayload, headers: { "Idempotency-Key"?: string } = {}): Promise<LotDto[]> =>
+    send<LotDto[]>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lot-grid`, { body, headers, responseType: "json", accepted: [201] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lot-grid/preview
+export const lotGridPreview = (subdivisionId: string, body: LotGridPayload, headers: { "Idempotency-Key"?: string } = {}): Promise<LotDto[]> =>
+    send<LotDto[]>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lot-grid/preview`, { body, headers, responseType: "json", accepted: [200] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/lots
+export const subdivisionLotsRetrieval = (subdivisionId: string, query: { crs?: string } = {}): Promise<LotDto[]> =>
+    send<LotDto[]>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots`, { query, responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lots
+export const lotCreation = (subdivisionId: string, body: LotDto, headers: { "Idempotency-Key"?: string } = {}): Promise<string> =>
+    send<string>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots`, { body, headers, responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lots/batch-creation
+export const lotsCreation = (subdivisionId: string, body: LotDto[], headers: { "Idempotency-Key"?: string } = {}): Promise<LotBatchReport> =>
+    send<LotBatchReport>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots/batch-creation`, { body, headers, responseType: "json", accepted: [200] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/lots/{lot_name}/memorial
+export const lotMemorial = (subdivisionId: string, lotName: string, query: { format?: MemorialFormat } = {}): Promise<ArrayBuffer> =>
+    send<ArrayBuffer>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots/${encodeURIComponent(lotName)}/memorial`, { query, responseType: "arraybuffer", accepted: [200] });
+
+// blocks
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/blocks
+export const blockListing = (subdivisionId: string): Promise<BlockSummary[]> =>
+    send<BlockSummary[]>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/blocks`, { responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/blocks
+export const blockCreation = (subdivisionId: string, body: BlockDto, headers: { "Idempotency-Key"?: string } = {}): Promise<string> =>
+    send<string>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/blocks`, { body, headers, responseType: "json", accepted: [201] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/blocks/{block_name}/lots
+export const blockLotsRetrieval = (subdivisionId: string, blockName: string): Promise<LotDto[]> =>
+    send<LotDto[]>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/blocks/${encodeURIComponent(blockName)}/lots`, { responseType: "json", accepted: [200] });
+
+// site-features
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/features
+export const siteFeaturesRetrieval = (subdivisionId: string): Promise<SiteFeatureDto[]> =>
+    send<SiteFeatureDto[]>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/features`, { responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/features
+export const siteFeatureCreation = (subdivisionId: string, body: SiteFeatureDto, headers: { "Idempotency-Key"?: string } = {}): Promise<string> =>
+    send<string>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/features`, { body, headers, responseType: "json", accepted: [201] });
+
+// DELETE /api/real-estate/subdivisions/{subdivision_id}/features/{feature_id}
+export const siteFeatureRemoval = (subdivisionId: string, featureId: string): Promise<void> =>
+    send<void>("DELETE", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/features/${encodeURIComponent(featureId)}`, { responseType: "json", accepted: [204] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/land-use
+export const landUseBreakdown = (subdivisionId: string): Promise<LandUseBreakdown> =>
+    send<LandUseBreakdown>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/land-use`, { responseType: "json", accepted: [200] });
+
+// imports
+
+// POST /api/real-estate/subdivisions/import/geojson
+export const geojsonImport = (body: GeoJsonImportPayload, headers: { "Idempotency-Key"?: string } = {}): Promise<ImportReport> =>
+    send<ImportReport>("POST", `/api/real-estate/subdivisions/import/geojson`, { body, headers, responseType: "json", accepted: [201, 422] });
+
+// POST /api/real-estate/subdivisions/import/geojson
+export const geojsonImportAsync = (body: GeoJsonImportPayload, headers: { "Idempotency-Key"?: string } = {}): Promise<JobDto> =>
+    send<JobDto>("POST", `/api/real-estate/subdivisions/import/geojson`, { body, headers: { ...headers, Prefer: "respond-async" }, responseType: "json", accepted: [202] });
+
+// POST /api/real-estate/subdivisions/import/shapefile
+export const shapefileImport = (body: BinaryFile, query: { subdivision_id: string; organization_id?: string; name_field?: string; kind_field?: string; price_field?: string }, headers: { "Idempotency-Key"?: string } = {}): Promise<ImportReport> =>
+    send<ImportReport>("POST", `/api/real-estate/subdivisions/import/shapefile`, { body, contentType: "application/zip", query, headers, responseType: "json", accepted: [201, 422] });
+
+// POST /api/real-estate/subdivisions/import/shapefile
+export const shapefileImportAsync = (body: BinaryFile, query: { subdivision_id: string; organization_id?: string; name_field?: string; kind_field?: string; price_field?: string }, headers: { "Idempotency-Key"?: string } = {}): Promise<JobDto> =>
+    send<JobDto>("POST", `/api/real-estate/subdivisions/import/shapefile`, { body, contentType: "application/zip", query, headers: { ...headers, Prefer: "respond-async" }, responseType: "json", accepted: [202] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lots/import
+export const spreadsheetImport = (subdivisionId: string, body: BinaryFile, contentType: "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" | "text/csv", query: { partial?: boolean; crs?: string } = {}, headers: { "Idempotency-Key"?: string } = {}): Promise<ImportReport> =>
+    send<ImportReport>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots/import`, { body, contentType, query, headers, responseType: "json", accepted: [201, 422] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lots/import
+export const spreadsheetImportAsync = (subdivisionId: string, body: BinaryFile, contentType: "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" | "text/csv", query: { partial?: boolean; crs?: string } = {}, headers: { "Idempotency-Key"?: string } = {}): Promise<JobDto> =>
+    send<JobDto>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots/import`, { body, contentType, query, headers: { ...headers, Prefer: "respond-async" }, responseType: "json", accepted: [202] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lots/import/dxf
+export const dxfImport = (subdivisionId: string, body: string, query: { lot_layer: string; label_layer: string; utm_zone: number; hemisphere?: Hemisphere }, headers: { "Idempotency-Key"?: string } = {}): Promise<ImportReport> =>
+    send<ImportReport>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots/import/dxf`, { body, contentType: "application/dxf", query, headers, responseType: "json", accepted: [201, 422] });
+
+// POST /api/real-estate/subdivisions/{subdivision_id}/lots/import/dxf
+export const dxfImportAsync = (subdivisionId: string, body: string, query: { lot_layer: string; label_layer: string; utm_zone: number; hemisphere?: Hemisphere }, headers: { "Idempotency-Key"?: string } = {}): Promise<JobDto> =>
+    send<JobDto>("POST", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/lots/import/dxf`, { body, contentType: "application/dxf", query, headers: { ...headers, Prefer: "respond-async" }, responseType: "json", accepted: [202] });
+
+// exports
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/export
+export const subdivisionExport = (subdivisionId: string, query: { format?: ExportFormat } = {}): Promise<ArrayBuffer> =>
+    send<ArrayBuffer>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/export`, { query, responseType: "arraybuffer", accepted: [200] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/export
+export const subdivisionExportAsync = (subdivisionId: string, query: { format?: ExportFormat } = {}): Promise<JobDto> =>
+    send<JobDto>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/export`, { query, headers: { Prefer: "respond-async" }, responseType: "json", accepted: [202] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/sales-table
+export const salesTable = (subdivisionId: string, query: { available_only?: boolean } = {}): Promise<ArrayBuffer> =>
+    send<ArrayBuffer>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/sales-table`, { query, responseType: "arraybuffer", accepted: [200] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/sales-table
+export const salesTableAsync = (subdivisionId: string, query: { available_only?: boolean } = {}): Promise<JobDto> =>
+    send<JobDto>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/sales-table`, { query, headers: { Prefer: "respond-async" }, responseType: "json", accepted: [202] });
+
+// maps
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/plat.png
+export const platPng = (subdivisionId: string, query: { selected_lot?: string; width?: number } = {}): Promise<ArrayBuffer> =>
+    send<ArrayBuffer>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/plat.png`, { query, responseType: "arraybuffer", accepted: [200] });
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/plat.svg
+export const platSvg = (subdivisionId: string, query: { selected_lot?: string; width?: number } = {}): Promise<string> =>
+    send<string>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/plat.svg`, { query, responseType: "text", accepted: [200] });
+
+// GET /api/real-estate/tiles/{z}/{x}/{tile}
+export const tile = (z: number, x: number, tile: string): Promise<ArrayBuffer> =>
+    send<ArrayBuffer>("GET", `/api/real-estate/tiles/${encodeURIComponent(z)}/${encodeURIComponent(x)}/${encodeURIComponent(tile)}`, { responseType: "arraybuffer", accepted: [200] });
+
+// jobs
+
+// GET /api/real-estate/jobs/{job_id}
+export const jobStatus = (jobId: string): Promise<JobDto> =>
+    send<JobDto>("GET", `/api/real-estate/jobs/${encodeURIComponent(jobId)}`, { responseType: "json", accepted: [200] });
+
+// POST /api/real-estate/jobs/{job_id}/cancel
+export const jobCancellation = (jobId: string, headers: { "Idempotency-Key"?: string } = {}): Promise<JobDto> =>
+    send<JobDto>("POST", `/api/real-estate/jobs/${encodeURIComponent(jobId)}/cancel`, { headers, responseType: "json", accepted: [200] });
+
+// GET /api/real-estate/jobs/{job_id}/result
+export const jobResult = (jobId: string): Promise<ArrayBuffer> =>
+    send<ArrayBuffer>("GET", `/api/real-estate/jobs/${encodeURIComponent(jobId)}/result`, { responseType: "arraybuffer", accepted: [200] });
+
+// contracts
+
+// GET /api/real-estate/subdivisions/{subdivision_id}/contract-template
+export const contractTemplateRetrieval = (subdivisionId: string): Promise<ContractTemplateDto> =>
+    send<ContractTemplateDto>("GET", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/contract-template`, { responseType: "json", accepted: [200] });
+
+// PUT /api/real-estate/subdivisions/{subdivision_id}/contract-template
+export const contractTemplateUpdate = (subdivisionId: string, body: ContractTemplateDto): Promise<void> =>
+    send<void>("PUT", `/api/real-estate/subdivisions/${encodeURIComponent(subdivisionId)}/contract-template`, { body, responseType: "json", accepted: [204] });
+
+// sales
+
+// POST /api/real-estate/sales
+export const saleCreation = (body: SaleDto, headers: { "Idempotency-Key"?: string } = {}): Promise<string> =>
+    send<string>("POST", `/api/real-estate/sales`, { body, headers, responseType: "json", accepted: [201] });
+
+// GET /api/real-estate/sales/{sale_id}/contract
+export const saleContractRetrieval = (saleId: string): Promise<ArrayBuffer> =>
+    send<ArrayBuffer>("GET", `/api/real-estate/sales/${encodeURIComponent(saleId)}/contract`, { responseType: "arraybuffer", accepted: [200] });
+
+// analytics
+
+// GET /api/real-estate/analytics
+export const analytics = (query: { from?: string; to?: string; organization_id?: string } = {}): Promise<AnalyticsReport> =>
+    send<AnalyticsReport>("GET", `/api/real-estate/analytics`, { query, responseType: "json", accepted: [200] });